@@ -0,0 +1,89 @@
+#![feature(test)]
+
+extern crate test;
+extern crate interpolation;
+
+use interpolation::{ease_slice, lerp_slice, lerp_soa, Ease, EaseFunction, Lerp};
+
+// Run with and without the `simd` feature to compare the batch functions
+// with the scalar loops below.
+
+fn input() -> Vec<f32> {
+    (0..4096).map(|x| x as f32 / 4095.0).collect()
+}
+
+#[bench]
+fn bench_lerp_loop(bencher: &mut test::Bencher) {
+    let (a, b) = (input(), input());
+    let mut out = vec![0.0; a.len()];
+    bencher.iter(|| {
+        for ((o, a), b) in out.iter_mut().zip(&a).zip(&b) {
+            *o = a + (b - a) * 0.5;
+        }
+        test::black_box(&out);
+    })
+}
+
+#[bench]
+fn bench_lerp_slice(bencher: &mut test::Bencher) {
+    let (a, b) = (input(), input());
+    let mut out = vec![0.0; a.len()];
+    bencher.iter(|| {
+        lerp_slice(&a, &b, 0.5, &mut out);
+        test::black_box(&out);
+    })
+}
+
+#[bench]
+fn bench_lerp_aos(bencher: &mut test::Bencher) {
+    let a: Vec<[f32; 3]> = input().into_iter().map(|x| [x, x, x]).collect();
+    let (b, t) = (a.clone(), input());
+    let mut out = vec![[0.0; 3]; a.len()];
+    bencher.iter(|| {
+        for (((o, a), b), t) in out.iter_mut().zip(&a).zip(&b).zip(&t) {
+            *o = a.lerp(b, t);
+        }
+        test::black_box(&out);
+    })
+}
+
+#[bench]
+fn bench_lerp_soa(bencher: &mut test::Bencher) {
+    let (a, b, t) = (input(), input(), input());
+    let mut out = vec![0.0; a.len()];
+    bencher.iter(|| {
+        for _ in 0..3 {
+            lerp_soa(&a, &b, &t, &mut out);
+        }
+        test::black_box(&out);
+    })
+}
+
+macro_rules! bench_batch {
+    ($loop_name: ident, $slice_name: ident, $f: ident) => (
+        #[bench]
+        fn $loop_name(bencher: &mut test::Bencher) {
+            let mut values = input();
+            bencher.iter(|| {
+                for v in values.iter_mut() {
+                    *v = v.calc(EaseFunction::$f);
+                }
+                test::black_box(&values);
+            })
+        }
+
+        #[bench]
+        fn $slice_name(bencher: &mut test::Bencher) {
+            let mut values = input();
+            bencher.iter(|| {
+                ease_slice(EaseFunction::$f, &mut values);
+                test::black_box(&values);
+            })
+        }
+    )
+}
+
+bench_batch!(bench_quadratic_in_out_loop, bench_quadratic_in_out_slice, QuadraticInOut);
+bench_batch!(bench_cubic_in_out_loop, bench_cubic_in_out_slice, CubicInOut);
+bench_batch!(bench_quartic_in_out_loop, bench_quartic_in_out_slice, QuarticInOut);
+bench_batch!(bench_quintic_in_out_loop, bench_quintic_in_out_slice, QuinticInOut);
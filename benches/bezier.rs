@@ -0,0 +1,70 @@
+#![feature(test)]
+
+extern crate test;
+extern crate interpolation;
+
+use interpolation::{cub_bez, cub_bez_poly, quad_bez, quad_bez_poly, CubicPoly};
+
+// Compares the nested lerp form of the Bézier functions with the polynomial form.
+
+const POINTS: [[f32; 2]; 4] = [[0.0, 0.0], [0.25, 1.0], [0.75, -1.0], [1.0, 0.0]];
+
+fn params() -> Vec<f32> {
+    (0..1024).map(|x| x as f32 / 1023.0).collect()
+}
+
+#[bench]
+fn bench_quad_bez(bencher: &mut test::Bencher) {
+    let [a, b, c, _] = POINTS;
+    let ts = params();
+    bencher.iter(|| {
+        for t in &ts {
+            test::black_box(quad_bez(&a, &b, &c, t));
+        }
+    })
+}
+
+#[bench]
+fn bench_quad_bez_poly(bencher: &mut test::Bencher) {
+    let [a, b, c, _] = POINTS;
+    let ts = params();
+    bencher.iter(|| {
+        for t in &ts {
+            test::black_box(quad_bez_poly(&a, &b, &c, t));
+        }
+    })
+}
+
+#[bench]
+fn bench_cub_bez(bencher: &mut test::Bencher) {
+    let [a, b, c, d] = POINTS;
+    let ts = params();
+    bencher.iter(|| {
+        for t in &ts {
+            test::black_box(cub_bez(&a, &b, &c, &d, t));
+        }
+    })
+}
+
+#[bench]
+fn bench_cub_bez_poly(bencher: &mut test::Bencher) {
+    let [a, b, c, d] = POINTS;
+    let ts = params();
+    bencher.iter(|| {
+        for t in &ts {
+            test::black_box(cub_bez_poly(&a, &b, &c, &d, t));
+        }
+    })
+}
+
+#[bench]
+fn bench_cubic_poly_eval(bencher: &mut test::Bencher) {
+    let [a, b, c, d] = POINTS;
+    let poly = CubicPoly::from_bezier(&a, &b, &c, &d);
+    let ts = params();
+    bencher.iter(|| {
+        for t in &ts {
+            test::black_box(poly.eval(t));
+        }
+    })
+}
@@ -3,7 +3,7 @@
 extern crate test;
 extern crate interpolation;
 
-use interpolation::Ease;
+use interpolation::{Ease, EaseFunction};
 
 macro_rules! bench_ease {
     ($name: ident, $T: ident, $f: ident) => (
@@ -109,3 +109,24 @@ bench_ease!(bench_bounce_out_f64, f64, bounce_out);
 bench_ease!(bench_bounce_in_out_f32, f32, bounce_in_out);
 bench_ease!(bench_bounce_in_out_f64, f64, bounce_in_out);
 
+
+macro_rules! bench_dispatch {
+    ($name: ident, $T: ident, $calc: expr) => (
+        #[bench]
+        fn $name(bencher: &mut test::Bencher) {
+            let values = (0..11).map(|x| x as $T / 10.0).collect::<Vec<$T>>();
+            bencher.iter(|| {
+                for &f in EaseFunction::ALL.iter() {
+                    for &x in values.iter() {
+                        test::black_box($calc(f, x));
+                    }
+                }
+            })
+        }
+    )
+}
+
+bench_dispatch!(bench_dispatch_generic_f32, f32, |f, x: f32| x.calc(f));
+bench_dispatch!(bench_dispatch_generic_f64, f64, |f, x: f64| x.calc(f));
+bench_dispatch!(bench_dispatch_calc_f32, f32, EaseFunction::calc_f32);
+bench_dispatch!(bench_dispatch_calc_f64, f64, EaseFunction::calc_f64);
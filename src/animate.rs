@@ -0,0 +1,62 @@
+//! Values that change over time
+
+/// Describes a value that changes over time.
+///
+/// This is implemented by tweens and other time driven types,
+/// so adapters can be used with any of them.
+pub trait Animate {
+    /// The animated value.
+    type Value;
+    /// The type used for time.
+    type Time;
+
+    /// Advances the animation by `dt`.
+    fn update(&mut self, dt: Self::Time);
+
+    /// Returns the current value.
+    fn value(&self) -> Self::Value;
+
+    /// Returns `true` when the animation will not change any more.
+    /// Animations that run forever never finish.
+    fn is_finished(&self) -> bool {
+        false
+    }
+}
+
+/// An animation with attached user data, such as an entity id.
+///
+/// The data is carried along through managers and completion callbacks,
+/// so animations can be mapped back to their owners.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tagged<A, D> {
+    /// The animation.
+    pub animation: A,
+    /// The user data.
+    pub data: D,
+}
+
+impl<A, D> Tagged<A, D> {
+    /// Attaches `data` to `animation`.
+    pub fn new(animation: A, data: D) -> Tagged<A, D> {
+        Tagged { animation, data }
+    }
+}
+
+impl<A, D> Animate for Tagged<A, D>
+    where A: Animate
+{
+    type Value = A::Value;
+    type Time = A::Time;
+
+    fn update(&mut self, dt: A::Time) {
+        self.animation.update(dt)
+    }
+
+    fn value(&self) -> A::Value {
+        self.animation.value()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.animation.is_finished()
+    }
+}
@@ -0,0 +1,213 @@
+//! Evaluation of curves at a distance along them
+//!
+//! The parameter `t` of a curve does not move at constant speed,
+//! so evenly spaced parameters give unevenly spaced points.
+//! `PreparedCurve` measures a curve once, and then maps a distance
+//! or a fraction of the length back to the parameter, so objects
+//! can be animated along the curve at constant speed.
+
+use core::cmp::Ordering;
+
+use float::Float;
+
+/// Describes a curve with a parameter from 0 to 1, which can be measured.
+pub trait ParametricCurve {
+    /// The type of the points on the curve.
+    type Point;
+    /// The type of the parameter and of lengths.
+    type Scalar: Float;
+
+    /// Returns the point at `t`.
+    fn point_at(&self, t: Self::Scalar) -> Self::Point;
+
+    /// Returns the speed at `t`, the length of the derivative.
+    fn speed_at(&self, t: Self::Scalar) -> Self::Scalar;
+}
+
+/// The number of segments in the table of a `PreparedCurve`.
+const TABLE_SEGMENTS: usize = 16;
+
+/// The maximum number of Newton steps when solving for a parameter.
+const NEWTON_STEPS: u32 = 8;
+
+/// A curve with a table of arc lengths, for evaluation at a distance along it.
+///
+/// The table stores the length at 16 evenly spaced parameters.
+/// A parameter is looked up in the table and refined with Newton's method,
+/// which falls back to bisection where the speed is zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedCurve<C: ParametricCurve> {
+    curve: C,
+    lengths: [C::Scalar; TABLE_SEGMENTS + 1],
+}
+
+impl<C: ParametricCurve> PreparedCurve<C> {
+    /// Measures a curve.
+    pub fn new(curve: C) -> PreparedCurve<C> {
+        let zero = C::Scalar::zero();
+        let mut lengths = [zero; TABLE_SEGMENTS + 1];
+        let mut total = zero;
+        for (i, length) in lengths.iter_mut().enumerate().skip(1) {
+            total = total + integrate_speed(|t| curve.speed_at(t), param(i - 1), param(i));
+            *length = total;
+        }
+        PreparedCurve { curve, lengths }
+    }
+
+    /// Returns the curve.
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the curve, dropping the table.
+    pub fn into_curve(self) -> C {
+        self.curve
+    }
+
+    /// Returns the length of the curve.
+    pub fn length(&self) -> C::Scalar {
+        let [.., length] = self.lengths;
+        length
+    }
+
+    /// Returns the parameter at `distance` along the curve.
+    /// `distance` is clamped to the ends of the curve, and NaN gives the start.
+    pub fn t_at_length(&self, distance: C::Scalar) -> C::Scalar {
+        let (zero, one) = (C::Scalar::zero(), C::Scalar::one());
+        let length = self.length();
+        let positive = |x: C::Scalar| x.partial_cmp(&zero) == Some(Ordering::Greater);
+        if !positive(length) || !positive(distance) {
+            return zero;
+        } else if distance >= length {
+            return one;
+        }
+        let i = self.lengths.partition_point(|&l| l <= distance).clamp(1, TABLE_SEGMENTS);
+        let (start, end) = match (self.lengths.get(i - 1), self.lengths.get(i)) {
+            (Some(&start), Some(&end)) => (start, end),
+            _ => return zero,
+        };
+        let (mut lo, mut hi) = (param(i - 1), param(i));
+        let local = distance - start;
+        let mut t = if end > start { lo + (hi - lo) * local / (end - start) } else { lo };
+        let tolerance = C::Scalar::from_f64(1e-7) * length;
+        for _ in 0..NEWTON_STEPS {
+            let error = integrate_speed(|t| self.curve.speed_at(t), param::<C::Scalar>(i - 1), t) - local;
+            if error < tolerance && -error < tolerance {
+                break;
+            } else if error > zero {
+                hi = t;
+            } else {
+                lo = t;
+            }
+            let speed = self.curve.speed_at(t);
+            let next = if speed > zero { t - error / speed } else { lo };
+            t = if next > lo && next < hi { next } else { (lo + hi) * C::Scalar::from_f64(0.5) };
+        }
+        t
+    }
+
+    /// Returns the parameter at a fraction of the length, from 0 at the start to 1 at the end.
+    pub fn t_at_fraction(&self, fraction: C::Scalar) -> C::Scalar {
+        self.t_at_length(fraction * self.length())
+    }
+
+    /// Returns the point at `distance` along the curve,
+    /// so evenly spaced distances give evenly spaced points.
+    pub fn eval_at_length(&self, distance: C::Scalar) -> C::Point {
+        self.curve.point_at(self.t_at_length(distance))
+    }
+
+    /// Returns the point at a fraction of the length, from 0 at the start to 1 at the end.
+    pub fn eval_at_fraction(&self, fraction: C::Scalar) -> C::Point {
+        self.curve.point_at(self.t_at_fraction(fraction))
+    }
+}
+
+/// Returns the parameter at entry `i` of the table.
+fn param<S: Float>(i: usize) -> S {
+    S::from_f64(i as f64 / TABLE_SEGMENTS as f64)
+}
+
+/// The nodes and weights of 8 point Gauss-Legendre quadrature on `[-1, 1]`,
+/// for one half of the symmetric nodes.
+const GAUSS_LEGENDRE_8: [(f64, f64); 4] = [
+    (0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (0.960_289_856_497_536_3, 0.101_228_536_290_376_3),
+];
+
+/// The maximum number of times an interval is halved when integrating arc length.
+const ARC_LENGTH_DEPTH: u32 = 8;
+
+/// Integrates `speed` from `a` to `b` with Gauss-Legendre quadrature.
+pub(crate) fn gauss_legendre<S, F>(speed: &F, a: S, b: S) -> S
+    where S: Float, F: Fn(S) -> S
+{
+    let half = S::from_f64(0.5);
+    let (mid, radius) = ((a + b) * half, (b - a) * half);
+    GAUSS_LEGENDRE_8.iter().fold(S::zero(), |sum, &(x, w)| {
+        let x = S::from_f64(x) * radius;
+        sum + S::from_f64(w) * (speed(mid - x) + speed(mid + x))
+    }) * radius
+}
+
+/// Integrates `speed` from `a` to `b`, halving the interval until the halves
+/// agree with the whole to within `tolerance`.
+fn adaptive<S, F>(speed: &F, a: S, b: S, whole: S, tolerance: S, depth: u32) -> S
+    where S: Float, F: Fn(S) -> S
+{
+    let mid = (a + b) * S::from_f64(0.5);
+    let (left, right) = (gauss_legendre(speed, a, mid), gauss_legendre(speed, mid, b));
+    let error = left + right - whole;
+    if depth == 0 || (error < tolerance && -error < tolerance) {
+        return left + right;
+    }
+    let tolerance = tolerance * S::from_f64(0.5);
+    adaptive(speed, a, mid, left, tolerance, depth - 1) + adaptive(speed, mid, b, right, tolerance, depth - 1)
+}
+
+/// Integrates the speed along a curve from `t0` to `t1`,
+/// to a relative error of about `1e-6`.
+pub(crate) fn integrate_speed<S, F>(speed: F, t0: S, t1: S) -> S
+    where S: Float, F: Fn(S) -> S
+{
+    let whole = gauss_legendre(&speed, t0, t1);
+    let tolerance = S::from_f64(1e-6) * if whole < S::zero() { -whole } else { whole };
+    adaptive(&speed, t0, t1, whole, tolerance, ARC_LENGTH_DEPTH)
+}
+
+#[test]
+fn prepared_curve_constant_speed() {
+    use bezier::CubicBezier;
+
+    let curve = CubicBezier::new([0.0f64, 0.0], [0.1, 3.0], [3.0, 3.0], [4.0, 0.0]);
+    let prepared = curve.prepared();
+    assert!((prepared.length() - curve.length()).abs() < 1e-9);
+    for i in 0..=10 {
+        let s = i as f64 / 10.0;
+        let t = prepared.t_at_fraction(s);
+        assert!((curve.arc_length(&0.0, &t) - s * prepared.length()).abs() < 1e-6);
+    }
+    assert_eq!(prepared.t_at_length(-1.0), 0.0);
+    assert_eq!(prepared.t_at_length(f64::NAN), 0.0);
+    assert_eq!(prepared.t_at_length(100.0), 1.0);
+    assert_eq!(prepared.eval_at_fraction(1.0), [4.0, 0.0]);
+    let p = prepared.eval_at_length(prepared.length() / 2.0);
+    assert_eq!(p, curve.sample(&prepared.t_at_fraction(0.5)));
+    assert_eq!(prepared.into_curve(), curve);
+}
+
+#[test]
+fn prepared_curve_degenerate() {
+    use bezier::{CubicBezier, QuadraticBezier};
+
+    // The speed is zero at the cusp, where Newton's method falls back to bisection.
+    let cusp = CubicBezier::new([0.0f32, 0.0], [2.0, 1.0], [0.0, 1.0], [2.0, 0.0]).prepared();
+    let t = cusp.t_at_fraction(0.5);
+    assert!((cusp.curve().arc_length(&0.0, &t) - cusp.length() / 2.0).abs() < 1e-4);
+    let point = QuadraticBezier::new(1.0f32, 1.0, 1.0).prepared();
+    assert_eq!(point.length(), 0.0);
+    assert_eq!(point.t_at_fraction(0.5), 0.0);
+    assert_eq!(point.eval_at_fraction(0.5), 1.0);
+}
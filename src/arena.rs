@@ -0,0 +1,268 @@
+//! Generational storage for many short lived animations
+
+use alloc::vec::Vec;
+
+use animate::Animate;
+
+/// A stable reference to a value stored in an `Arena`.
+///
+/// A handle stays valid until its value is removed.
+/// Slots are reused, but a stale handle never refers to a newer value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: u32,
+    generation: u32,
+}
+
+impl Handle {
+    /// Returns the slot index.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// Returns the generation of the slot when the handle was created.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Packs the handle into a `u64`, for storing as an id outside the crate.
+    pub fn to_bits(&self) -> u64 {
+        (u64::from(self.generation) << 32) | u64::from(self.index)
+    }
+
+    /// Unpacks a handle from a `u64` created by `to_bits`.
+    pub fn from_bits(bits: u64) -> Handle {
+        Handle {
+            index: bits as u32,
+            generation: (bits >> 32) as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+/// Stores values in reusable slots, addressed by generational handles.
+///
+/// Removed slots are put on a free list and reused by later inserts,
+/// so creating and destroying many tweens does not allocate once warmed up.
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Arena<T> {
+        Arena::new()
+    }
+}
+
+impl<T> Arena<T> {
+    /// Creates an empty arena.
+    pub fn new() -> Arena<T> {
+        Arena { slots: Vec::new(), free: Vec::new() }
+    }
+
+    /// Creates an empty arena with room for `capacity` values.
+    pub fn with_capacity(capacity: usize) -> Arena<T> {
+        Arena { slots: Vec::with_capacity(capacity), free: Vec::new() }
+    }
+
+    /// Inserts a value, returning its handle.
+    pub fn insert(&mut self, value: T) -> Handle {
+        if let Some(index) = self.free.pop() {
+            if let Some(slot) = self.slots.get_mut(index as usize) {
+                slot.value = Some(value);
+                return Handle { index, generation: slot.generation };
+            }
+        }
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot { generation: 0, value: Some(value) });
+        Handle { index, generation: 0 }
+    }
+
+    /// Removes a value, returning it if the handle was valid.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let value = match self.slots.get_mut(handle.index()) {
+            Some(slot) if slot.generation == handle.generation => {
+                let value = slot.value.take();
+                if value.is_some() {
+                    slot.generation = slot.generation.wrapping_add(1);
+                }
+                value
+            }
+            _ => None,
+        };
+        if value.is_some() {
+            self.free.push(handle.index);
+        }
+        value
+    }
+
+    /// Returns `true` if the handle refers to a value.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Returns the value of a handle.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        match self.slots.get(handle.index()) {
+            Some(slot) if slot.generation == handle.generation => slot.value.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a handle mutably.
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        match self.slots.get_mut(handle.index()) {
+            Some(slot) if slot.generation == handle.generation => slot.value.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of stored values.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Returns `true` if there are no stored values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of slots, occupied or free.
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of values that can be stored without allocating.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Returns the fraction of slots that are occupied.
+    pub fn occupancy(&self) -> f32 {
+        if self.slots.is_empty() {
+            0.0
+        } else {
+            self.len() as f32 / self.slots.len() as f32
+        }
+    }
+
+    /// Removes all values, invalidating every handle.
+    pub fn clear(&mut self) {
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(i as u32);
+            }
+        }
+    }
+
+    /// Returns the handle of the first value matching `f`.
+    pub fn find<F>(&self, mut f: F) -> Option<Handle>
+        where F: FnMut(&T) -> bool
+    {
+        self.iter().find(|&(_, v)| f(v)).map(|(handle, _)| handle)
+    }
+
+    /// Iterates over the stored values and their handles.
+    pub fn iter(&self) -> impl Iterator<Item = (Handle, &T)> {
+        self.slots.iter().enumerate().filter_map(|(i, slot)| {
+            let generation = slot.generation;
+            slot.value.as_ref().map(|v| (Handle { index: i as u32, generation }, v))
+        })
+    }
+
+    /// Iterates mutably over the stored values and their handles.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Handle, &mut T)> {
+        self.slots.iter_mut().enumerate().filter_map(|(i, slot)| {
+            let generation = slot.generation;
+            slot.value.as_mut().map(|v| (Handle { index: i as u32, generation }, v))
+        })
+    }
+}
+
+impl<A> Arena<A>
+    where A: Animate, A::Time: Copy
+{
+    /// Advances every stored animation by `dt`.
+    pub fn update(&mut self, dt: A::Time) {
+        for (_, animation) in self.iter_mut() {
+            animation.update(dt);
+        }
+    }
+
+    /// Removes finished animations, passing each of them to `f`.
+    pub fn remove_finished<F>(&mut self, mut f: F)
+        where F: FnMut(Handle, A)
+    {
+        for i in 0..self.slots.len() {
+            let generation = match self.slots.get(i) {
+                Some(&Slot { value: Some(ref animation), generation }) if animation.is_finished() => generation,
+                _ => continue,
+            };
+            let handle = Handle { index: i as u32, generation };
+            if let Some(animation) = self.remove(handle) {
+                f(handle, animation);
+            }
+        }
+    }
+}
+
+#[test]
+fn arena_reuse() {
+    let mut arena = Arena::new();
+    let a = arena.insert('a');
+    let b = arena.insert('b');
+    assert_eq!(arena.len(), 2);
+    assert_eq!(arena.remove(a), Some('a'));
+    assert_eq!(arena.remove(a), None);
+    assert_eq!(arena.occupancy(), 0.5);
+
+    let c = arena.insert('c');
+    assert_eq!(c.index(), a.index());
+    assert_eq!(arena.get(a), None);
+    assert_eq!(arena.get(c), Some(&'c'));
+    assert_eq!(arena.get(b), Some(&'b'));
+    assert_eq!(arena.slots(), 2);
+
+    arena.clear();
+    assert!(arena.is_empty());
+    assert!(!arena.contains(b));
+}
+
+#[test]
+fn arena_update() {
+    use tween::Tween;
+
+    let mut arena = Arena::new();
+    let short = arena.insert(Tween::new(0.0f32, 1.0, 1.0));
+    let long = arena.insert(Tween::new(0.0f32, 1.0, 2.0));
+    arena.update(1.0);
+    let mut finished = vec![];
+    arena.remove_finished(|handle, tween| finished.push((handle, tween.value())));
+    assert_eq!(finished, vec![(short, 1.0)]);
+    assert_eq!(arena.get(long).map(|t| t.value()), Some(0.5));
+}
+
+#[test]
+fn arena_tagged() {
+    use animate::Tagged;
+    use tween::Tween;
+
+    let mut arena = Arena::new();
+    let a = arena.insert(Tagged::new(Tween::new(0.0f32, 1.0, 1.0), 7u64));
+    let b = arena.insert(Tagged::new(Tween::new(0.0f32, 1.0, 2.0), 9u64));
+    assert_eq!(arena.find(|t| t.data == 9), Some(b));
+    assert_eq!(Handle::from_bits(a.to_bits()), a);
+
+    arena.update(1.0);
+    let mut entities = vec![];
+    arena.remove_finished(|_, tagged| entities.push(tagged.data));
+    assert_eq!(entities, vec![7]);
+}
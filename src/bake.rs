@@ -0,0 +1,244 @@
+//! Precomputed interpolation data
+//!
+//! Baking evaluates a curve or animation once, so it can be sampled cheaply
+//! or stored with the assets of a game.
+//! With the `rkyv` feature, the baked data can be archived and accessed
+//! in place from a memory mapped file, without parsing.
+//! With the `bincode` feature, it can be encoded in a compact binary format.
+//! With the `serde` feature, the samples are serialized as compact byte blobs.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use ease::Easing;
+use lerp::Lerp;
+use progress::ProgressMode;
+
+/// Samples a table of `len` evenly spaced values at `t` from zero to one,
+/// interpolating linearly between the values.
+fn sample_table<F>(len: usize, t: f32, get: F) -> f32
+    where F: Fn(usize) -> f32
+{
+    match len {
+        0 => t,
+        1 => get(0),
+        _ => {
+            let last = len - 1;
+            let x = t.clamp(0.0, 1.0) * last as f32;
+            let i = (x as usize).min(last - 1);
+            get(i).lerp(&get(i + 1), &(x - i as f32))
+        }
+    }
+}
+
+/// Returns the index and fraction to interpolate at `time`
+/// in `len` values sampled every `step` from `start`.
+fn locate(start: f32, step: f32, len: usize, time: f32) -> (usize, f32) {
+    let x = if step > 0.0 { (time - start) / step } else { 0.0 };
+    let last = len.saturating_sub(1);
+    if x.is_nan() || x <= 0.0 {
+        (0, 0.0)
+    } else if x >= last as f32 {
+        (last, 0.0)
+    } else {
+        let i = x as usize;
+        (i, x - i as f32)
+    }
+}
+
+/// A lookup table of an easing curve, sampled at evenly spaced points.
+///
+/// Values between the samples are interpolated linearly.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[cfg_attr(feature = "rkyv", derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize))]
+#[cfg_attr(feature = "bincode", derive(::bincode::Encode, ::bincode::Decode))]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+pub struct EaseTable {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "::lut_serde::deserialize_samples"))]
+    samples: Vec<f32>,
+}
+
+impl EaseTable {
+    /// Bakes `n` samples of an easing, including both ends.
+    pub fn new<E: Into<Easing>>(easing: E, n: usize) -> EaseTable {
+        let easing = easing.into();
+        let samples = match n {
+            0 => vec![],
+            1 => vec![easing.calc(0.0f32)],
+            _ => (0..n).map(|i| easing.calc(i as f32 / (n - 1) as f32)).collect(),
+        };
+        EaseTable { samples }
+    }
+
+    /// Returns the samples.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Returns the eased value at `t`, clamped to the range from zero to one.
+    /// An empty table is linear.
+    pub fn sample(&self, t: f32) -> f32 {
+        sample_table(self.samples.len(), t, |i| self.samples.get(i).map_or(t, |&v| v))
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedEaseTable {
+    /// Returns the eased value at `t`, without deserializing the table.
+    pub fn sample(&self, t: f32) -> f32 {
+        sample_table(self.samples.len(), t, |i| self.samples.get(i).map_or(t, |v| v.to_native()))
+    }
+}
+
+/// The values of an animation, sampled at a fixed rate.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[cfg_attr(feature = "rkyv", derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize))]
+#[cfg_attr(feature = "bincode", derive(::bincode::Encode, ::bincode::Decode))]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "V: ::lut_serde::LutSample + ::serde::Deserialize<'de>")))]
+pub struct BakedTrack<V> {
+    /// The time of the first value.
+    pub start: f32,
+    /// The time between two values.
+    pub step: f32,
+    /// The sampled values.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "::lut_serde::deserialize_samples"))]
+    pub values: Vec<V>,
+}
+
+impl<V> BakedTrack<V> {
+    /// Bakes `n` values of `f`, sampled every `step` from `start`.
+    pub fn bake<F>(start: f32, step: f32, n: usize, mut f: F) -> BakedTrack<V>
+        where F: FnMut(f32) -> V
+    {
+        BakedTrack {
+            start,
+            step,
+            values: (0..n).map(|i| f(start + step * i as f32)).collect(),
+        }
+    }
+
+    /// Returns the time of the last value.
+    pub fn end(&self) -> f32 {
+        self.start + self.step * self.values.len().saturating_sub(1) as f32
+    }
+
+    /// Returns the values paired with their times, for exporting or plotting.
+    pub fn samples(&self) -> Vec<(f32, V)>
+        where V: Clone
+    {
+        self.values.iter().enumerate()
+            .map(|(i, v)| (self.start + self.step * i as f32, v.clone()))
+            .collect()
+    }
+}
+
+impl<V> BakedTrack<V>
+    where V: Lerp<Scalar = f32> + Clone
+{
+    /// Returns the value at `time`, interpolating linearly between the values.
+    /// The first and last values are held outside the baked range.
+    pub fn sample(&self, time: f32) -> Option<V> {
+        let (i, t) = locate(self.start, self.step, self.values.len(), time);
+        let a = self.values.get(i)?;
+        match self.values.get(i + 1) {
+            Some(b) if t != 0.0 => Some(a.lerp(b, &t)),
+            _ => Some(a.clone()),
+        }
+    }
+
+    /// Returns the value at `time`, with the time outside the baked range
+    /// mapped into it with `mode`, such as wrapped for a looping track.
+    pub fn sample_progress(&self, time: f32, mode: ProgressMode) -> Option<V> {
+        let start = self.start;
+        let duration = self.end() - start;
+        if duration > 0.0 {
+            self.sample(start + mode.apply((time - start) / duration) * duration)
+        } else {
+            self.sample(time)
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedBakedTrack<f32> {
+    /// Returns the value at `time`, without deserializing the track.
+    pub fn sample(&self, time: f32) -> Option<f32> {
+        let len = self.values.len();
+        let (i, t) = locate(self.start.to_native(), self.step.to_native(), len, time);
+        let a = self.values.get(i)?.to_native();
+        match self.values.get(i + 1) {
+            Some(b) if t != 0.0 => Some(a.lerp(&b.to_native(), &t)),
+            _ => Some(a),
+        }
+    }
+}
+
+#[test]
+fn ease_table() {
+    use ease::{Ease, EaseFunction};
+
+    let table = EaseTable::new(EaseFunction::QuadraticIn, 5);
+    assert_eq!(table.samples(), &[0.0, 0.0625, 0.25, 0.5625, 1.0]);
+    assert_eq!(table.sample(0.5), 0.5f32.quadratic_in());
+    assert_eq!(table.sample(0.125), 0.03125);
+    assert_eq!(table.sample(2.0), 1.0);
+    assert_eq!(EaseTable::new(Easing::Linear, 0).sample(0.3), 0.3);
+}
+
+#[test]
+fn baked_track() {
+    use tween::Tween;
+
+    let tween = Tween::new(0.0f32, 10.0, 1.0);
+    let track = BakedTrack::bake(0.0, 0.25, 5, |t| tween.value_at(t));
+    assert_eq!(track.values, vec![0.0, 2.5, 5.0, 7.5, 10.0]);
+    assert_eq!(track.end(), 1.0);
+    assert_eq!(track.samples()[1], (0.25, 2.5));
+    assert_eq!(track.sample(0.125), Some(1.25));
+    assert_eq!(track.sample(-1.0), Some(0.0));
+    assert_eq!(track.sample(2.0), Some(10.0));
+    assert_eq!(track.sample_progress(1.25, ProgressMode::Wrap), Some(2.5));
+    assert_eq!(track.sample_progress(1.25, ProgressMode::Mirror), Some(7.5));
+    assert_eq!(track.sample_progress(1.25, ProgressMode::Clamp), Some(10.0));
+}
+
+#[test]
+fn bake_invalid_input() {
+    let single = BakedTrack { start: 0.0, step: 1.0, values: vec![3.0f32] };
+    assert_eq!(single.sample(f32::NAN), Some(3.0));
+    let track = BakedTrack { start: 0.0, step: f32::NAN, values: vec![1.0f32, 2.0] };
+    assert_eq!(track.sample(0.5), Some(1.0));
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn bake_rkyv() {
+    use ease::EaseFunction;
+    use rkyv::rancor::Error;
+
+    let table = EaseTable::new(EaseFunction::CubicOut, 64);
+    let bytes = rkyv::to_bytes::<Error>(&table).unwrap();
+    let archived = rkyv::access::<ArchivedEaseTable, Error>(&bytes).unwrap();
+    assert_eq!(archived.sample(0.3), table.sample(0.3));
+
+    let track = BakedTrack::bake(1.0, 0.5, 4, |t| t * t);
+    let bytes = rkyv::to_bytes::<Error>(&track).unwrap();
+    let archived = rkyv::access::<ArchivedBakedTrack<f32>, Error>(&bytes).unwrap();
+    assert_eq!(archived.sample(1.75), track.sample(1.75));
+    assert_eq!(rkyv::deserialize::<BakedTrack<f32>, Error>(archived).unwrap(), track);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn bake_bincode() {
+    use ease::EaseFunction;
+
+    let config = bincode::config::standard();
+    let table = EaseTable::new(EaseFunction::SineInOut, 16);
+    let bytes = bincode::encode_to_vec(&table, config).unwrap();
+    let (decoded, _): (EaseTable, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, table);
+}
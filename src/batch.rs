@@ -0,0 +1,252 @@
+//! Batch evaluation over contiguous slices
+//!
+//! With the `simd` feature, which needs a nightly compiler,
+//! the polynomial easings and lerp are evaluated eight lanes at a time.
+//! Otherwise, and for the remainder of a slice, a scalar loop is used.
+//! Both give the same results.
+
+use ease::{Ease, EaseFunction};
+
+#[cfg(feature = "simd")]
+use core::simd::f32x8;
+#[cfg(feature = "simd")]
+use core::simd::cmp::SimdPartialOrd;
+#[cfg(feature = "simd")]
+use core::simd::Select;
+
+/// Interpolates `a` and `b` element-wise, writing the results to `out`.
+///
+/// Only the elements that exist in all three slices are computed.
+pub fn lerp_slice(a: &[f32], b: &[f32], t: f32, out: &mut [f32]) {
+    #[cfg(feature = "simd")]
+    let start = {
+        let tv = f32x8::splat(t);
+        for ((o, a), b) in out.chunks_exact_mut(8).zip(a.chunks_exact(8)).zip(b.chunks_exact(8)) {
+            let a = f32x8::from_slice(a);
+            let b = f32x8::from_slice(b);
+            (a + (b - a) * tv).copy_to_slice(o);
+        }
+        let len = out.len().min(a.len()).min(b.len());
+        len - len % 8
+    };
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+    for ((o, a), b) in out.iter_mut().zip(a).zip(b).skip(start) {
+        *o = a + (b - a) * t;
+    }
+}
+
+/// Interpolates `a` and `b` element-wise with a factor per element, writing the results to `out`.
+///
+/// This suits data stored as a structure of arrays, such as particle systems
+/// that keep each channel and the progress of every particle in separate slices.
+/// Only the elements that exist in all four slices are computed.
+pub fn lerp_soa(a: &[f32], b: &[f32], t: &[f32], out: &mut [f32]) {
+    #[cfg(feature = "simd")]
+    let start = {
+        let chunks = out.chunks_exact_mut(8).zip(a.chunks_exact(8)).zip(b.chunks_exact(8)).zip(t.chunks_exact(8));
+        for (((o, a), b), t) in chunks {
+            let a = f32x8::from_slice(a);
+            let b = f32x8::from_slice(b);
+            (a + (b - a) * f32x8::from_slice(t)).copy_to_slice(o);
+        }
+        let len = out.len().min(a.len()).min(b.len()).min(t.len());
+        len - len % 8
+    };
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+    for (((o, a), b), t) in out.iter_mut().zip(a).zip(b).zip(t).skip(start) {
+        *o = a + (b - a) * t;
+    }
+}
+
+/// Interpolates several channels with `lerp_soa`, sharing the factors in `t`.
+///
+/// For example, positions stored as separate `x`, `y` and `z` slices
+/// are interpolated with `N = 3`.
+pub fn lerp_soa_channels<const N: usize>(
+    a: [&[f32]; N],
+    b: [&[f32]; N],
+    t: &[f32],
+    out: [&mut [f32]; N]
+) {
+    for ((o, a), b) in IntoIterator::into_iter(out).zip(&a).zip(&b) {
+        lerp_soa(a, b, t, o);
+    }
+}
+
+/// Replaces every value in `values` with its eased value.
+pub fn ease_slice(f: EaseFunction, values: &mut [f32]) {
+    #[cfg(feature = "simd")]
+    let start = if is_polynomial(f) {
+        for chunk in values.chunks_exact_mut(8) {
+            ease_x8(f, f32x8::from_slice(chunk)).copy_to_slice(chunk);
+        }
+        values.len() - values.len() % 8
+    } else {
+        0
+    };
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+    for v in values.iter_mut().skip(start) {
+        *v = v.calc(f);
+    }
+}
+
+/// Replaces every value in `values` with `3p^2 - 2p^3`,
+/// where `p` is the value clamped to the range from zero to one.
+pub fn smoothstep_slice(values: &mut [f32]) {
+    #[cfg(feature = "simd")]
+    let start = {
+        for chunk in values.chunks_exact_mut(8) {
+            let p = clamp_x8(f32x8::from_slice(chunk));
+            (p * p * (f32x8::splat(3.0) - f32x8::splat(2.0) * p)).copy_to_slice(chunk);
+        }
+        values.len() - values.len() % 8
+    };
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+    for v in values.iter_mut().skip(start) {
+        let p = match () {
+            _ if *v > 1.0 => 1.0,
+            _ if *v < 0.0 => 0.0,
+            _ => *v
+        };
+        *v = p * p * (3.0 - 2.0 * p);
+    }
+}
+
+#[cfg(feature = "simd")]
+fn is_polynomial(f: EaseFunction) -> bool {
+    use ease::EaseFunction::*;
+
+    matches!(f, QuadraticIn | QuadraticOut | QuadraticInOut |
+                CubicIn | CubicOut | CubicInOut |
+                QuarticIn | QuarticOut | QuarticInOut |
+                QuinticIn | QuinticOut | QuinticInOut)
+}
+
+/// Clamps like the scalar ease functions, passing NaN through.
+#[cfg(feature = "simd")]
+fn clamp_x8(p: f32x8) -> f32x8 {
+    let zero = f32x8::splat(0.0);
+    let one = f32x8::splat(1.0);
+    let p = p.simd_gt(one).select(one, p);
+    p.simd_lt(zero).select(zero, p)
+}
+
+/// Computes `a * b + c` in each lane, fused like the scalar `mul_add`.
+#[cfg(feature = "simd")]
+#[inline(always)]
+fn mul_add_x8(a: f32x8, b: f32x8, c: f32x8) -> f32x8 {
+    #[cfg(all(feature = "fma", feature = "std"))]
+    return ::std::simd::StdFloat::mul_add(a, b, c);
+    // `StdFloat` is not in `core`, so each lane is fused on its own.
+    #[cfg(all(feature = "fma", not(feature = "std")))]
+    return {
+        let (a, b, c) = (a.to_array(), b.to_array(), c.to_array());
+        f32x8::from_array(::core::array::from_fn(|i| match (a.get(i), b.get(i), c.get(i)) {
+            (Some(&a), Some(&b), Some(&c)) => ::num_traits::Float::mul_add(a, b, c),
+            _ => 0.0,
+        }))
+    };
+    #[cfg(not(feature = "fma"))]
+    return a * b + c;
+}
+
+/// Evaluates a polynomial easing, in the same order of operations as `Ease`.
+///
+/// Other easings are evaluated one lane at a time with `Ease`.
+#[cfg(feature = "simd")]
+fn ease_x8(f: EaseFunction, p: f32x8) -> f32x8 {
+    use ease::EaseFunction::*;
+
+    let s = f32x8::splat;
+    let p = clamp_x8(p);
+    let one = s(1.0);
+    let low = p.simd_lt(s(0.5));
+    match f {
+        QuadraticIn => p * p,
+        QuadraticOut => -(p * (p - s(2.0))),
+        QuadraticInOut => low.select(
+            s(2.0) * p * p,
+            mul_add_x8(s(-2.0) * p, p, s(4.0) * p) - one
+        ),
+        CubicIn => p * p * p,
+        CubicOut => {
+            let f = p - one;
+            mul_add_x8(f * f, f, one)
+        }
+        CubicInOut => {
+            let f = (s(2.0) * p) - s(2.0);
+            low.select(s(4.0) * p * p * p, mul_add_x8(s(0.5) * f * f, f, one))
+        }
+        QuarticIn => p * p * p * p,
+        QuarticOut => {
+            let f = p - one;
+            mul_add_x8(f * f * f, one - p, one)
+        }
+        QuarticInOut => {
+            let f = p - one;
+            low.select(s(8.0) * p * p * p * p, mul_add_x8(s(-8.0) * f * f * f, f, one))
+        }
+        QuinticIn => p * p * p * p * p,
+        QuinticOut => {
+            let f = p - one;
+            mul_add_x8(f * f * f * f, f, one)
+        }
+        QuinticInOut => {
+            let f = (s(2.0) * p) - s(2.0);
+            low.select(s(16.0) * p * p * p * p * p, mul_add_x8(s(0.5) * f * f * f * f, f, one))
+        }
+        _ => f32x8::from_array(p.to_array().map(|p| p.calc(f))),
+    }
+}
+
+#[test]
+fn batch_matches_scalar() {
+    use ease::EaseFunction::*;
+
+    let input: Vec<f32> = (-10..=110).map(|i| i as f32 / 100.0).collect();
+    for &f in &[QuadraticInOut, CubicOut, QuarticInOut, QuinticInOut, BounceOut] {
+        let mut values = input.clone();
+        ease_slice(f, &mut values);
+        for (v, x) in values.iter().zip(&input) {
+            assert_eq!(*v, x.calc(f));
+        }
+    }
+    // Every easing has a vector path, even the ones `ease_slice` keeps scalar.
+    #[cfg(feature = "simd")]
+    for &f in EaseFunction::ALL.iter() {
+        let p = f32x8::from_array([-0.5, 0.0, 0.1, 0.3, 0.5, 0.7, 1.0, 1.5]);
+        assert_eq!(ease_x8(f, p).to_array(), p.to_array().map(|p| p.calc(f)), "{:?}", f);
+    }
+
+    let mut out = vec![0.0; 19];
+    let b: Vec<f32> = (0..19).map(|i| i as f32).collect();
+    lerp_slice(&input, &b, 0.5, &mut out);
+    assert_eq!(out[18], input[18] + (18.0 - input[18]) * 0.5);
+
+    let mut values = vec![-1.0, 0.5, 2.0];
+    smoothstep_slice(&mut values);
+    assert_eq!(values, vec![0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn lerp_soa_matches_scalar() {
+    use lerp::Lerp;
+
+    let ax: Vec<f32> = (0..19).map(|i| i as f32).collect();
+    let bx: Vec<f32> = (0..19).map(|i| 100.0 - i as f32 * 3.0).collect();
+    let t: Vec<f32> = (0..19).map(|i| i as f32 / 18.0).collect();
+    let mut out = vec![0.0; 19];
+    lerp_soa(&ax, &bx, &t, &mut out);
+    for i in 0..19 {
+        assert_eq!(out[i], ax[i].lerp(&bx[i], &t[i]));
+    }
+
+    let (mut ox, mut oy) = (vec![0.0; 19], vec![0.0; 17]);
+    lerp_soa_channels([&ax, &bx], [&bx, &ax], &t, [&mut ox, &mut oy]);
+    assert_eq!(ox, out);
+    assert_eq!(oy[16], bx[16].lerp(&ax[16], &t[16]));
+}
@@ -0,0 +1,1647 @@
+//! Bézier curves
+//!
+//! `QuadraticBezier` and `CubicBezier` store the control points of a curve,
+//! so it can be passed around and evaluated with methods.
+//! Curves of any degree are evaluated with de Casteljau's algorithm, which repeats
+//! linear interpolation between neighbouring points until one point is left.
+//! This works for any `Lerp` type and is numerically stable,
+//! but needs a scratch buffer as long as the list of points.
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use arc_length::{integrate_speed, ParametricCurve, PreparedCurve};
+use float::Float;
+use lerp::Lerp;
+use poly::{CubicPoly, QuadraticPoly};
+use spatial::{InnerSpace, Planar, Spatial};
+
+/// A quadratic Bézier curve.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct QuadraticBezier<T> {
+    /// The control points, from the start to the end.
+    pub points: [T; 3],
+}
+
+impl<T> QuadraticBezier<T> {
+    /// Creates a curve from `x0` to `x2`, pulled towards `x1`.
+    pub fn new(x0: T, x1: T, x2: T) -> QuadraticBezier<T> {
+        QuadraticBezier { points: [x0, x1, x2] }
+    }
+
+    /// Returns the same curve, from the end to the start.
+    pub fn reversed(self) -> QuadraticBezier<T> {
+        let [x0, x1, x2] = self.points;
+        QuadraticBezier::new(x2, x1, x0)
+    }
+
+    /// Returns the start of the curve.
+    pub fn start(&self) -> &T {
+        let [ref x0, _, _] = self.points;
+        x0
+    }
+
+    /// Returns the end of the curve.
+    pub fn end(&self) -> &T {
+        let [_, _, ref x2] = self.points;
+        x2
+    }
+
+    /// Returns the point at `t`.
+    #[inline(always)]
+    pub fn sample(&self, t: &T::Scalar) -> T
+        where T: Lerp
+    {
+        let [ref x0, ref x1, ref x2] = self.points;
+        ::quad_bez(x0, x1, x2, t)
+    }
+
+    /// Splits the curve at `t` with de Casteljau's algorithm,
+    /// into the curves before and after `t`.
+    ///
+    /// Both curves are parameterized from 0 to 1, and together trace the same curve.
+    pub fn split(&self, t: &T::Scalar) -> (QuadraticBezier<T>, QuadraticBezier<T>)
+        where T: Lerp + Clone
+    {
+        let [ref x0, ref x1, ref x2] = self.points;
+        let (x01, x12) = (x0.lerp(x1, t), x1.lerp(x2, t));
+        let x = x01.lerp(&x12, t);
+        (QuadraticBezier::new(x0.clone(), x01, x.clone()), QuadraticBezier::new(x, x12, x2.clone()))
+    }
+
+    /// Returns the part of the curve from `t0` to `t1`, parameterized from 0 to 1.
+    ///
+    /// When `t1` is before `t0`, the part runs backwards.
+    pub fn subcurve(&self, t0: &T::Scalar, t1: &T::Scalar) -> QuadraticBezier<T>
+        where T: Lerp + Clone, T::Scalar: Float
+    {
+        // The part after the first parameter is split again at the second one,
+        // rescaled to the rest of the curve, so both splits stay within it.
+        let (lo, hi) = if t0 <= t1 { (*t0, *t1) } else { (*t1, *t0) };
+        let one = T::Scalar::one();
+        let part = if lo != one {
+            self.split(&lo).1.split(&((hi - lo) / (one - lo))).0
+        } else {
+            self.split(&hi).0.split(&(lo / hi)).1
+        };
+        if t0 > t1 { part.reversed() } else { part }
+    }
+
+    /// Returns the same curve as a cubic curve.
+    pub fn elevate(&self) -> CubicBezier<T>
+        where T: Lerp + Clone, T::Scalar: Float
+    {
+        let [ref x0, ref x1, ref x2] = self.points;
+        let two_thirds = T::Scalar::from_f64(2.0 / 3.0);
+        CubicBezier::new(x0.clone(), x0.lerp(x1, &two_thirds), x2.lerp(x1, &two_thirds), x2.clone())
+    }
+
+    /// Returns points along the curve, from the start to the end,
+    /// so that the curve is within `tolerance` of the lines between them.
+    ///
+    /// The curve is halved until each part is flat enough,
+    /// at most 10 times, which gives at most 1025 points.
+    #[cfg(feature = "alloc")]
+    pub fn flatten(&self, tolerance: <T as Lerp>::Scalar) -> Vec<T>
+        where T: Lerp + InnerSpace<Scalar = <T as Lerp>::Scalar> + Clone, <T as Lerp>::Scalar: Float
+    {
+        // The curve is within half the distance of the control point
+        // from the middle of the chord.
+        let half = <T as Lerp>::Scalar::from_f64(0.5);
+        let flat = |curve: &QuadraticBezier<T>| {
+            let [ref x0, ref x1, ref x2] = curve.points;
+            x1.distance(&x0.lerp(x2, &half)) * half <= tolerance
+        };
+        let end = |curve: QuadraticBezier<T>| {
+            let [_, _, x2] = curve.points;
+            x2
+        };
+        let mut points = vec![self.start().clone()];
+        subdivide(self.clone(), FLATTEN_DEPTH, &flat, &|curve| curve.split(&half), &end, &mut points);
+        points
+    }
+
+    /// Expands the curve into polynomial form, for faster evaluation.
+    pub fn to_poly(&self) -> QuadraticPoly<T>
+        where T: Spatial + Clone, T::Scalar: Float
+    {
+        let [ref x0, ref x1, ref x2] = self.points;
+        QuadraticPoly::from_bezier(x0, x1, x2)
+    }
+}
+
+impl<T> QuadraticBezier<T>
+    where T: Spatial, T::Scalar: Float
+{
+    /// Returns the first derivative at `t`, the velocity along the curve.
+    pub fn derivative(&self, t: &T::Scalar) -> T {
+        let [ref x0, ref x1, ref x2] = self.points;
+        mix(&x1.sub(x0), &x2.sub(x1), t).scale(&T::Scalar::from_f64(2.0))
+    }
+
+    /// Returns the second derivative, which is the same along the whole curve.
+    pub fn second_derivative(&self) -> T {
+        let [ref x0, ref x1, ref x2] = self.points;
+        x2.sub(x1).sub(&x1.sub(x0)).scale(&T::Scalar::from_f64(2.0))
+    }
+
+    /// Returns the unit tangent at `t`,
+    /// or `None` where the derivative is zero.
+    pub fn tangent(&self, t: &T::Scalar) -> Option<T>
+        where T: InnerSpace
+    {
+        normalize(self.derivative(t))
+    }
+
+    /// Returns the unit normal at `t`, a quarter turn from the tangent,
+    /// or `None` where the derivative is zero.
+    pub fn normal(&self, t: &T::Scalar) -> Option<T>
+        where T: Planar
+    {
+        self.tangent(t).map(|tangent| tangent.perp())
+    }
+
+    /// Returns the signed curvature at `t`, the inverse of the radius of the turn,
+    /// which is positive when turning towards the normal,
+    /// or `None` where the derivative is zero.
+    pub fn curvature(&self, t: &T::Scalar) -> Option<T::Scalar>
+        where T: Planar
+    {
+        curvature(&self.derivative(t), &self.second_derivative())
+    }
+
+    /// Returns the signed radius of curvature at `t`,
+    /// or `None` where the curve is straight or the derivative is zero.
+    pub fn radius_of_curvature(&self, t: &T::Scalar) -> Option<T::Scalar>
+        where T: Planar
+    {
+        radius(self.curvature(t)?)
+    }
+
+    /// Returns the length of the curve.
+    pub fn length(&self) -> T::Scalar
+        where T: InnerSpace
+    {
+        self.arc_length(&T::Scalar::zero(), &T::Scalar::one())
+    }
+
+    /// Returns the length of the curve from `t0` to `t1`,
+    /// which is negative when `t1` is before `t0`.
+    ///
+    /// The speed along the curve is integrated with adaptive Gauss-Legendre quadrature,
+    /// to a relative error of about `1e-6`.
+    pub fn arc_length(&self, t0: &T::Scalar, t1: &T::Scalar) -> T::Scalar
+        where T: InnerSpace
+    {
+        integrate_speed(|t| self.derivative(&t).length(), *t0, *t1)
+    }
+
+    /// Measures the curve for evaluation at a distance along it,
+    /// such as for motion at constant speed.
+    pub fn prepared(self) -> PreparedCurve<Self>
+        where T: Lerp<Scalar = <T as Spatial>::Scalar> + InnerSpace
+    {
+        PreparedCurve::new(self)
+    }
+
+    /// Returns the parameter of the point on the curve closest to `point`,
+    /// and the distance to it.
+    ///
+    /// The curve is sampled at 16 evenly spaced parameters, and the closest sample
+    /// is refined with Newton's method on the derivative of the squared distance.
+    pub fn project(&self, point: &T) -> (T::Scalar, T::Scalar)
+        where T: InnerSpace + Clone
+    {
+        let poly = self.to_poly();
+        let second = self.second_derivative();
+        closest_point(point, |t| poly.eval(t), |t| self.derivative(t), |_| second.clone())
+    }
+}
+
+/// A cubic Bézier curve.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct CubicBezier<T> {
+    /// The control points, from the start to the end.
+    pub points: [T; 4],
+}
+
+impl<T> CubicBezier<T> {
+    /// Creates a curve from `x0` to `x3`, leaving towards `x1` and arriving from `x2`.
+    pub fn new(x0: T, x1: T, x2: T, x3: T) -> CubicBezier<T> {
+        CubicBezier { points: [x0, x1, x2, x3] }
+    }
+
+    /// Returns the same curve, from the end to the start.
+    pub fn reversed(self) -> CubicBezier<T> {
+        let [x0, x1, x2, x3] = self.points;
+        CubicBezier::new(x3, x2, x1, x0)
+    }
+
+    /// Returns the start of the curve.
+    pub fn start(&self) -> &T {
+        let [ref x0, _, _, _] = self.points;
+        x0
+    }
+
+    /// Returns the end of the curve.
+    pub fn end(&self) -> &T {
+        let [_, _, _, ref x3] = self.points;
+        x3
+    }
+
+    /// Returns the point at `t`.
+    #[inline(always)]
+    pub fn sample(&self, t: &T::Scalar) -> T
+        where T: Lerp
+    {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        ::cub_bez(x0, x1, x2, x3, t)
+    }
+
+    /// Splits the curve at `t` with de Casteljau's algorithm,
+    /// into the curves before and after `t`.
+    ///
+    /// Both curves are parameterized from 0 to 1, and together trace the same curve.
+    pub fn split(&self, t: &T::Scalar) -> (CubicBezier<T>, CubicBezier<T>)
+        where T: Lerp + Clone
+    {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        let (x01, x12, x23) = (x0.lerp(x1, t), x1.lerp(x2, t), x2.lerp(x3, t));
+        let (x012, x123) = (x01.lerp(&x12, t), x12.lerp(&x23, t));
+        let x = x012.lerp(&x123, t);
+        (CubicBezier::new(x0.clone(), x01, x012, x.clone()), CubicBezier::new(x, x123, x23, x3.clone()))
+    }
+
+    /// Returns the part of the curve from `t0` to `t1`, parameterized from 0 to 1.
+    ///
+    /// When `t1` is before `t0`, the part runs backwards.
+    pub fn subcurve(&self, t0: &T::Scalar, t1: &T::Scalar) -> CubicBezier<T>
+        where T: Lerp + Clone, T::Scalar: Float
+    {
+        // The part after the first parameter is split again at the second one,
+        // rescaled to the rest of the curve, so both splits stay within it.
+        let (lo, hi) = if t0 <= t1 { (*t0, *t1) } else { (*t1, *t0) };
+        let one = T::Scalar::one();
+        let part = if lo != one {
+            self.split(&lo).1.split(&((hi - lo) / (one - lo))).0
+        } else {
+            self.split(&hi).0.split(&(lo / hi)).1
+        };
+        if t0 > t1 { part.reversed() } else { part }
+    }
+
+    /// Returns points along the curve, from the start to the end,
+    /// so that the curve is within `tolerance` of the lines between them.
+    ///
+    /// The curve is halved until each part is flat enough,
+    /// at most 10 times, which gives at most 1025 points.
+    #[cfg(feature = "alloc")]
+    pub fn flatten(&self, tolerance: <T as Lerp>::Scalar) -> Vec<T>
+        where T: Lerp + InnerSpace<Scalar = <T as Lerp>::Scalar> + Clone, <T as Lerp>::Scalar: Float
+    {
+        // The curve is within three quarters of the largest distance of the control points
+        // from the points at one and two thirds of the chord.
+        let (third, two_thirds) = (<T as Lerp>::Scalar::from_f64(1.0 / 3.0), <T as Lerp>::Scalar::from_f64(2.0 / 3.0));
+        let three_quarters = <T as Lerp>::Scalar::from_f64(0.75);
+        let flat = |curve: &CubicBezier<T>| {
+            let [ref x0, ref x1, ref x2, ref x3] = curve.points;
+            let (u, v) = (x1.distance(&x0.lerp(x3, &third)), x2.distance(&x0.lerp(x3, &two_thirds)));
+            (if u > v { u } else { v }) * three_quarters <= tolerance
+        };
+        let half = <T as Lerp>::Scalar::from_f64(0.5);
+        let end = |curve: CubicBezier<T>| {
+            let [_, _, _, x3] = curve.points;
+            x3
+        };
+        let mut points = vec![self.start().clone()];
+        subdivide(self.clone(), FLATTEN_DEPTH, &flat, &|curve| curve.split(&half), &end, &mut points);
+        points
+    }
+
+    /// Returns cubic curves, joined end to end, that are within `tolerance`
+    /// of the curve offset by `distance` towards its normal.
+    ///
+    /// Each curve starts and ends at the offset ends, along the same tangents,
+    /// with handles scaled by how the offset changes the radius of curvature there.
+    /// Where this is not within `tolerance`, the curve is halved, at most 8 times.
+    /// Offsetting inwards by more than the radius of curvature gives loops,
+    /// which are kept. A curve whose points all coincide gives no curves.
+    #[cfg(feature = "alloc")]
+    pub fn offset(&self, distance: <T as Lerp>::Scalar, tolerance: <T as Lerp>::Scalar) -> Vec<CubicBezier<T>>
+        where T: Lerp + Planar<Scalar = <T as Lerp>::Scalar> + Clone, <T as Lerp>::Scalar: Float
+    {
+        let mut curves = Vec::new();
+        offset_parts(self.clone(), distance, tolerance, OFFSET_DEPTH, &mut curves);
+        curves
+    }
+
+    /// Returns a quadratic curve with the same ends that approximates this curve.
+    ///
+    /// The control point is the mean of the two points that the tangents at the ends
+    /// point to, so a curve made with `QuadraticBezier::elevate` is reduced exactly.
+    /// See `reduction_error` for how far the result can be from this curve.
+    pub fn reduce(&self) -> QuadraticBezier<T>
+        where T: Spatial + Clone, T::Scalar: Float
+    {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        let control = x1.add(x2).scale(&T::Scalar::from_f64(0.75))
+            .sub(&x0.add(x3).scale(&T::Scalar::from_f64(0.25)));
+        QuadraticBezier::new(x0.clone(), control, x3.clone())
+    }
+
+    /// Returns an upper bound for the distance between this curve and `reduce`
+    /// at the same parameter.
+    ///
+    /// When it is too large, split the curve and reduce the parts.
+    pub fn reduction_error(&self) -> T::Scalar
+        where T: InnerSpace, T::Scalar: Float
+    {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        let three = T::Scalar::from_f64(3.0);
+        let d = x3.sub(x0).add(&x1.sub(x2).scale(&three));
+        d.length() * T::Scalar::from_f64(3.0f64.sqrt() / 36.0)
+    }
+
+    /// Expands the curve into polynomial form, for faster evaluation.
+    pub fn to_poly(&self) -> CubicPoly<T>
+        where T: Spatial + Clone, T::Scalar: Float
+    {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        CubicPoly::from_bezier(x0, x1, x2, x3)
+    }
+}
+
+impl<T> CubicBezier<T>
+    where T: Spatial, T::Scalar: Float
+{
+    /// Returns the first derivative at `t`, the velocity along the curve.
+    pub fn derivative(&self, t: &T::Scalar) -> T {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        let (d0, d1, d2) = (x1.sub(x0), x2.sub(x1), x3.sub(x2));
+        mix(&mix(&d0, &d1, t), &mix(&d1, &d2, t), t).scale(&T::Scalar::from_f64(3.0))
+    }
+
+    /// Returns the second derivative at `t`, the acceleration along the curve.
+    pub fn second_derivative(&self, t: &T::Scalar) -> T {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        let (d0, d1, d2) = (x1.sub(x0), x2.sub(x1), x3.sub(x2));
+        mix(&d1.sub(&d0), &d2.sub(&d1), t).scale(&T::Scalar::from_f64(6.0))
+    }
+
+    /// Returns the unit tangent at `t`,
+    /// or `None` where the derivative is zero.
+    ///
+    /// The derivative is zero at the ends when a control point coincides with them,
+    /// and at cusps.
+    pub fn tangent(&self, t: &T::Scalar) -> Option<T>
+        where T: InnerSpace
+    {
+        normalize(self.derivative(t))
+    }
+
+    /// Returns the unit normal at `t`, a quarter turn from the tangent,
+    /// or `None` where the derivative is zero.
+    pub fn normal(&self, t: &T::Scalar) -> Option<T>
+        where T: Planar
+    {
+        self.tangent(t).map(|tangent| tangent.perp())
+    }
+
+    /// Returns the signed curvature at `t`, the inverse of the radius of the turn,
+    /// which is positive when turning towards the normal,
+    /// or `None` where the derivative is zero.
+    pub fn curvature(&self, t: &T::Scalar) -> Option<T::Scalar>
+        where T: Planar
+    {
+        curvature(&self.derivative(t), &self.second_derivative(t))
+    }
+
+    /// Returns the signed radius of curvature at `t`,
+    /// or `None` where the curve is straight or the derivative is zero.
+    pub fn radius_of_curvature(&self, t: &T::Scalar) -> Option<T::Scalar>
+        where T: Planar
+    {
+        radius(self.curvature(t)?)
+    }
+
+    /// Returns the length of the curve.
+    pub fn length(&self) -> T::Scalar
+        where T: InnerSpace
+    {
+        self.arc_length(&T::Scalar::zero(), &T::Scalar::one())
+    }
+
+    /// Returns the length of the curve from `t0` to `t1`,
+    /// which is negative when `t1` is before `t0`.
+    ///
+    /// The speed along the curve is integrated with adaptive Gauss-Legendre quadrature,
+    /// to a relative error of about `1e-6`.
+    pub fn arc_length(&self, t0: &T::Scalar, t1: &T::Scalar) -> T::Scalar
+        where T: InnerSpace
+    {
+        integrate_speed(|t| self.derivative(&t).length(), *t0, *t1)
+    }
+
+    /// Measures the curve for evaluation at a distance along it,
+    /// such as for motion at constant speed.
+    pub fn prepared(self) -> PreparedCurve<Self>
+        where T: Lerp<Scalar = <T as Spatial>::Scalar> + InnerSpace
+    {
+        PreparedCurve::new(self)
+    }
+
+    /// Returns the parameter of the point on the curve closest to `point`,
+    /// and the distance to it.
+    ///
+    /// The curve is sampled at 16 evenly spaced parameters, and the closest sample
+    /// is refined with Newton's method on the derivative of the squared distance.
+    /// This finds the closest point unless the curve comes back close to `point`
+    /// between two samples, which needs a loop smaller than the spacing of the samples.
+    pub fn project(&self, point: &T) -> (T::Scalar, T::Scalar)
+        where T: InnerSpace + Clone
+    {
+        let poly = self.to_poly();
+        closest_point(point, |t| poly.eval(t), |t| self.derivative(t), |t| self.second_derivative(t))
+    }
+
+    /// Returns the parameters where the curve crosses the infinite line
+    /// through `point` along `direction`, in increasing order.
+    ///
+    /// The signed distance from the line is a cubic in the parameter,
+    /// whose zeros are found by bisection between its turning points.
+    /// A curve lying on the line, or a zero direction, gives no parameters.
+    #[cfg(feature = "alloc")]
+    pub fn intersect_line(&self, point: &T, direction: &T) -> Vec<T::Scalar>
+        where T: Planar
+    {
+        let [ref x0, ref x1, ref x2, ref x3] = self.points;
+        let side = |x: &T| direction.cross(&x.sub(point));
+        cubic_zeros([side(x0), side(x1), side(x2), side(x3)])
+    }
+
+    /// Returns the parameters where the curve crosses the ray
+    /// from `origin` along `direction`, in increasing order.
+    #[cfg(feature = "alloc")]
+    pub fn intersect_ray(&self, origin: &T, direction: &T) -> Vec<T::Scalar>
+        where T: Planar + Clone
+    {
+        let poly = self.to_poly();
+        let mut ts = self.intersect_line(origin, direction);
+        ts.retain(|t| poly.eval(t).sub(origin).dot(direction) >= T::Scalar::zero());
+        ts
+    }
+
+    /// Returns the parameters where the curve crosses the line segment
+    /// from `start` to `end`, in increasing order.
+    #[cfg(feature = "alloc")]
+    pub fn intersect_segment(&self, start: &T, end: &T) -> Vec<T::Scalar>
+        where T: Planar + Clone
+    {
+        let poly = self.to_poly();
+        let direction = end.sub(start);
+        let length2 = direction.dot(&direction);
+        let mut ts = self.intersect_line(start, &direction);
+        ts.retain(|t| {
+            let along = poly.eval(t).sub(start).dot(&direction);
+            along >= T::Scalar::zero() && along <= length2
+        });
+        ts
+    }
+}
+
+impl<S: Float + Lerp<Scalar = S>, const N: usize> QuadraticBezier<[S; N]> {
+    /// Returns the smallest axis-aligned box that contains the curve,
+    /// as the minimum and maximum corners.
+    ///
+    /// Besides the ends, each coordinate can only be extreme
+    /// where its derivative is zero, which is solved for exactly.
+    /// This is often much smaller than the box around the control points.
+    pub fn bounding_box(&self) -> ([S; N], [S; N]) {
+        let [x0, x1, x2] = self.points;
+        let (mut min, mut max) = (x0, x0);
+        for (i, (lo, hi)) in min.iter_mut().zip(max.iter_mut()).enumerate() {
+            if let (Some(&a), Some(&b), Some(&c)) = (x0.get(i), x1.get(i), x2.get(i)) {
+                let curve = QuadraticBezier::new(a, b, c);
+                let root = (a - b) / (a - b - b + c);
+                let (l, h) = extend((a, c), inside(Some(root)).map(|t| curve.sample(&t)));
+                *lo = l;
+                *hi = h;
+            }
+        }
+        (min, max)
+    }
+}
+
+impl<S: Float + Lerp<Scalar = S>, const N: usize> CubicBezier<[S; N]> {
+    /// Returns the smallest axis-aligned box that contains the curve,
+    /// as the minimum and maximum corners.
+    ///
+    /// Besides the ends, each coordinate can only be extreme
+    /// where its derivative is zero, which is solved for exactly.
+    /// This is often much smaller than the box around the control points.
+    pub fn bounding_box(&self) -> ([S; N], [S; N]) {
+        let [x0, x1, x2, x3] = self.points;
+        let (mut min, mut max) = (x0, x0);
+        for (i, (lo, hi)) in min.iter_mut().zip(max.iter_mut()).enumerate() {
+            if let (Some(&a), Some(&b), Some(&c), Some(&d)) = (x0.get(i), x1.get(i), x2.get(i), x3.get(i)) {
+                let curve = CubicBezier::new(a, b, c, d);
+                let roots = stationary_points([a, b, c, d]).into_iter().flatten();
+                let (l, h) = extend((a, d), inside(roots).map(|t| curve.sample(&t)));
+                *lo = l;
+                *hi = h;
+            }
+        }
+        (min, max)
+    }
+
+    /// Returns the points where this curve crosses `other`, as pairs of
+    /// the parameter on this curve and the parameter on `other`, in order along this curve.
+    ///
+    /// Both curves are halved where the boxes around their control points overlap,
+    /// until the boxes are smaller than `tolerance`, at most 32 times.
+    /// Parts are also dropped where the control points of one lie beside the band
+    /// around the chord of the other, and at most 65536 pairs of parts are compared.
+    /// Neighbouring parts around the same crossing are merged, so it is reported once.
+    /// Curves that overlap give a point in the middle of the overlap instead:
+    /// parts of the same curve are recognized before halving, by the ends that lie on the other curve,
+    /// and parts that are straight and on the same line within `tolerance` are not halved further,
+    /// such as a shared edge of two shapes.
+    /// Quadratic curves can be intersected after `elevate`.
+    #[cfg(feature = "alloc")]
+    pub fn intersect(&self, other: &CubicBezier<[S; N]>, tolerance: S) -> Vec<(S, S)>
+        where [S; N]: Lerp<Scalar = S> + InnerSpace + Spatial<Scalar = S>
+    {
+        let (zero, one) = (S::zero(), S::one());
+        let (mut found, mut budget) = (Vec::new(), INTERSECT_PAIRS);
+        match same_curve(self, other, tolerance) {
+            Some(overlap) => found.push(overlap),
+            None => intersect_parts((*self, zero, one), (*other, zero, one), INTERSECT_DEPTH,
+                                    tolerance, &mut budget, &mut found),
+        }
+        let half = S::from_f64(0.5);
+        let mut found: Vec<(S, S)> = found.into_iter()
+            .map(|((t0, t1), (u0, u1))| ((t0 + t1) * half, (u0 + u1) * half))
+            .collect();
+        found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(::core::cmp::Ordering::Equal));
+        found
+    }
+}
+
+/// The maximum number of times each curve is halved when intersecting.
+#[cfg(feature = "alloc")]
+const INTERSECT_DEPTH: u32 = 32;
+
+/// The maximum number of pairs of parts compared when intersecting.
+#[cfg(feature = "alloc")]
+const INTERSECT_PAIRS: u32 = 1 << 16;
+
+/// A part of a curve, with the parameters of its ends on the whole curve.
+#[cfg(feature = "alloc")]
+type Part<S, const N: usize> = (CubicBezier<[S; N]>, S, S);
+
+/// A range of parameters, from the first to the last.
+#[cfg(feature = "alloc")]
+type Span<S> = (S, S);
+
+/// The parameter ranges of a crossing on both curves.
+#[cfg(feature = "alloc")]
+type Crossing<S> = (Span<S>, Span<S>);
+
+/// Checks whether two curves are parts of the same curve where they overlap.
+///
+/// The ends of each curve that lie on the other one within `tolerance` bound the overlap,
+/// which is returned if the two curves agree between them.
+/// Returns `None` if the ends are closer than `tolerance`, where the curves only touch.
+#[cfg(feature = "alloc")]
+fn same_curve<S, const N: usize>(a: &CubicBezier<[S; N]>, b: &CubicBezier<[S; N]>, tolerance: S)
+                                 -> Option<Crossing<S>>
+    where S: Float, [S; N]: Lerp<Scalar = S> + InnerSpace + Spatial<Scalar = S>
+{
+    let (zero, one) = (S::zero(), S::one());
+    let on = |curve: &CubicBezier<[S; N]>, point: &[S; N]| {
+        let (t, distance) = curve.project(point);
+        if distance <= tolerance { Some(t) } else { None }
+    };
+    let ([a0, _, _, a3], [b0, _, _, b3]) = (a.points, b.points);
+    let ends = [on(a, &b0).map(|t| (t, zero)), on(a, &b3).map(|t| (t, one)),
+                on(b, &a0).map(|u| (zero, u)), on(b, &a3).map(|u| (one, u))];
+    let mut ends = ends.iter().flatten();
+    let first = *ends.next()?;
+    let (mut lo, mut hi) = (first, first);
+    for &end in ends {
+        if end.0 < lo.0 {
+            lo = end;
+        } else if end.0 > hi.0 {
+            hi = end;
+        }
+    }
+    if a.sample(&lo.0).distance(&a.sample(&hi.0)) <= tolerance {
+        return None;
+    }
+    let (part_a, part_b) = (a.subcurve(&lo.0, &hi.0), b.subcurve(&lo.1, &hi.1));
+    if part_a.points.iter().zip(&part_b.points).any(|(p, q)| p.distance(q) > tolerance) {
+        return None;
+    }
+    let u = if lo.1 < hi.1 { (lo.1, hi.1) } else { (hi.1, lo.1) };
+    Some(((lo.0, hi.0), u))
+}
+
+/// Halves both parts while their boxes overlap, and records the parameter ranges
+/// of the crossings, merging neighbouring ranges.
+#[cfg(feature = "alloc")]
+fn intersect_parts<S, const N: usize>(a: Part<S, N>, b: Part<S, N>, depth: u32, tolerance: S,
+                                      budget: &mut u32, found: &mut Vec<Crossing<S>>)
+    where S: Float, [S; N]: Lerp<Scalar = S> + InnerSpace + Spatial<Scalar = S>
+{
+    if *budget == 0 {
+        return;
+    }
+    *budget -= 1;
+    let (box_a, box_b) = (hull(&a.0.points), hull(&b.0.points));
+    let overlap = box_a.0.iter().zip(&box_a.1).zip(box_b.0.iter().zip(&box_b.1))
+        .all(|((lo_a, hi_a), (lo_b, hi_b))| lo_a <= hi_b && lo_b <= hi_a);
+    if !overlap || separated(&a.0, &b.0) || separated(&b.0, &a.0) {
+        return;
+    }
+    let small = |(lo, hi): ([S; N], [S; N])| lo.iter().zip(&hi).all(|(&lo, &hi)| hi - lo <= tolerance);
+    let (small_a, small_b) = (small(box_a), small(box_b));
+    if depth == 0 || (small_a && small_b) {
+        record(found, (a.1, a.2), (b.1, b.2));
+        return;
+    }
+    if let Some(overlap) = collinear_overlap(&a, &b, tolerance) {
+        if let Some((t, u)) = overlap {
+            record(found, t, u);
+        }
+        return;
+    }
+    let half = S::from_f64(0.5);
+    let halves = |part: Part<S, N>, small: bool| {
+        let (curve, t0, t1) = part;
+        if small {
+            return [Some((curve, t0, t1)), None];
+        }
+        let mid = (t0 + t1) * half;
+        let (left, right) = curve.split(&half);
+        [Some((left, t0, mid)), Some((right, mid, t1))]
+    };
+    let (parts_a, parts_b) = (halves(a, small_a), halves(b, small_b));
+    for a in parts_a.iter().flatten() {
+        for b in parts_b.iter().flatten() {
+            intersect_parts(*a, *b, depth - 1, tolerance, budget, found);
+        }
+    }
+}
+
+/// Checks whether the control points of `b` are all on one side of the band
+/// that holds the control points of `a`, across the chord of `a` towards `b`.
+///
+/// In the plane, this is the fat line of `a`. It separates near-parallel parts
+/// long before their boxes do, such as a curve and its offset.
+#[cfg(feature = "alloc")]
+fn separated<S, const N: usize>(a: &CubicBezier<[S; N]>, b: &CubicBezier<[S; N]>) -> bool
+    where S: Float, [S; N]: InnerSpace + Spatial<Scalar = S>
+{
+    let zero = S::zero();
+    let [a0, _, _, a3] = a.points;
+    let [b0, b1, b2, b3] = b.points;
+    let chord = a3.sub(&a0);
+    let toward = b0.add(&b1).add(&b2).add(&b3).scale(&S::from_f64(0.25)).sub(&a0);
+    let length2 = chord.dot(&chord);
+    let normal = if length2 > zero { toward.sub(&chord.scale(&(toward.dot(&chord) / length2))) } else { toward };
+    let range = |points: &[[S; N]; 4]| {
+        let sides = points.iter().map(|p| p.sub(&a0).dot(&normal));
+        let first = sides.clone().next().unwrap_or(zero);
+        sides.fold((first, first), |(lo, hi), x| (if x < lo { x } else { lo }, if x > hi { x } else { hi }))
+    };
+    let ((lo_a, hi_a), (lo_b, hi_b)) = (range(&a.points), range(&b.points));
+    lo_b > hi_a || hi_b < lo_a
+}
+
+/// Records the parameter ranges of a crossing, merged with a crossing they touch.
+#[cfg(feature = "alloc")]
+fn record<S: Float>(found: &mut Vec<Crossing<S>>, t: Span<S>, u: Span<S>) {
+    let touches = |(t0, t1): Span<S>, (s0, s1): Span<S>| t0 <= s1 && s0 <= t1;
+    match found.iter_mut().find(|(ft, fu)| touches(*ft, t) && touches(*fu, u)) {
+        Some((ft, fu)) => {
+            let union = |(t0, t1): Span<S>, (s0, s1): Span<S>| {
+                (if s0 < t0 { s0 } else { t0 }, if s1 > t1 { s1 } else { t1 })
+            };
+            *ft = union(*ft, t);
+            *fu = union(*fu, u);
+        }
+        None => found.push((t, u)),
+    }
+}
+
+/// Checks whether two parts are straight and on the same line within `tolerance`.
+///
+/// Returns `None` if they are not, and otherwise the parameter ranges where they overlap,
+/// or `Some(None)` if they do not overlap. The ranges are interpolated along the chords,
+/// which is exact for evenly spaced control points.
+#[cfg(feature = "alloc")]
+fn collinear_overlap<S, const N: usize>(a: &Part<S, N>, b: &Part<S, N>, tolerance: S)
+                                        -> Option<Option<Crossing<S>>>
+    where S: Float
+{
+    let (zero, one) = (S::zero(), S::one());
+    let sub = |p: &[S; N], q: &[S; N]| {
+        let mut d = *p;
+        d.iter_mut().zip(q).for_each(|(d, &q)| *d = *d - q);
+        d
+    };
+    let dot = |p: &[S; N], q: &[S; N]| p.iter().zip(q).fold(zero, |sum, (&p, &q)| sum + p * q);
+    // The position of `p` along the chord from `start` in units of `chord`,
+    // if `p` is within `tolerance` of the line.
+    let along = |p: &[S; N], start: &[S; N], chord: &[S; N], length2: S| {
+        let d = sub(p, start);
+        let s = dot(&d, chord) / length2;
+        let off = d.iter().zip(chord).fold(zero, |sum, (&d, &c)| sum + (d - c * s) * (d - c * s));
+        if off <= tolerance * tolerance { Some(s) } else { None }
+    };
+    let chord = |part: &Part<S, N>| {
+        let [ref p0, ref p1, ref p2, ref p3] = part.0.points;
+        let chord = sub(p3, p0);
+        let length2 = dot(&chord, &chord);
+        if length2 <= tolerance * tolerance {
+            return None;
+        }
+        along(p1, p0, &chord, length2)?;
+        along(p2, p0, &chord, length2)?;
+        Some((*p0, *p3, chord, length2))
+    };
+    let ((a0, a3, chord_a, length2_a), (b0, b3, chord_b, length2_b)) = (chord(a)?, chord(b)?);
+    // The range of each chord covered by the other one.
+    let range = |p: &[S; N], q: &[S; N], start: &[S; N], chord: &[S; N], length2: S| {
+        let (s, t) = (along(p, start, chord, length2)?, along(q, start, chord, length2)?);
+        let (lo, hi) = if s < t { (s, t) } else { (t, s) };
+        Some((if lo > zero { lo } else { zero }, if hi < one { hi } else { one }))
+    };
+    let (on_a, on_b) = (range(&b0, &b3, &a0, &chord_a, length2_a)?,
+                        range(&a0, &a3, &b0, &chord_b, length2_b)?);
+    if on_a.0 > on_a.1 || on_b.0 > on_b.1 {
+        return Some(None);
+    }
+    let span = |part: &Part<S, N>, (lo, hi): Span<S>| (part.1 + (part.2 - part.1) * lo, part.1 + (part.2 - part.1) * hi);
+    Some(Some((span(a, on_a), span(b, on_b))))
+}
+
+/// Returns the minimum and maximum corners of the box around points.
+#[cfg(feature = "alloc")]
+fn hull<S: Float, const N: usize>(points: &[[S; N]]) -> ([S; N], [S; N]) {
+    let first = points.first().map_or([S::zero(); N], |&p| p);
+    let (mut lo, mut hi) = (first, first);
+    for p in points {
+        for ((lo, hi), &x) in lo.iter_mut().zip(hi.iter_mut()).zip(p) {
+            if x < *lo {
+                *lo = x;
+            } else if x > *hi {
+                *hi = x;
+            }
+        }
+    }
+    (lo, hi)
+}
+
+/// Returns the real roots of `a * t^2 + b * t + c`, or `None` if there are none.
+///
+/// The roots are computed in a form that avoids cancellation,
+/// which also covers the linear case where `a` is zero,
+/// but leaves an infinite or NaN root in place of a missing one.
+fn quadratic_roots<S: Float>(a: S, b: S, c: S) -> Option<[S; 2]> {
+    let (zero, two) = (S::zero(), S::from_f64(2.0));
+    let discriminant = b * b - S::from_f64(4.0) * a * c;
+    if discriminant < zero {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let q = if b < zero { (root - b) / two } else { -(b + root) / two };
+    Some([q / a, c / q])
+}
+
+/// Returns the parameters where the derivative of a cubic is zero,
+/// given its Bézier control values, or `None` if there are none.
+fn stationary_points<S: Float>([a, b, c, d]: [S; 4]) -> Option<[S; 2]> {
+    // The derivative is a quadratic with these coefficients, divided by three.
+    let (d0, d1, d2) = (b - a, c - b, d - c);
+    quadratic_roots(d0 - d1 - d1 + d2, (d1 - d0) * S::from_f64(2.0), d0)
+}
+
+/// Returns the parameters from 0 to 1 where a cubic is zero, in increasing order,
+/// given its Bézier control values.
+///
+/// The cubic is split where its derivative is zero, into parts that cross zero
+/// at most once, and each crossing is found by bisection.
+/// A cubic that is zero everywhere gives no parameters.
+#[cfg(feature = "alloc")]
+fn cubic_zeros<S: Float>(values: [S; 4]) -> Vec<S> {
+    let (zero, one, half) = (S::zero(), S::one(), S::from_f64(0.5));
+    let mut zeros = Vec::new();
+    if values.iter().all(|&v| v == zero) {
+        return zeros;
+    }
+    let [a, b, c, d] = values;
+    let value = |t: S| {
+        let mix = |a: S, b: S| a + (b - a) * t;
+        let (ab, bc, cd) = (mix(a, b), mix(b, c), mix(c, d));
+        mix(mix(ab, bc), mix(bc, cd))
+    };
+    let mut ends = vec![zero, one];
+    ends.extend(inside(stationary_points(values).into_iter().flatten()));
+    ends.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::core::cmp::Ordering::Equal));
+    for pair in ends.windows(2) {
+        let (mut lo, mut hi) = match *pair {
+            [lo, hi] => (lo, hi),
+            _ => continue,
+        };
+        let (value_lo, value_hi) = (value(lo), value(hi));
+        if value_lo == zero {
+            if zeros.last() != Some(&lo) {
+                zeros.push(lo);
+            }
+        } else if (value_lo < zero) != (value_hi < zero) && value_hi != zero {
+            for _ in 0..BISECTION_STEPS {
+                let mid = (lo + hi) * half;
+                if mid <= lo || mid >= hi {
+                    break;
+                }
+                if (value(mid) < zero) == (value_lo < zero) {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            zeros.push((lo + hi) * half);
+        }
+    }
+    if value(one) == zero {
+        zeros.push(one);
+    }
+    zeros
+}
+
+/// The maximum number of bisection steps, enough for the precision of `f64`.
+#[cfg(feature = "alloc")]
+const BISECTION_STEPS: u32 = 64;
+
+/// Keeps the parameters strictly between the ends of a curve, which leaves out NaN.
+fn inside<S: Float, I: IntoIterator<Item = S>>(ts: I) -> impl Iterator<Item = S> {
+    ts.into_iter().filter(|&t| t > S::zero() && t < S::one())
+}
+
+/// Extends the range between the ends of a curve by the values at interior parameters.
+fn extend<S: Float, I: Iterator<Item = S>>((start, end): (S, S), values: I) -> (S, S) {
+    let (mut lo, mut hi) = if start < end { (start, end) } else { (end, start) };
+    for v in values {
+        if v < lo {
+            lo = v;
+        } else if v > hi {
+            hi = v;
+        }
+    }
+    (lo, hi)
+}
+
+/// Interpolates linearly between `a` and `b` with vector operations.
+#[inline(always)]
+fn mix<T: Spatial>(a: &T, b: &T, t: &T::Scalar) -> T {
+    b.sub(a).mul_add(t, a)
+}
+
+/// Returns the signed curvature from the first and second derivatives.
+fn curvature<T>(d1: &T, d2: &T) -> Option<T::Scalar>
+    where T: Planar, T::Scalar: Float
+{
+    let speed = d1.length();
+    if speed > T::Scalar::zero() {
+        Some(d1.cross(d2) / (speed * speed * speed))
+    } else {
+        None
+    }
+}
+
+/// Returns the radius for a curvature, or `None` if it is zero.
+fn radius<S: Float>(curvature: S) -> Option<S> {
+    if curvature == S::zero() {
+        None
+    } else {
+        Some(S::one() / curvature)
+    }
+}
+
+/// Scales `v` to unit length, or returns `None` if its length is zero or NaN.
+fn normalize<T>(v: T) -> Option<T>
+    where T: InnerSpace, T::Scalar: Float
+{
+    let length = v.length();
+    if length > T::Scalar::zero() {
+        Some(v.scale(&(T::Scalar::one() / length)))
+    } else {
+        None
+    }
+}
+
+/// The number of segments sampled when looking for the closest point.
+const PROJECT_SEGMENTS: u32 = 16;
+
+/// The maximum number of Newton steps when refining the closest point.
+const PROJECT_STEPS: u32 = 8;
+
+/// Returns the parameter and distance of the closest point to `point`,
+/// given the position and the first two derivatives of a curve.
+fn closest_point<T, P, D1, D2>(point: &T, position: P, d1: D1, d2: D2) -> (T::Scalar, T::Scalar)
+    where T: InnerSpace, T::Scalar: Float,
+          P: Fn(&T::Scalar) -> T, D1: Fn(&T::Scalar) -> T, D2: Fn(&T::Scalar) -> T
+{
+    let (zero, one) = (T::Scalar::zero(), T::Scalar::one());
+    let mut best = (zero, position(&zero).distance(point));
+    for i in 1..=PROJECT_SEGMENTS {
+        let t = T::Scalar::from_f64(f64::from(i) / f64::from(PROJECT_SEGMENTS));
+        let distance = position(&t).distance(point);
+        if distance < best.1 {
+            best = (t, distance);
+        }
+    }
+    for _ in 0..PROJECT_STEPS {
+        let t = best.0;
+        let (offset, velocity) = (position(&t).sub(point), d1(&t));
+        // The first and second derivatives of half the squared distance.
+        let slope = offset.dot(&velocity);
+        let bend = velocity.dot(&velocity) + offset.dot(&d2(&t));
+        if bend <= zero || slope == zero {
+            break;
+        }
+        let next = t - slope / bend;
+        let next = if next < zero { zero } else if next > one { one } else { next };
+        let distance = position(&next).distance(point);
+        if distance < best.1 {
+            best = (next, distance);
+        } else {
+            break;
+        }
+    }
+    best
+}
+
+impl<T> From<QuadraticBezier<T>> for CubicBezier<T>
+    where T: Lerp + Clone, T::Scalar: Float
+{
+    /// Elevates the degree of a quadratic curve.
+    fn from(curve: QuadraticBezier<T>) -> CubicBezier<T> {
+        curve.elevate()
+    }
+}
+
+/// Impls of `ParametricCurve` for curves, and trimming of prepared curves
+macro_rules! impl_parametric_curve {
+    ($ty: ident) => (
+        impl<T> ParametricCurve for $ty<T>
+            where T: Lerp<Scalar = <T as Spatial>::Scalar> + InnerSpace, <T as Spatial>::Scalar: Float
+        {
+            type Point = T;
+            type Scalar = <T as Spatial>::Scalar;
+
+            #[inline(always)]
+            fn point_at(&self, t: Self::Scalar) -> T {
+                self.sample(&t)
+            }
+
+            #[inline(always)]
+            fn speed_at(&self, t: Self::Scalar) -> Self::Scalar {
+                self.derivative(&t).length()
+            }
+        }
+
+        impl<T> PreparedCurve<$ty<T>>
+            where T: Lerp<Scalar = <T as Spatial>::Scalar> + InnerSpace + Clone, <T as Spatial>::Scalar: Float
+        {
+            /// Returns the part of the curve from `s0` to `s1` along it,
+            /// such as for drawing the curve progressively at constant speed.
+            ///
+            /// The distances are clamped to the ends of the curve,
+            /// and when `s1` is before `s0`, the part runs backwards.
+            pub fn trim(&self, s0: <T as Spatial>::Scalar, s1: <T as Spatial>::Scalar) -> $ty<T> {
+                self.curve().subcurve(&self.t_at_length(s0), &self.t_at_length(s1))
+            }
+        }
+    )
+}
+
+impl_parametric_curve!(QuadraticBezier);
+impl_parametric_curve!(CubicBezier);
+
+/// Point-wise impls of `Lerp` and conversions from arrays for curves
+macro_rules! impl_for_bezier {
+    ($ty: ident, $n: expr; $($a: ident, $b: ident),+) => (
+        impl<T: Lerp> Lerp for $ty<T> {
+            type Scalar = T::Scalar;
+
+            /// Interpolates the control points, to morph between curves.
+            #[inline(always)]
+            fn lerp(&self, other: &$ty<T>, scalar: &T::Scalar) -> $ty<T> {
+                let [$(ref $a),+] = self.points;
+                let [$(ref $b),+] = other.points;
+                $ty { points: [$($a.lerp($b, scalar)),+] }
+            }
+        }
+
+        impl<T> From<[T; $n]> for $ty<T> {
+            fn from(points: [T; $n]) -> $ty<T> {
+                $ty { points }
+            }
+        }
+    )
+}
+
+impl_for_bezier!(QuadraticBezier, 3; a0, b0, a1, b1, a2, b2);
+impl_for_bezier!(CubicBezier, 4; a0, b0, a1, b1, a2, b2, a3, b3);
+
+/// The number of points that `bezier` evaluates without allocating.
+const STACK_POINTS: usize = 8;
+
+/// Performs Bézier interpolation between any number of points,
+/// using them as the scratch buffer.
+///
+/// Returns `None` if there are no points.
+/// Afterwards, `points` holds the control points of the part of the curve
+/// from `t` to the end.
+pub fn bezier_in_place<T>(points: &mut [T], t: &T::Scalar) -> Option<T>
+    where T: Lerp + Clone
+{
+    for n in (1..points.len()).rev() {
+        for i in 0..n {
+            if let Some([a, b, ..]) = points.get_mut(i..) {
+                *a = a.lerp(b, t);
+            }
+        }
+    }
+    points.first().cloned()
+}
+
+/// Performs Bézier interpolation between any number of points.
+///
+/// Returns `None` if there are no points.
+/// Curves with up to 8 points are evaluated on the stack.
+/// Longer curves are copied to a `Vec` with the `alloc` feature,
+/// and give `None` without it; use `bezier_in_place` for them instead.
+/// For 3 and 4 points, `quad_bez` and `cub_bez` are faster.
+pub fn bezier<T>(points: &[T], t: &T::Scalar) -> Option<T>
+    where T: Lerp + Clone
+{
+    if points.len() > STACK_POINTS {
+        #[cfg(feature = "alloc")]
+        return bezier_in_place(&mut points.to_vec(), t);
+        #[cfg(not(feature = "alloc"))]
+        return None;
+    }
+    let mut scratch: [Option<T>; STACK_POINTS] = ::core::array::from_fn(|i| points.get(i).cloned());
+    for n in (1..points.len()).rev() {
+        for i in 0..n {
+            if let Some([Some(a), Some(b), ..]) = scratch.get_mut(i..) {
+                *a = a.lerp(b, t);
+            }
+        }
+    }
+    scratch.first_mut()?.take()
+}
+
+/// The maximum number of times a curve is halved when flattening.
+#[cfg(feature = "alloc")]
+const FLATTEN_DEPTH: u32 = 10;
+
+/// Halves `curve` until it is flat, and pushes the end of every part to `points`.
+#[cfg(feature = "alloc")]
+fn subdivide<C, T, F, S, E>(curve: C, depth: u32, flat: &F, split: &S, end: &E, points: &mut Vec<T>)
+    where F: Fn(&C) -> bool, S: Fn(&C) -> (C, C), E: Fn(C) -> T
+{
+    if depth == 0 || flat(&curve) {
+        points.push(end(curve));
+        return;
+    }
+    let (a, b) = split(&curve);
+    subdivide(a, depth - 1, flat, split, end, points);
+    subdivide(b, depth - 1, flat, split, end, points);
+}
+
+/// The maximum number of times a curve is halved when offsetting.
+#[cfg(feature = "alloc")]
+const OFFSET_DEPTH: u32 = 8;
+
+/// Offsets `curve` in one piece where that is within `tolerance`,
+/// or else offsets its halves.
+#[cfg(feature = "alloc")]
+fn offset_parts<T, S>(curve: CubicBezier<T>, distance: S, tolerance: S, depth: u32,
+                      curves: &mut Vec<CubicBezier<T>>)
+    where T: Lerp<Scalar = S> + Planar<Scalar = S> + Clone, S: Float
+{
+    let (zero, one) = (S::zero(), S::one());
+    let [ref x0, ref x1, ref x2, ref x3] = curve.points;
+    // Where a control point coincides with an end, the tangent follows the next one.
+    let start = [x1, x2, x3].iter().map(|x| x.sub(x0)).find_map(normalize);
+    let end = [x2, x1, x0].iter().map(|x| x3.sub(x)).find_map(normalize);
+    let (start, end) = match (start, end) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return,
+    };
+    // Offsetting towards the centre of curvature shortens the radius, and the handles with it.
+    let scale = |t: &S| match curve.curvature(t) {
+        Some(curvature) => one - distance * curvature,
+        None => one,
+    };
+    let (y0, y3) = (start.perp().mul_add(&distance, x0), end.perp().mul_add(&distance, x3));
+    let y1 = x1.sub(x0).mul_add(&scale(&zero), &y0);
+    let y2 = x2.sub(x3).mul_add(&scale(&one), &y3);
+    let offset = CubicBezier::new(y0, y1, y2, y3);
+    let within = (1..OFFSET_SAMPLES).all(|i| {
+        let t = S::from_f64(f64::from(i) / f64::from(OFFSET_SAMPLES));
+        match curve.normal(&t) {
+            Some(normal) => normal.mul_add(&distance, &curve.sample(&t)).distance(&offset.sample(&t)) <= tolerance,
+            None => true,
+        }
+    });
+    if within || depth == 0 {
+        curves.push(offset);
+    } else {
+        let (a, b) = curve.split(&S::from_f64(0.5));
+        offset_parts(a, distance, tolerance, depth - 1, curves);
+        offset_parts(b, distance, tolerance, depth - 1, curves);
+    }
+}
+
+/// The number of parts of a curve between the samples that check an offset.
+#[cfg(feature = "alloc")]
+const OFFSET_SAMPLES: u32 = 8;
+
+/// Returns the control points of the same curve with one more point.
+///
+/// Repeating this gives curves of any higher degree.
+#[cfg(feature = "alloc")]
+pub fn elevate_degree<T>(points: &[T]) -> Vec<T>
+    where T: Lerp + Clone, T::Scalar: Float
+{
+    let n = points.len();
+    let mut elevated: Vec<T> = points.first().cloned().into_iter().collect();
+    for (i, pair) in points.windows(2).enumerate() {
+        if let [a, b] = pair {
+            elevated.push(b.lerp(a, &T::Scalar::from_f64((i + 1) as f64 / n as f64)));
+        }
+    }
+    elevated.extend(points.last().cloned());
+    elevated
+}
+
+#[test]
+fn bezier_de_casteljau() {
+    let points = [0.0f64, 3.0, -2.0, 5.0];
+    for i in 0..=8 {
+        let t = i as f64 / 8.0;
+        let expected = ::cub_bez(&points[0], &points[1], &points[2], &points[3], &t);
+        assert!((bezier(&points, &t).unwrap() - expected).abs() < 1e-12);
+    }
+    // Bernstein form of a quintic curve.
+    let points = [1.0f64, -1.0, 4.0, 0.5, 2.0, 3.0];
+    let binomial = [1.0, 5.0, 10.0, 10.0, 5.0, 1.0];
+    let t = 0.3f64;
+    let expected: f64 = points.iter().zip(&binomial).enumerate()
+        .map(|(i, (p, c))| c * t.powi(i as i32) * (1.0 - t).powi(5 - i as i32) * p)
+        .sum();
+    assert!((bezier(&points, &t).unwrap() - expected).abs() < 1e-12);
+    assert_eq!(bezier::<f32>(&[], &0.5), None);
+    assert_eq!(bezier(&[[1.0f32, 2.0]], &0.5), Some([1.0, 2.0]));
+}
+
+#[test]
+fn bezier_structs() {
+    let quad = QuadraticBezier::new([0.0f32, 0.0], [1.0, 2.0], [2.0, 0.0]);
+    assert_eq!(quad.sample(&0.5), [1.0, 1.0]);
+    assert_eq!(quad.to_poly().eval(&0.5), [1.0, 1.0]);
+    assert_eq!(quad.reversed().sample(&0.25), quad.sample(&0.75));
+    assert_eq!((quad.start(), quad.end()), (&[0.0, 0.0], &[2.0, 0.0]));
+
+    let cubic = CubicBezier::from([0.0f64, 1.0, 2.0, 3.0]);
+    assert_eq!(cubic.sample(&0.5), 1.5);
+    assert_eq!(cubic.to_poly().eval(&0.25), 0.75);
+    assert_eq!(cubic.reversed().points, [3.0, 2.0, 1.0, 0.0]);
+    let other = CubicBezier::new(2.0, 2.0, 2.0, 2.0);
+    assert_eq!(cubic.lerp(&other, &0.5).points, [1.0, 1.5, 2.0, 2.5]);
+}
+
+#[test]
+fn bezier_derivatives() {
+    let quad = QuadraticBezier::new([0.0f64, 0.0], [1.0, 2.0], [3.0, 0.0]);
+    let cubic = CubicBezier::new([0.0f64, 0.0], [1.0, 2.0], [3.0, 3.0], [4.0, 0.0]);
+    let h = 1e-6;
+    for i in 1..8 {
+        let t = i as f64 / 8.0;
+        let (a, b) = (quad.sample(&(t - h)), quad.sample(&(t + h)));
+        let d = quad.derivative(&t);
+        assert!(((b[0] - a[0]) / (2.0 * h) - d[0]).abs() < 1e-6 && ((b[1] - a[1]) / (2.0 * h) - d[1]).abs() < 1e-6);
+        let (a, b) = (cubic.sample(&(t - h)), cubic.sample(&(t + h)));
+        let d = cubic.derivative(&t);
+        assert!(((b[0] - a[0]) / (2.0 * h) - d[0]).abs() < 1e-6 && ((b[1] - a[1]) / (2.0 * h) - d[1]).abs() < 1e-6);
+        let (a, b) = (cubic.derivative(&(t - h)), cubic.derivative(&(t + h)));
+        let d = cubic.second_derivative(&t);
+        assert!(((b[0] - a[0]) / (2.0 * h) - d[0]).abs() < 1e-6 && ((b[1] - a[1]) / (2.0 * h) - d[1]).abs() < 1e-6);
+    }
+    assert_eq!(quad.derivative(&0.0), [2.0, 4.0]);
+    assert_eq!(quad.second_derivative(), [2.0, -8.0]);
+    assert_eq!(cubic.derivative(&1.0), [3.0, -9.0]);
+    assert_eq!(cubic.second_derivative(&0.0), [6.0, -6.0]);
+}
+
+#[test]
+fn bezier_tangent_normal() {
+    let cubic = CubicBezier::new([0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [1.0, 1.0]);
+    assert_eq!(cubic.tangent(&0.0), Some([1.0, 0.0]));
+    assert_eq!(cubic.normal(&0.0), Some([0.0, 1.0]));
+    assert_eq!(cubic.tangent(&1.0), None);
+    let quad = QuadraticBezier::new(1.0f64, 3.0, 2.0);
+    assert_eq!(quad.tangent(&0.0), Some(1.0));
+    assert_eq!(quad.tangent(&1.0), Some(-1.0));
+    // The normal is on the left of the direction of travel.
+    let n = QuadraticBezier::new([2.0f64, 0.0], [0.0, 0.0], [0.0, 2.0]).normal(&0.5).unwrap();
+    assert!((n[0] + 0.5f64.sqrt()).abs() < 1e-12 && (n[1] + 0.5f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn bezier_curvature() {
+    // A quarter circle approximated by a cubic curve turns left with a radius close to 2.
+    let k = 4.0 / 3.0 * (2.0f64.sqrt() - 1.0) * 2.0;
+    let arc = CubicBezier::new([2.0, 0.0], [2.0, k], [k, 2.0], [0.0, 2.0]);
+    for i in 0..=8 {
+        let r = arc.radius_of_curvature(&(i as f64 / 8.0)).unwrap();
+        assert!((r - 2.0).abs() < 0.05);
+    }
+    assert!((arc.reversed().curvature(&0.5).unwrap() + 0.5).abs() < 0.02);
+    let quad = QuadraticBezier::new([0.0f32, 0.0], [1.0, 1.0], [2.0, 0.0]);
+    assert_eq!(quad.curvature(&0.5), Some(-1.0));
+    assert_eq!(quad.radius_of_curvature(&0.5), Some(-1.0));
+    let line = CubicBezier::new([0.0f32, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]);
+    assert_eq!(line.curvature(&0.5), Some(0.0));
+    assert_eq!(line.radius_of_curvature(&0.5), None);
+    assert_eq!(CubicBezier::new([0.0f32, 0.0], [0.0, 0.0], [1.0, 1.0], [1.0, 0.0]).curvature(&0.0), None);
+}
+
+#[test]
+fn bezier_arc_length() {
+    fn polyline<F: Fn(f64) -> [f64; 2]>(f: F, t0: f64, t1: f64) -> f64 {
+        let n = 100_000;
+        (0..n).map(|i| {
+            let (a, b) = (f(t0 + (t1 - t0) * i as f64 / n as f64), f(t0 + (t1 - t0) * (i + 1) as f64 / n as f64));
+            ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt()
+        }).sum()
+    }
+
+    let quad = QuadraticBezier::new([0.0f64, 0.0], [1.0, 2.0], [2.0, 0.0]);
+    let expected = polyline(|t| quad.sample(&t), 0.0, 1.0);
+    assert!((quad.length() - expected).abs() < 1e-6);
+    let cubic = CubicBezier::new([0.0f64, 0.0], [4.0, 3.0], [-1.0, 3.0], [3.0, 0.0]);
+    let expected = polyline(|t| cubic.sample(&t), 0.2, 0.9);
+    assert!((cubic.arc_length(&0.2, &0.9) - expected).abs() < 1e-6);
+    assert!((cubic.arc_length(&0.9, &0.2) + expected).abs() < 1e-6);
+    // A cusp, where the speed drops to zero.
+    let cusp = CubicBezier::new([0.0f64, 0.0], [2.0, 1.0], [0.0, 1.0], [2.0, 0.0]);
+    let expected = polyline(|t| cusp.sample(&t), 0.0, 1.0);
+    assert!((cusp.length() - expected).abs() < 1e-5);
+
+    assert_eq!(CubicBezier::new(0.0f32, 1.0, 2.0, 3.0).length(), 3.0);
+    assert_eq!(QuadraticBezier::new([1.0f32, 1.0], [1.0, 1.0], [1.0, 1.0]).length(), 0.0);
+}
+
+#[test]
+fn bezier_split() {
+    let cubic = CubicBezier::new([0.0f64, 0.0], [1.0, 3.0], [3.0, -1.0], [4.0, 2.0]);
+    let (a, b) = cubic.split(&0.25);
+    assert_eq!(a.points[0], cubic.points[0]);
+    assert_eq!(b.points[3], cubic.points[3]);
+    assert_eq!(a.points[3], b.points[0]);
+    for i in 0..=8 {
+        let s = i as f64 / 8.0;
+        let (p, q) = (a.sample(&s), cubic.sample(&(0.25 * s)));
+        assert!((p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12);
+        let (p, q) = (b.sample(&s), cubic.sample(&(0.25 + 0.75 * s)));
+        assert!((p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12);
+    }
+    let quad = QuadraticBezier::new(0.0f32, 4.0, 0.0);
+    let (a, b) = quad.split(&0.5);
+    assert_eq!(a.points, [0.0, 2.0, 2.0]);
+    assert_eq!(b.points, [2.0, 2.0, 0.0]);
+    assert_eq!(cubic.split(&0.0).1, cubic);
+    assert_eq!(cubic.split(&1.0).0, cubic);
+}
+
+#[test]
+fn bezier_project() {
+    let cubic = CubicBezier::new([0.0f64, 0.0], [10.0, 30.0], [30.0, -10.0], [40.0, 20.0]);
+    let quad = QuadraticBezier::new([0.0f64, 0.0], [10.0, 30.0], [40.0, 20.0]);
+    let brute = |sample: &dyn Fn(f64) -> [f64; 2], p: [f64; 2]| {
+        (0..=10000).map(|i| sample(i as f64 / 10000.0).distance(&p)).fold(f64::INFINITY, f64::min)
+    };
+    for &p in &[[5.0, 5.0], [20.0, 30.0], [-10.0, 3.0], [50.0, 25.0], [20.0, 5.0], [35.0, -20.0]] {
+        let (t, d) = cubic.project(&p);
+        assert!((cubic.sample(&t).distance(&p) - d).abs() < 1e-12);
+        assert!(d <= brute(&|t| cubic.sample(&t), p) + 1e-9);
+        let (t, d) = quad.project(&p);
+        assert!((quad.sample(&t).distance(&p) - d).abs() < 1e-12);
+        assert!(d <= brute(&|t| quad.sample(&t), p) + 1e-9);
+    }
+    let (t, d) = cubic.project(&cubic.sample(&0.3));
+    assert!((t - 0.3).abs() < 1e-9 && d < 1e-9);
+    assert_eq!(cubic.project(&[-5.0, -5.0]), (0.0, 50.0f64.sqrt()));
+    assert_eq!(CubicBezier::new(0.0f32, 1.0, 2.0, 3.0).project(&1.5), (0.5, 0.0));
+}
+
+#[test]
+fn bezier_bounding_box() {
+    let brute = |sample: &dyn Fn(f64) -> [f64; 2]| {
+        (0..=10000).map(|i| sample(i as f64 / 10000.0)).fold(([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]),
+            |(lo, hi), p| ([lo[0].min(p[0]), lo[1].min(p[1])], [hi[0].max(p[0]), hi[1].max(p[1])]))
+    };
+    let close = |a: ([f64; 2], [f64; 2]), b: ([f64; 2], [f64; 2])| {
+        (0..2).all(|i| (a.0[i] - b.0[i]).abs() < 1e-6 && (a.1[i] - b.1[i]).abs() < 1e-6)
+    };
+    let cubic = CubicBezier::new([0.0f64, 0.0], [10.0, 30.0], [30.0, -10.0], [40.0, 20.0]);
+    let bounds = cubic.bounding_box();
+    assert!(close(bounds, brute(&|t| cubic.sample(&t))));
+    assert!(bounds.0[1] > -10.0 && bounds.1[1] < 30.0);
+    let quad = QuadraticBezier::new([0.0f64, 0.0], [10.0, 30.0], [40.0, 20.0]);
+    assert!(close(quad.bounding_box(), brute(&|t| quad.sample(&t))));
+    // A loop, where both extremes of a coordinate are inside the curve.
+    let looped = CubicBezier::new([0.0f64, 0.0], [40.0, 30.0], [-20.0, 30.0], [20.0, 0.0]);
+    assert!(close(looped.bounding_box(), brute(&|t| looped.sample(&t))));
+    // The quadratic term of the derivative vanishes.
+    let even = CubicBezier::new([0.0f32], [1.0], [2.0], [3.0]);
+    assert_eq!(even.bounding_box(), ([0.0], [3.0]));
+    assert_eq!(QuadraticBezier::new([2.0f32, 1.0], [2.0, 1.0], [2.0, 1.0]).bounding_box(), ([2.0, 1.0], [2.0, 1.0]));
+    assert_eq!(QuadraticBezier::new([0.0f32], [2.0], [0.0]).bounding_box(), ([0.0], [1.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_intersect() {
+    // Crosses the line three times, at the middle and symmetric around it.
+    let wave = CubicBezier::new([0.0f64, -1.0], [1.0, 3.0], [2.0, -3.0], [3.0, 1.0]);
+    let line = CubicBezier::new([0.0f64, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 0.0]);
+    let found = wave.intersect(&line, 1e-9);
+    assert_eq!(found.len(), 3);
+    for &(t, u) in &found {
+        let (p, q) = (wave.sample(&t), line.sample(&u));
+        assert!((p[0] - q[0]).abs() < 1e-6 && p[1].abs() < 1e-6);
+    }
+    assert!((found[1].0 - 0.5).abs() < 1e-6 && (found[0].0 + found[2].0 - 1.0).abs() < 1e-6);
+    let swapped = line.intersect(&wave, 1e-9);
+    assert_eq!(swapped.len(), 3);
+    assert!((swapped[0].1 - found[0].0).abs() < 1e-6);
+    // Curves that only share an end.
+    let next = CubicBezier::new([3.0f64, 0.0], [4.0, 1.0], [5.0, 1.0], [6.0, 0.0]);
+    let found = line.intersect(&next, 1e-9);
+    assert_eq!(found.len(), 1);
+    assert!((found[0].0 - 1.0).abs() < 1e-6 && found[0].1.abs() < 1e-6);
+    let apart = CubicBezier::new([0.0f64, 5.0], [1.0, 6.0], [2.0, 6.0], [3.0, 5.0]);
+    assert!(wave.intersect(&apart, 1e-9).is_empty());
+    assert!(!wave.intersect(&wave, 1e-3).is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_intersect_offset() {
+    // Near-parallel curves just farther apart than the tolerance are told apart
+    // long before the halving reaches the tolerance.
+    let a = CubicBezier::new([0.0f64, 0.0], [1.0, 2.0], [2.0, -2.0], [3.0, 0.0]);
+    for &(shift, tolerance) in &[(1e-7, 1e-9), (1e-6, 1e-8), (1e-3, 1e-12)] {
+        let b = CubicBezier { points: a.points.map(|[x, y]| [x, y + shift]) };
+        assert!(a.intersect(&b, tolerance).is_empty());
+        let (mut budget, mut found) = (INTERSECT_PAIRS, Vec::new());
+        intersect_parts((a, 0.0, 1.0), (b, 0.0, 1.0), INTERSECT_DEPTH, tolerance, &mut budget, &mut found);
+        assert!(found.is_empty() && budget > INTERSECT_PAIRS / 2);
+    }
+    // An offset that crosses the curve is still found.
+    let b = CubicBezier { points: a.points.map(|[x, y]| [x, y + 1e-3 * (x - 1.5)]) };
+    let found = a.intersect(&b, 1e-9);
+    assert_eq!(found.len(), 1);
+    assert!((found[0].0 - 0.5).abs() < 1e-6 && (found[0].1 - 0.5).abs() < 1e-6);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_intersect_overlap() {
+    // Overlapping curves give the middle of the overlap, even at a small tolerance.
+    let wave = CubicBezier::new([0.0f64, -1.0], [1.0, 3.0], [2.0, -3.0], [3.0, 1.0]);
+    let (half, _) = wave.split(&0.5);
+    for &tolerance in &[1e-3, 1e-9, 1e-12] {
+        assert_eq!(wave.intersect(&wave, tolerance), vec![(0.5, 0.5)]);
+        let found = wave.intersect(&half, tolerance);
+        assert_eq!(found.len(), 1);
+        assert!((found[0].0 - 0.25).abs() < 1e-9 && (found[0].1 - 0.5).abs() < 1e-9);
+        // Parts that overlap from 0.4 to 0.7 on the wave, one of them backwards.
+        let (a, b) = (wave.subcurve(&0.0, &0.7), wave.subcurve(&1.0, &0.4));
+        let found = a.intersect(&b, tolerance);
+        assert_eq!(found.len(), 1);
+        let (p, q) = (a.sample(&found[0].0), b.sample(&found[0].1));
+        let middle = wave.sample(&0.55);
+        assert!(p.distance(&middle) < 1e-9 && q.distance(&middle) < 1e-9);
+    }
+    // Straight edges with unevenly spaced control points, which overlap from 1 to 3.
+    let a = CubicBezier::new([0.0f64, 0.0], [0.5, 0.0], [2.9, 0.0], [3.0, 0.0]);
+    let b = CubicBezier::new([1.0f64, 0.0], [1.2, 0.0], [2.0, 0.0], [5.0, 0.0]);
+    let found = a.intersect(&b, 1e-12);
+    assert_eq!(found.len(), 1);
+    let (p, q) = (a.sample(&found[0].0), b.sample(&found[0].1));
+    assert!(p[0] > 1.0 && p[0] < 3.0 && q[0] > 1.0 && q[0] < 3.0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_intersect_line() {
+    let wave = CubicBezier::new([0.0f64, -1.0], [1.0, 3.0], [2.0, -3.0], [3.0, 1.0]);
+    let ts = wave.intersect_line(&[0.0, 0.0], &[1.0, 0.0]);
+    assert_eq!(ts.len(), 3);
+    assert!(ts.windows(2).all(|w| w[0] < w[1]));
+    assert!(ts.iter().all(|t| wave.sample(t)[1].abs() < 1e-12));
+    assert!((ts[1] - 0.5).abs() < 1e-12);
+    let close = |a: Vec<f64>, b: &[f64]| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12);
+    // The same line, given by another point and the opposite direction.
+    assert!(close(wave.intersect_line(&[5.0, 0.0], &[-2.0, 0.0]), &ts));
+    assert!(close(wave.intersect_ray(&[1.5, 0.0], &[1.0, 0.0]), &ts[1..]));
+    assert!(close(wave.intersect_ray(&[1.5, 0.0], &[-1.0, 0.0]), &ts[..2]));
+    assert!(close(wave.intersect_segment(&[0.0, 0.0], &[1.4, 0.0]), &ts[..1]));
+    assert!(close(wave.intersect_segment(&[3.0, 0.0], &[1.4, 0.0]), &ts[1..]));
+    // Crossings at the ends, and a line that misses.
+    assert_eq!(wave.intersect_line(&[0.0, -1.0], &[1.0, 0.0]), vec![0.0]);
+    assert_eq!(wave.intersect_line(&[0.0, 1.0], &[1.0, 0.0]).last(), Some(&1.0));
+    assert!(wave.intersect_line(&[0.0, 5.0], &[1.0, 0.0]).is_empty());
+    let line = CubicBezier::new([0.0f32, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]);
+    assert!(line.intersect_line(&[0.0, 0.0], &[1.0, 1.0]).is_empty());
+    assert_eq!(line.intersect_line(&[0.0, 3.0], &[1.0, -1.0]), vec![0.5]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_offset() {
+    let cubic = CubicBezier::new([0.0f64, 0.0], [10.0, 30.0], [30.0, -10.0], [40.0, 20.0]);
+    for &(distance, tolerance) in &[(2.0, 0.1), (-2.0, 0.1), (5.0, 0.01)] {
+        let curves = cubic.offset(distance, tolerance);
+        assert!(!curves.is_empty());
+        assert!(curves.windows(2).all(|w| w[0].points[3] == w[1].points[0]));
+        // Every offset point is at `distance` from the curve.
+        for curve in &curves {
+            for i in 0..=16 {
+                let p = curve.sample(&(i as f64 / 16.0));
+                let (_, d) = cubic.project(&p);
+                assert!((d - distance.abs()).abs() <= tolerance);
+            }
+        }
+        let start = curves[0].points[0];
+        let normal = cubic.normal(&0.0).unwrap();
+        assert!((start[0] - distance * normal[0]).abs() < 1e-12 && (start[1] - distance * normal[1]).abs() < 1e-12);
+    }
+    assert!(cubic.offset(2.0, 0.001).len() > cubic.offset(2.0, 0.1).len());
+    // A straight curve, and one with a control point on its start.
+    let line = CubicBezier::new([0.0f64, 0.0], [0.0, 0.0], [2.0, 0.0], [3.0, 0.0]);
+    assert_eq!(line.offset(1.0, 0.01), vec![CubicBezier::new([0.0, 1.0], [0.0, 1.0], [2.0, 1.0], [3.0, 1.0])]);
+    assert!(CubicBezier::new([1.0f32, 1.0], [1.0, 1.0], [1.0, 1.0], [1.0, 1.0]).offset(1.0, 0.1).is_empty());
+}
+
+#[test]
+fn bezier_subcurve() {
+    let cubic = CubicBezier::new([0.0f64, 0.0], [1.0, 3.0], [3.0, -1.0], [4.0, 2.0]);
+    let close = |p: [f64; 2], q: [f64; 2]| (p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12;
+    for &(t0, t1) in &[(0.25, 0.75), (0.0, 0.5), (0.5, 1.0), (0.8, 0.2)] {
+        let part = cubic.subcurve(&t0, &t1);
+        for i in 0..=8 {
+            let s = i as f64 / 8.0;
+            assert!(close(part.sample(&s), cubic.sample(&(t0 + (t1 - t0) * s))));
+        }
+    }
+    assert_eq!(cubic.subcurve(&0.0, &1.0), cubic);
+    assert_eq!(cubic.subcurve(&0.0, &0.0).points, [[0.0, 0.0]; 4]);
+    assert_eq!(cubic.subcurve(&1.0, &1.0).points, [[4.0, 2.0]; 4]);
+    // Backwards to the start, and from a parameter close to it.
+    for &(t0, t1) in &[(0.5, 0.0), (1.0, 0.0), (1.0, 1e-9), (1e-9, 1.0)] {
+        let part = cubic.subcurve(&t0, &t1);
+        for i in 0..=8 {
+            let s = i as f64 / 8.0;
+            assert!(close(part.sample(&s), cubic.sample(&(t0 + (t1 - t0) * s))));
+        }
+    }
+    assert_eq!(cubic.subcurve(&1.0, &0.0), cubic.reversed());
+    let cubic32 = CubicBezier::new([0.0f32, 0.0], [1.0, 3.0], [3.0, -1.0], [4.0, 2.0]);
+    let part = cubic32.subcurve(&0.9, &0.01);
+    for i in 0..=8 {
+        let s = i as f32 / 8.0;
+        let (p, q) = (part.sample(&s), cubic32.sample(&(0.9 - 0.89 * s)));
+        assert!((p[0] - q[0]).abs() < 1e-5 && (p[1] - q[1]).abs() < 1e-5);
+    }
+    let quad = QuadraticBezier::new([0.0f64, 0.0], [2.0, 4.0], [4.0, 0.0]);
+    let part = quad.subcurve(&0.25, &0.5);
+    assert!(close(part.sample(&0.5), quad.sample(&0.375)));
+    let part = quad.subcurve(&0.5, &0.0);
+    assert!(close(*part.start(), quad.sample(&0.5)) && close(*part.end(), [0.0, 0.0]));
+
+    // Trimming by distance along the curve.
+    let prepared = cubic.prepared();
+    let length = prepared.length();
+    let part = prepared.trim(0.25 * length, 0.75 * length);
+    assert!((part.length() - 0.5 * length).abs() < 1e-6 * length);
+    assert!(close(*part.start(), prepared.eval_at_fraction(0.25)));
+    assert!(close(*part.end(), prepared.eval_at_fraction(0.75)));
+    assert_eq!(prepared.trim(-1.0, 2.0 * length), cubic);
+    // Backwards, such as for erasing the curve from its end.
+    let part = prepared.trim(length, 0.0);
+    assert!(close(*part.start(), [4.0, 2.0]) && close(*part.end(), [0.0, 0.0]));
+    assert!((part.length() - length).abs() < 1e-9 * length);
+    let part = prepared.trim(0.75 * length, 0.0);
+    assert!(close(*part.start(), prepared.eval_at_fraction(0.75)) && close(*part.end(), [0.0, 0.0]));
+}
+
+#[test]
+fn bezier_elevate_reduce() {
+    let quad = QuadraticBezier::new([0.0f64, 0.0], [1.0, 3.0], [3.0, 0.0]);
+    let cubic = CubicBezier::from(quad);
+    assert_eq!(cubic.points[1], [2.0 / 3.0, 2.0]);
+    for i in 0..=8 {
+        let t = i as f64 / 8.0;
+        let (p, q) = (quad.sample(&t), cubic.sample(&t));
+        assert!((p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12);
+    }
+    let reduced = cubic.reduce();
+    assert!((reduced.points[1][0] - 1.0).abs() < 1e-12 && (reduced.points[1][1] - 3.0).abs() < 1e-12);
+    assert!(cubic.reduction_error() < 1e-12);
+
+    let cubic = CubicBezier::new([0.0f64, 0.0], [0.0, 2.0], [3.0, 1.0], [2.0, 2.0]);
+    let (reduced, bound) = (cubic.reduce(), cubic.reduction_error());
+    let worst = (0..=64).map(|i| {
+        let t = i as f64 / 64.0;
+        let (p, q) = (cubic.sample(&t), reduced.sample(&t));
+        ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt()
+    }).fold(0.0, f64::max);
+    assert!(worst <= bound + 1e-12 && worst > bound * 0.9);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_elevate_degree() {
+    let points = [0.0f64, 3.0, -2.0, 5.0];
+    let mut elevated = points.to_vec();
+    for _ in 0..3 {
+        elevated = elevate_degree(&elevated);
+    }
+    assert_eq!(elevated.len(), 7);
+    for i in 0..=8 {
+        let t = i as f64 / 8.0;
+        assert!((bezier(&points, &t).unwrap() - bezier(&elevated, &t).unwrap()).abs() < 1e-12);
+    }
+    assert_eq!(elevate_degree(&[1.0f32, 3.0]), vec![1.0, 2.0, 3.0]);
+    assert_eq!(elevate_degree(&[1.0f32]), vec![1.0, 1.0]);
+    assert!(elevate_degree::<f32>(&[]).is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_flatten() {
+    fn segment_distance(p: [f64; 2], a: [f64; 2], b: [f64; 2]) -> f64 {
+        let (ab, ap) = ([b[0] - a[0], b[1] - a[1]], [p[0] - a[0], p[1] - a[1]]);
+        let len2 = ab[0] * ab[0] + ab[1] * ab[1];
+        let t = if len2 > 0.0 { ((ap[0] * ab[0] + ap[1] * ab[1]) / len2).clamp(0.0, 1.0) } else { 0.0 };
+        ((ap[0] - t * ab[0]).powi(2) + (ap[1] - t * ab[1]).powi(2)).sqrt()
+    }
+    fn polyline_distance(p: [f64; 2], points: &[[f64; 2]]) -> f64 {
+        points.windows(2).map(|w| segment_distance(p, w[0], w[1])).fold(f64::INFINITY, f64::min)
+    }
+
+    let cubic = CubicBezier::new([0.0f64, 0.0], [10.0, 30.0], [30.0, -10.0], [40.0, 20.0]);
+    let quad = QuadraticBezier::new([0.0f64, 0.0], [10.0, 30.0], [40.0, 20.0]);
+    for &tolerance in &[1.0, 0.1, 0.01] {
+        let points = cubic.flatten(tolerance);
+        assert_eq!((points[0], *points.last().unwrap()), (cubic.points[0], cubic.points[3]));
+        assert!((0..=256).all(|i| polyline_distance(cubic.sample(&(i as f64 / 256.0)), &points) <= tolerance));
+        let points = quad.flatten(tolerance);
+        assert!((0..=256).all(|i| polyline_distance(quad.sample(&(i as f64 / 256.0)), &points) <= tolerance));
+    }
+    assert!(cubic.flatten(0.01).len() > cubic.flatten(1.0).len());
+    let line = CubicBezier::new([0.0f32, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]);
+    assert_eq!(line.flatten(0.1), vec![[0.0, 0.0], [3.0, 3.0]]);
+    assert_eq!(cubic.flatten(0.0).len(), 1025);
+    assert_eq!(cubic.flatten(f64::NAN).len(), 1025);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bezier_long_and_in_place() {
+    let points: Vec<f32> = (0..12).map(|i| (i * i) as f32).collect();
+    let mut scratch = points.clone();
+    let value = bezier_in_place(&mut scratch, &0.25);
+    assert_eq!(bezier(&points, &0.25), value);
+    // The scratch buffer holds the rest of the curve.
+    assert_eq!(scratch[0], value.unwrap());
+    assert_eq!(scratch[11], 121.0);
+    assert_eq!(bezier(&scratch, &0.0), value);
+    let rest = bezier(&scratch, &(1.0 / 3.0)).unwrap();
+    assert!((rest - bezier(&points, &0.5).unwrap()).abs() < 1e-3);
+}
+
+
@@ -0,0 +1,110 @@
+//! Easing curves defined by cubic Bézier control points
+
+use float::mul_add;
+
+/// An easing curve through `(0, 0)` and `(1, 1)`,
+/// shaped by two control points like a CSS `cubic-bezier` timing function.
+///
+/// The x coordinates of the control points are clamped to `[0, 1]`,
+/// so the curve is a function of progress.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct CubicBezierEase {
+    /// The x coordinate of the first control point.
+    pub x1: f64,
+    /// The y coordinate of the first control point.
+    pub y1: f64,
+    /// The x coordinate of the second control point.
+    pub x2: f64,
+    /// The y coordinate of the second control point.
+    pub y2: f64,
+}
+
+impl CubicBezierEase {
+    /// Creates an easing curve from two control points.
+    pub fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> CubicBezierEase {
+        CubicBezierEase {
+            x1: x1.clamp(0.0, 1.0),
+            y1,
+            x2: x2.clamp(0.0, 1.0),
+            y2,
+        }
+    }
+
+    /// Evaluates one coordinate of the curve at parameter `t`,
+    /// given the coordinates of the control points.
+    fn bezier(a: f64, b: f64, t: f64) -> f64 {
+        let u = 1.0 - t;
+        mul_add(3.0 * u * u * t, a, 3.0 * u * t * t * b) + t * t * t
+    }
+
+    fn bezier_derivative(a: f64, b: f64, t: f64) -> f64 {
+        let u = 1.0 - t;
+        mul_add(3.0 * u * u, a, 6.0 * u * t * (b - a)) + 3.0 * t * t * (1.0 - b)
+    }
+
+    /// Finds the curve parameter where the x coordinate is `x`.
+    pub(crate) fn solve(&self, x: f64) -> f64 {
+        const EPSILON: f64 = 1e-9;
+
+        // Newton's method converges quickly on most curves.
+        let mut t = x;
+        for _ in 0..8 {
+            let err = Self::bezier(self.x1, self.x2, t) - x;
+            if err.abs() < EPSILON {
+                return t;
+            }
+            let d = Self::bezier_derivative(self.x1, self.x2, t);
+            if d.abs() < 1e-6 {
+                break;
+            }
+            t -= err / d;
+        }
+
+        // Bisection handles flat spots, where the derivative vanishes.
+        let (mut lo, mut hi) = (0.0, 1.0);
+        t = x;
+        for _ in 0..64 {
+            let v = Self::bezier(self.x1, self.x2, t);
+            if (v - x).abs() < EPSILON {
+                break;
+            }
+            if v < x { lo = t } else { hi = t }
+            t = 0.5 * (lo + hi);
+        }
+        t
+    }
+
+    /// Returns the eased value of progress `p`, clamped to `[0, 1]`.
+    pub fn calc(&self, p: f64) -> f64 {
+        if p.is_nan() {
+            return p;
+        }
+        if p <= 0.0 {
+            return 0.0;
+        }
+        if p >= 1.0 {
+            return 1.0;
+        }
+        Self::bezier(self.y1, self.y2, self.solve(p))
+    }
+}
+
+#[test]
+fn cubic_bezier_ease() {
+    let linear = CubicBezierEase::new(0.25, 0.25, 0.75, 0.75);
+    for i in 0..=10 {
+        let p = i as f64 / 10.0;
+        assert!((linear.calc(p) - p).abs() < 1e-9);
+    }
+
+    // The CSS `ease-in-out` curve is symmetric around the middle.
+    let ease_in_out = CubicBezierEase::new(0.42, 0.0, 0.58, 1.0);
+    assert!((ease_in_out.calc(0.5) - 0.5).abs() < 1e-9);
+    assert!((ease_in_out.calc(0.25) + ease_in_out.calc(0.75) - 1.0).abs() < 1e-9);
+
+    let overshoot = CubicBezierEase::new(0.5, 1.5, 0.5, 1.5);
+    assert!(overshoot.calc(0.5) > 1.0);
+    assert_eq!(CubicBezierEase::new(2.0, 0.0, -1.0, 1.0).x1, 1.0);
+}
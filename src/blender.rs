@@ -0,0 +1,90 @@
+//! Cross-fading between the outputs of two states
+
+use ease::{Ease, Easing};
+use float::Float;
+use lerp::Lerp;
+
+/// Manages the cross-fade weight when an animation state machine changes state.
+///
+/// The blender does not own the states.
+/// Each frame, pass the output of the previous and the new state to `blend`.
+/// When a state changes during a blend, pass the last blended output as the previous state.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TransitionBlender<S> {
+    /// The time the cross-fade takes.
+    pub duration: S,
+    /// The easing of the cross-fade weight.
+    pub easing: Easing,
+    elapsed: S,
+}
+
+impl<S> TransitionBlender<S>
+    where S: Float + Ease
+{
+    /// Creates a finished blender with linear easing.
+    pub fn new(duration: S) -> TransitionBlender<S> {
+        TransitionBlender {
+            duration,
+            easing: Easing::Linear,
+            elapsed: duration,
+        }
+    }
+
+    /// Sets the easing.
+    pub fn easing<E: Into<Easing>>(mut self, easing: E) -> TransitionBlender<S> {
+        self.easing = easing.into();
+        self
+    }
+
+    /// Starts a new cross-fade from the previous state.
+    pub fn start(&mut self) {
+        self.elapsed = S::zero();
+    }
+
+    /// Advances the cross-fade by `dt`.
+    pub fn update(&mut self, dt: S) {
+        let elapsed = self.elapsed + dt;
+        self.elapsed = if elapsed > self.duration { self.duration } else { elapsed };
+    }
+
+    /// Returns `true` when the new state has full weight.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Returns the weight of the new state, between zero and one.
+    pub fn weight(&self) -> S {
+        if self.is_finished() || self.duration <= S::zero() {
+            S::one()
+        } else {
+            self.easing.calc(self.elapsed / self.duration)
+        }
+    }
+
+    /// Blends the output of the previous state with the output of the new state.
+    pub fn blend<T>(&self, previous: &T, next: &T) -> T
+        where T: Lerp<Scalar = S>
+    {
+        previous.lerp(next, &self.weight())
+    }
+}
+
+#[test]
+fn transition_blender() {
+    use ease::EaseFunction;
+
+    let mut blender = TransitionBlender::new(2.0f32);
+    assert_eq!(blender.blend(&0.0f32, &10.0), 10.0);
+    blender.start();
+    assert_eq!(blender.blend(&0.0f32, &10.0), 0.0);
+    blender.update(1.0);
+    assert_eq!(blender.blend(&0.0f32, &10.0), 5.0);
+    blender.update(5.0);
+    assert!(blender.is_finished());
+    assert_eq!(blender.weight(), 1.0);
+
+    let mut blender = TransitionBlender::new(1.0f64).easing(EaseFunction::QuadraticIn);
+    blender.start();
+    blender.update(0.5);
+    assert_eq!(blender.blend(&[0.0, 0.0], &[4.0, 8.0]), [1.0, 2.0]);
+}
@@ -0,0 +1,306 @@
+//! B-spline curves with a knot vector
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use error::{self, check_len, check_non_decreasing, InterpolationError};
+use float::Float;
+use lerp::Lerp;
+
+/// A B-spline curve of any degree, with control points and a knot vector.
+///
+/// There are `degree + 1` more knots than control points.
+/// The curve is defined between the knot at index `degree`
+/// and the knot at the index of the number of control points.
+/// It passes through the first and last control points when the first
+/// and last `degree + 1` knots are equal, as with `BSpline::clamped`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline<T: Lerp> {
+    degree: usize,
+    points: Vec<T>,
+    knots: Vec<T::Scalar>,
+}
+
+impl<T> BSpline<T>
+    where T: Lerp + Clone, T::Scalar: Float
+{
+    /// Creates a B-spline from its degree, control points and knots.
+    ///
+    /// If the number of knots does not match, the curve evaluates to `None`.
+    pub fn new(degree: usize, points: Vec<T>, knots: Vec<T::Scalar>) -> BSpline<T> {
+        BSpline { degree, points, knots }
+    }
+
+    /// Creates a B-spline, checking that there are more control points than the degree,
+    /// that there are `degree + 1` more knots than control points,
+    /// and that the knots never decrease and span a non-empty domain.
+    pub fn try_new(degree: usize, points: Vec<T>, knots: Vec<T::Scalar>) -> error::Result<BSpline<T>> {
+        if points.is_empty() {
+            return Err(InterpolationError::Empty);
+        } else if degree >= points.len() {
+            return Err(InterpolationError::InvalidParameter { name: "degree" });
+        }
+        check_len(points.len() + degree + 1, knots.len())?;
+        check_non_decreasing(&knots)?;
+        let spline = BSpline::new(degree, points, knots);
+        match spline.domain() {
+            Some((start, end)) if start < end => Ok(spline),
+            _ => Err(InterpolationError::InvalidParameter { name: "knots" }),
+        }
+    }
+
+    /// Creates a B-spline through the first and last control points,
+    /// with uniformly spaced knots between 0 and 1.
+    ///
+    /// The degree is lowered when there are not enough control points for it.
+    /// With `degree + 1` control points, this is a Bézier curve.
+    pub fn clamped(degree: usize, points: Vec<T>) -> BSpline<T> {
+        let n = points.len();
+        let degree = degree.min(n.saturating_sub(1));
+        let spans = n - degree;
+        let zero = T::Scalar::zero();
+        let one = T::Scalar::one();
+        let knots = (0..n + degree + 1).map(|i| {
+            if i <= degree {
+                zero
+            } else if i >= n {
+                one
+            } else {
+                T::Scalar::from_f64((i - degree) as f64 / spans as f64)
+            }
+        }).collect();
+        BSpline { degree, points, knots }
+    }
+
+    /// Returns the degree.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the control points.
+    pub fn points(&self) -> &[T] {
+        &self.points
+    }
+
+    /// Returns the knots.
+    pub fn knots(&self) -> &[T::Scalar] {
+        &self.knots
+    }
+
+    /// Returns the first and last parameter of the curve,
+    /// or `None` if the number of knots does not match.
+    pub fn domain(&self) -> Option<(T::Scalar, T::Scalar)> {
+        if self.knots.len() != self.points.len() + self.degree + 1 {
+            return None;
+        }
+        Some((*self.knots.get(self.degree)?, *self.knots.get(self.points.len())?))
+    }
+
+    /// Returns the point at `t` with de Boor's algorithm,
+    /// which evaluates the Cox-de Boor recursion with linear interpolations.
+    ///
+    /// `t` is clamped to the domain, and NaN gives the start.
+    pub fn sample(&self, t: T::Scalar) -> Option<T> {
+        let (k, t) = self.span(t)?;
+        let mut d = self.points.get(k - self.degree..=k)?.to_vec();
+        self.de_boor(k, t, &mut d, |a, b, alpha| a.lerp(b, &alpha))
+    }
+
+    /// Inserts the knot `u` with Boehm's algorithm, without changing the shape of the curve.
+    ///
+    /// One control point is added and the ones next to `u` are moved.
+    /// Returns `false` and leaves the curve unchanged if `u` is outside the domain or NaN,
+    /// if the knots do not match, or if `u` is already a knot `degree` times,
+    /// where the curve already passes through a control point.
+    pub fn insert_knot(&mut self, u: T::Scalar) -> bool {
+        let (start, end) = match self.domain() {
+            Some(domain) => domain,
+            None => return false,
+        };
+        match (u.partial_cmp(&start), u.partial_cmp(&end)) {
+            (Some(Ordering::Less), _) | (None, _) | (_, Some(Ordering::Greater)) => return false,
+            _ => {}
+        }
+        let p = self.degree;
+        if self.knots.iter().filter(|&&knot| knot == u).count() >= p {
+            return false;
+        }
+        let k = match self.span(u) {
+            Some((k, _)) => k,
+            None => return false,
+        };
+        let zero = T::Scalar::zero();
+        let points: Option<Vec<T>> = (0..=self.points.len()).map(|i| {
+            if i + p <= k {
+                self.points.get(i).cloned()
+            } else if i > k {
+                self.points.get(i - 1).cloned()
+            } else {
+                let (u0, u1) = (*self.knots.get(i)?, *self.knots.get(i + p)?);
+                let alpha = if u1 > u0 { (u - u0) / (u1 - u0) } else { zero };
+                Some(self.points.get(i - 1)?.lerp(self.points.get(i)?, &alpha))
+            }
+        }).collect();
+        match points {
+            Some(points) => {
+                self.points = points;
+                self.knots.insert(k + 1, u);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts several knots with `insert_knot`, and returns how many were inserted.
+    pub fn refine(&mut self, knots: &[T::Scalar]) -> usize {
+        knots.iter().filter(|&&u| self.insert_knot(u)).count()
+    }
+
+    /// Splits the curve into Bézier curves, one per non-empty knot span,
+    /// by inserting every knot of the domain until it is repeated `degree` times.
+    ///
+    /// Each curve has `degree + 1` control points, which can be evaluated with `bezier`
+    /// from 0 to 1 over its knot span.
+    pub fn bezier_segments(&self) -> Vec<Vec<T>> {
+        let mut spline = self.clone();
+        let (start, end) = match self.domain() {
+            Some(domain) => domain,
+            None => return Vec::new(),
+        };
+        let mut breaks: Vec<T::Scalar> = self.knots.iter().cloned()
+            .filter(|&u| u >= start && u <= end).collect();
+        breaks.dedup();
+        for u in breaks {
+            while spline.insert_knot(u) {}
+        }
+        let p = spline.degree;
+        (p..spline.points.len()).filter_map(|k| {
+            match (spline.knots.get(k), spline.knots.get(k + 1)) {
+                (Some(u0), Some(u1)) if u0 < u1 => spline.points.get(k - p..=k).map(|points| points.to_vec()),
+                _ => None,
+            }
+        }).collect()
+    }
+
+    /// Clamps `t` to the domain, and returns the index `k` of the knot span
+    /// with `knots[k] <= t < knots[k + 1]`, or the last non-empty span at the end.
+    pub(crate) fn span(&self, t: T::Scalar) -> Option<(usize, T::Scalar)> {
+        let (start, end) = self.domain()?;
+        let t = match (t.partial_cmp(&start), t.partial_cmp(&end)) {
+            (Some(Ordering::Less), _) | (None, _) => start,
+            (_, Some(Ordering::Greater)) => end,
+            _ => t,
+        };
+        let (p, n) = (self.degree, self.points.len());
+        let k = p + self.knots.get(p + 1..n)?.iter().take_while(|&&u| u <= t && u < end).count();
+        Some((k, t))
+    }
+
+    /// Runs de Boor's algorithm on the `degree + 1` values of span `k`,
+    /// which can be control points or other values that are interpolated like them.
+    pub(crate) fn de_boor<P, F>(&self, k: usize, t: T::Scalar, d: &mut [P], lerp: F) -> Option<P>
+        where P: Clone, F: Fn(&P, &P, T::Scalar) -> P
+    {
+        let p = self.degree;
+        let zero = T::Scalar::zero();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let i = j + k - p;
+                let (u0, u1) = (*self.knots.get(i)?, *self.knots.get(i + 1 + p - r)?);
+                let alpha = if u1 > u0 { (t - u0) / (u1 - u0) } else { zero };
+                if let Some([a, b, ..]) = d.get_mut(j - 1..) {
+                    *b = lerp(a, b, alpha);
+                }
+            }
+        }
+        d.last().cloned()
+    }
+}
+
+#[test]
+fn bspline_bezier() {
+    let points = vec![[0.0f64, 0.0], [1.0, 2.0], [3.0, 2.0], [4.0, 0.0]];
+    let spline = BSpline::clamped(3, points.clone());
+    assert_eq!(spline.knots(), &[0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+    for i in 0..=8 {
+        let t = i as f64 / 8.0;
+        let expected = ::cub_bez(&points[0], &points[1], &points[2], &points[3], &t);
+        let p = spline.sample(t).unwrap();
+        assert!((p[0] - expected[0]).abs() < 1e-12 && (p[1] - expected[1]).abs() < 1e-12);
+    }
+    assert_eq!(spline.sample(-1.0), Some(points[0]));
+    assert_eq!(spline.sample(f64::NAN), Some(points[0]));
+    assert_eq!(spline.sample(2.0), Some(points[3]));
+
+    let polyline = BSpline::clamped(1, vec![0.0f32, 2.0, 0.0]);
+    assert_eq!(polyline.knots(), &[0.0, 0.0, 0.5, 1.0, 1.0]);
+    assert_eq!(polyline.sample(0.25), Some(1.0));
+    assert_eq!(polyline.sample(0.75), Some(1.0));
+    assert_eq!(BSpline::clamped(5, vec![1.0f32, 3.0]).sample(0.5), Some(2.0));
+    assert_eq!(BSpline::<f32>::clamped(3, vec![]).sample(0.5), None);
+}
+
+#[test]
+fn bspline_uniform() {
+    // At the knots of a uniform cubic B-spline, the weights are 1/6, 4/6 and 1/6.
+    let points = vec![0.0f64, 6.0, 0.0, 6.0, 12.0];
+    let spline = BSpline::try_new(3, points, (0..9).map(|i| i as f64).collect()).unwrap();
+    assert_eq!(spline.domain(), Some((3.0, 5.0)));
+    assert!((spline.sample(3.0).unwrap() - 4.0).abs() < 1e-12);
+    assert!((spline.sample(4.0).unwrap() - 2.0).abs() < 1e-12);
+    assert!((spline.sample(5.0).unwrap() - 6.0).abs() < 1e-12);
+    // A repeated knot at the end of the domain is skipped.
+    let spline = BSpline::try_new(1, vec![0.0f32, 1.0, 3.0], vec![0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+    assert_eq!(spline.sample(1.0), Some(1.0));
+    assert_eq!(BSpline::new(1, vec![0.0f32, 1.0], vec![0.0, 1.0]).sample(0.5), None);
+}
+
+#[test]
+fn bspline_insert_knot() {
+    let spline = BSpline::clamped(3, vec![[0.0f64, 0.0], [1.0, 3.0], [2.0, -1.0], [4.0, 2.0], [5.0, 0.0]]);
+    let mut refined = spline.clone();
+    assert!(refined.insert_knot(0.25));
+    assert_eq!(refined.points().len(), 6);
+    assert_eq!(refined.knots(), &[0.0, 0.0, 0.0, 0.0, 0.25, 0.5, 1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(refined.refine(&[0.5, 0.5, 0.5, 0.75, 2.0, f64::NAN]), 3);
+    assert_eq!(refined.knots().iter().filter(|&&u| u == 0.5).count(), 3);
+    assert!(!refined.insert_knot(1.0));
+    for i in 0..=16 {
+        let t = i as f64 / 16.0;
+        let (a, b) = (spline.sample(t).unwrap(), refined.sample(t).unwrap());
+        assert!((a[0] - b[0]).abs() < 1e-12 && (a[1] - b[1]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn bspline_bezier_segments() {
+    let spline = BSpline::clamped(3, vec![0.0f64, 3.0, -1.0, 2.0, 0.0, 1.0]);
+    let segments = spline.bezier_segments();
+    assert_eq!(segments.len(), 3);
+    for (i, segment) in segments.iter().enumerate() {
+        assert_eq!(segment.len(), 4);
+        for j in 0..=4 {
+            let s = j as f64 / 4.0;
+            let t = (i as f64 + s) / 3.0;
+            assert!((::bezier(segment, &s).unwrap() - spline.sample(t).unwrap()).abs() < 1e-12);
+        }
+    }
+    // The ends of an unclamped spline are clamped first.
+    let spline = BSpline::new(2, vec![0.0f64, 4.0, 1.0, 3.0], (0..7).map(|i| i as f64).collect());
+    let segments = spline.bezier_segments();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0][0], spline.sample(2.0).unwrap());
+    assert_eq!(segments[1][2], spline.sample(4.0).unwrap());
+}
+
+#[test]
+fn bspline_try_new() {
+    use error::InterpolationError::*;
+
+    assert_eq!(BSpline::<f32>::try_new(1, vec![], vec![]).err(), Some(Empty));
+    assert_eq!(BSpline::try_new(2, vec![0.0f32, 1.0], vec![0.0; 5]).err(), Some(InvalidParameter { name: "degree" }));
+    assert_eq!(BSpline::try_new(1, vec![0.0f32, 1.0], vec![0.0; 3]).err(),
+               Some(MismatchedLengths { expected: 4, found: 3 }));
+    assert_eq!(BSpline::try_new(1, vec![0.0f32, 1.0], vec![0.0, 1.0, 0.5, 2.0]).err(), Some(NotIncreasing { index: 2 }));
+    assert_eq!(BSpline::try_new(1, vec![0.0f32, 1.0], vec![0.0, 1.0, 1.0, 2.0]).err(), Some(InvalidParameter { name: "knots" }));
+}
@@ -0,0 +1,100 @@
+//! Camera position, orientation and field of view blending
+
+use ease::{Ease, Easing};
+use float::Float;
+use lerp::Lerp;
+use slerp::Slerp;
+use tween::Tween;
+
+/// Moves a camera towards a target with damping, and eases the field of view.
+///
+/// Position and rotation follow their targets exponentially,
+/// so the motion is independent of the frame rate.
+/// The field of view is eased over a fixed duration when zooming.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraBlend<V, R, S>
+    where S: Lerp<Scalar = S>
+{
+    /// The current position.
+    pub position: V,
+    /// The current rotation.
+    pub rotation: R,
+    /// The position to follow.
+    pub target_position: V,
+    /// The rotation to follow.
+    pub target_rotation: R,
+    /// How fast the position follows, as a rate per unit of time.
+    pub position_rate: S,
+    /// How fast the rotation follows, as a rate per unit of time.
+    pub rotation_rate: S,
+    fov: Tween<S>,
+}
+
+impl<V, R, S> CameraBlend<V, R, S>
+    where V: Lerp<Scalar = S> + Clone,
+          R: Slerp<Scalar = S> + Clone,
+          S: Lerp<Scalar = S> + Float + Ease
+{
+    /// Creates a camera at rest, with follow rates of one.
+    pub fn new(position: V, rotation: R, fov: S) -> CameraBlend<V, R, S> {
+        CameraBlend {
+            target_position: position.clone(),
+            target_rotation: rotation.clone(),
+            position,
+            rotation,
+            position_rate: S::one(),
+            rotation_rate: S::one(),
+            fov: Tween::new(fov, fov, S::zero()),
+        }
+    }
+
+    /// Sets the position and rotation to follow.
+    pub fn set_target(&mut self, position: V, rotation: R) {
+        self.target_position = position;
+        self.target_rotation = rotation;
+    }
+
+    /// Eases the field of view from its current value to `fov` over `duration`.
+    pub fn zoom<E: Into<Easing>>(&mut self, fov: S, duration: S, easing: E) {
+        self.fov = Tween::new(self.fov.value(), fov, duration).easing(easing);
+    }
+
+    /// Moves the camera towards its target and advances the zoom by `dt`.
+    pub fn update(&mut self, dt: S) {
+        let position = S::one() - (-self.position_rate * dt).exp();
+        let rotation = S::one() - (-self.rotation_rate * dt).exp();
+        self.position = self.position.lerp(&self.target_position, &position);
+        self.rotation = self.rotation.slerp(&self.target_rotation, &rotation);
+        self.fov.update(dt);
+    }
+
+    /// Returns the current field of view.
+    pub fn fov(&self) -> S {
+        self.fov.value()
+    }
+
+    /// Returns `true` while the field of view is changing.
+    pub fn is_zooming(&self) -> bool {
+        !self.fov.is_finished()
+    }
+}
+
+#[test]
+fn camera_blend() {
+    use ease::EaseFunction;
+
+    let mut camera = CameraBlend::new([0.0f64, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0], 60.0);
+    camera.position_rate = 2.0f64.ln();
+    camera.set_target([8.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0]);
+    camera.zoom(90.0, 2.0, EaseFunction::QuadraticIn);
+    assert!(camera.is_zooming());
+
+    camera.update(1.0);
+    assert!((camera.position[0] - 4.0).abs() < 1e-12);
+    assert_eq!(camera.fov(), 67.5);
+
+    camera.update(1.0);
+    assert!((camera.position[0] - 6.0).abs() < 1e-12);
+    assert_eq!(camera.fov(), 90.0);
+    assert!(!camera.is_zooming());
+}
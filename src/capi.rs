@@ -0,0 +1,121 @@
+//! C interface to the core evaluators
+//!
+//! The functions use C types and the `u32` enum `EaseFunction` only,
+//! and the track is an opaque handle,
+//! so a header can be generated with cbindgen.
+//! Build the crate as a `staticlib` or `cdylib` with the `capi` feature
+//! to link it into a C or C++ engine.
+
+use alloc::boxed::Box;
+use alloc::vec;
+use core::slice;
+
+use bake::BakedTrack;
+use ease::{Ease, EaseFunction};
+use lerp::Lerp;
+
+/// Evaluates an ease function at `t`.
+///
+/// `EaseFunction` is a `u32` enum in C, and must be one of its declared values.
+#[no_mangle]
+pub extern "C" fn interpolation_ease_f32(function: EaseFunction, t: f32) -> f32 {
+    t.calc(function)
+}
+
+/// Evaluates an ease function at `t`, see `interpolation_ease_f32`.
+#[no_mangle]
+pub extern "C" fn interpolation_ease_f64(function: EaseFunction, t: f64) -> f64 {
+    t.calc(function)
+}
+
+/// Interpolates linearly between `a` and `b`.
+#[no_mangle]
+pub extern "C" fn interpolation_lerp_f32(a: f32, b: f32, t: f32) -> f32 {
+    a.lerp(&b, &t)
+}
+
+/// Interpolates linearly between `a` and `b`.
+#[no_mangle]
+pub extern "C" fn interpolation_lerp_f64(a: f64, b: f64, t: f64) -> f64 {
+    a.lerp(&b, &t)
+}
+
+/// Evaluates a cubic Bézier curve through `x0` and `x3`, with control points `x1` and `x2`.
+#[no_mangle]
+pub extern "C" fn interpolation_cubic_bezier_f32(x0: f32, x1: f32, x2: f32, x3: f32, t: f32) -> f32 {
+    ::cub_bez(&x0, &x1, &x2, &x3, &t)
+}
+
+/// Evaluates a cubic Bézier curve through `x0` and `x3`, with control points `x1` and `x2`.
+#[no_mangle]
+pub extern "C" fn interpolation_cubic_bezier_f64(x0: f64, x1: f64, x2: f64, x3: f64, t: f64) -> f64 {
+    ::cub_bez(&x0, &x1, &x2, &x3, &t)
+}
+
+/// A baked track, owned by the caller through a pointer.
+pub struct InterpolationTrack {
+    track: BakedTrack<f32>,
+}
+
+/// Creates a track from `len` values sampled every `step` from `start`.
+///
+/// The track must be freed with `interpolation_track_free`.
+///
+/// # Safety
+///
+/// `values` must point to `len` readable floats, or be null if `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn interpolation_track_new(
+    start: f32,
+    step: f32,
+    values: *const f32,
+    len: usize
+) -> *mut InterpolationTrack {
+    let values = if len == 0 { vec![] } else { slice::from_raw_parts(values, len).to_vec() };
+    let track = BakedTrack { start, step, values };
+    Box::into_raw(Box::new(InterpolationTrack { track }))
+}
+
+/// Returns the value of a track at `time`, or NaN if the track is empty or null.
+///
+/// # Safety
+///
+/// `track` must be null or a pointer returned by `interpolation_track_new`
+/// that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn interpolation_track_sample(track: *const InterpolationTrack, time: f32) -> f32 {
+    match track.as_ref() {
+        Some(t) => t.track.sample(time).unwrap_or(f32::NAN),
+        None => f32::NAN,
+    }
+}
+
+/// Frees a track. Does nothing if `track` is null.
+///
+/// # Safety
+///
+/// `track` must be null or a pointer returned by `interpolation_track_new`
+/// that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn interpolation_track_free(track: *mut InterpolationTrack) {
+    if !track.is_null() {
+        drop(Box::from_raw(track));
+    }
+}
+
+#[test]
+fn capi_evaluators() {
+    assert_eq!(interpolation_ease_f32(EaseFunction::CubicIn, 0.5), 0.125);
+    assert_eq!(interpolation_ease_f64(EaseFunction::QuadraticIn, 0.5), 0.25);
+    assert!(EaseFunction::ALL.iter().enumerate().all(|(i, &f)| f as u32 == i as u32));
+    assert_eq!(interpolation_lerp_f64(1.0, 3.0, 0.5), 2.0);
+    assert_eq!(interpolation_cubic_bezier_f32(0.0, 1.0, 2.0, 3.0, 0.5), 1.5);
+
+    let values = [0.0f32, 2.0, 4.0];
+    unsafe {
+        let track = interpolation_track_new(1.0, 0.5, values.as_ptr(), values.len());
+        assert_eq!(interpolation_track_sample(track, 1.25), 1.0);
+        interpolation_track_free(track);
+        assert!(interpolation_track_sample(::std::ptr::null(), 0.0).is_nan());
+    }
+}
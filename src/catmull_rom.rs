@@ -0,0 +1,87 @@
+//! Catmull-Rom splines through waypoints
+//!
+//! A Catmull-Rom segment runs from `p1` to `p2`, with tangents taken from the
+//! neighbouring points `p0` and `p3`. The parameter `alpha` chooses how the
+//! knots are spaced: `0.0` gives the uniform spline, `0.5` the centripetal spline
+//! and `1.0` the chordal spline. The uniform spline can form cusps and loops
+//! when the points are unevenly spaced, which the centripetal spline never does,
+//! so it is the usual choice for moving along arbitrary waypoints.
+
+use float::Float;
+use poly::CubicPoly;
+use spatial::InnerSpace;
+
+/// Returns the control points of a cubic Bézier curve that matches
+/// the Catmull-Rom segment from `p1` to `p2`.
+///
+/// When `p0` coincides with `p1`, or `p3` with `p2`, it is replaced by the reflection
+/// of the other neighbour, which gives a straight handle.
+pub fn catmull_rom_bezier<T>(p0: &T, p1: &T, p2: &T, p3: &T, alpha: &T::Scalar) -> [T; 4]
+    where T: InnerSpace + Clone, T::Scalar: Float
+{
+    let zero = T::Scalar::zero();
+    let three = T::Scalar::from_f64(3.0);
+    let half = *alpha * T::Scalar::from_f64(0.5);
+    let knot = |a: &T, b: &T| {
+        let d = b.sub(a);
+        d.dot(&d).powf(half)
+    };
+    let (d01, d12, d23) = (p1.sub(p0), p2.sub(p1), p3.sub(p2));
+    let d2 = knot(p1, p2);
+    let (d01, d1) = if d01.dot(&d01) == zero { (d12.clone(), d2) } else { (d01, knot(p0, p1)) };
+    let (d23, d3) = if d23.dot(&d23) == zero { (d12.clone(), d2) } else { (d23, knot(p2, p3)) };
+    let handle = |d_near: T::Scalar, d_far: T::Scalar, outer: &T| {
+        if d_near + d_far == zero {
+            return d12.scale(&zero);
+        }
+        let a = d_near / (three * (d_near + d_far));
+        let b = d_far * d_far / (three * d_near * (d_near + d_far));
+        d12.scale(&a).add(&outer.scale(&b))
+    };
+    let b1 = p1.add(&handle(d1, d2, &d01));
+    let b2 = p2.sub(&handle(d3, d2, &d23));
+    [p1.clone(), b1, b2, p2.clone()]
+}
+
+/// Evaluates the Catmull-Rom segment from `p1` to `p2` at `t`.
+///
+/// To evaluate the same segment many times, convert it once with
+/// `catmull_rom_bezier` and `CubicPoly::from_bezier`.
+pub fn catmull_rom<T>(p0: &T, p1: &T, p2: &T, p3: &T, alpha: &T::Scalar, t: &T::Scalar) -> T
+    where T: InnerSpace + Clone, T::Scalar: Float
+{
+    let [b0, b1, b2, b3] = catmull_rom_bezier(p0, p1, p2, p3, alpha);
+    CubicPoly::from_bezier(&b0, &b1, &b2, &b3).eval(t)
+}
+
+#[test]
+fn catmull_rom_uniform() {
+    let (p0, p1, p2, p3) = (-1.0f64, 2.0, 0.5, 4.0);
+    for i in 0..=8 {
+        let x = i as f64 / 8.0;
+        let c1 = 0.5 * (p2 - p0);
+        let c2 = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+        let c3 = 0.5 * (p3 - p0) + 1.5 * (p1 - p2);
+        let expected = ((c3 * x + c2) * x + c1) * x + p1;
+        assert!((catmull_rom(&p0, &p1, &p2, &p3, &0.0, &x) - expected).abs() < 1e-12);
+    }
+    let [_, b1, b2, _] = catmull_rom_bezier(&1.0f32, &1.0, &4.0, &4.0, &0.5);
+    assert_eq!((b1, b2), (2.0, 3.0));
+}
+
+#[test]
+fn catmull_rom_centripetal() {
+    // A short segment between long ones makes the uniform spline loop back.
+    let (p0, p1, p2, p3) = ([0.0f64, 0.0], [10.0, 0.0], [10.5, 0.0], [20.5, 0.0]);
+    let xs = |alpha: f64| (0..=16).map(move |i| catmull_rom(&p0, &p1, &p2, &p3, &alpha, &(i as f64 / 16.0))[0]);
+    assert!(xs(0.0).any(|x| x < 10.0));
+    for alpha in [0.5, 1.0] {
+        let mut prev = 10.0;
+        for x in xs(alpha) {
+            assert!(x >= prev - 1e-12 && x <= 10.5 + 1e-12);
+            prev = x;
+        }
+        assert_eq!(catmull_rom(&p0, &p1, &p2, &p3, &alpha, &0.0), p1);
+        assert_eq!(catmull_rom(&p0, &p1, &p2, &p3, &alpha, &1.0), p2);
+    }
+}
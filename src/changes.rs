@@ -0,0 +1,94 @@
+//! Skipping samples that did not change
+
+use animate::Animate;
+
+/// Remembers the last value and filters out repeats.
+///
+/// This is used to skip work downstream, such as uploading uniforms,
+/// while a track holds a value or after an animation has finished.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeFilter<V> {
+    last: Option<V>,
+}
+
+impl<V> Default for ChangeFilter<V> {
+    fn default() -> ChangeFilter<V> {
+        ChangeFilter { last: None }
+    }
+}
+
+impl<V> ChangeFilter<V>
+    where V: PartialEq + Clone
+{
+    /// Creates a filter that has seen no values.
+    pub fn new() -> ChangeFilter<V> {
+        ChangeFilter::default()
+    }
+
+    /// Returns `Some(value)` if it differs from the last value passed,
+    /// or `None` if it is identical.
+    pub fn filter(&mut self, value: V) -> Option<V> {
+        if self.last.as_ref() == Some(&value) {
+            None
+        } else {
+            self.last = Some(value.clone());
+            Some(value)
+        }
+    }
+
+    /// Returns the last value passed.
+    pub fn last(&self) -> Option<&V> {
+        self.last.as_ref()
+    }
+
+    /// Forgets the last value, so the next value is reported as changed.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Wraps an animation and reports its value only when it changes.
+#[derive(Debug, Clone)]
+pub struct Changes<A: Animate> {
+    /// The wrapped animation.
+    pub animation: A,
+    filter: ChangeFilter<A::Value>,
+}
+
+impl<A> Changes<A>
+    where A: Animate, A::Value: PartialEq + Clone
+{
+    /// Creates a new change detector.
+    /// The first update always reports a value.
+    pub fn new(animation: A) -> Changes<A> {
+        Changes {
+            animation,
+            filter: ChangeFilter::new(),
+        }
+    }
+
+    /// Advances the animation by `dt`,
+    /// returning the new value if it differs from the last one reported.
+    pub fn update(&mut self, dt: A::Time) -> Option<A::Value> {
+        self.animation.update(dt);
+        self.filter.filter(self.animation.value())
+    }
+
+    /// Returns the last value reported.
+    pub fn last(&self) -> Option<&A::Value> {
+        self.filter.last()
+    }
+}
+
+#[test]
+fn changes_after_finish() {
+    use tween::Tween;
+
+    let mut changes = Changes::new(Tween::new(0.0f32, 1.0, 1.0));
+    assert_eq!(changes.update(0.0), Some(0.0));
+    assert_eq!(changes.update(0.0), None);
+    assert_eq!(changes.update(0.5), Some(0.5));
+    assert_eq!(changes.update(1.0), Some(1.0));
+    assert_eq!(changes.update(1.0), None);
+    assert_eq!(changes.last(), Some(&1.0));
+}
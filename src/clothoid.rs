@@ -0,0 +1,304 @@
+//! Euler spirals, whose curvature changes linearly with arc length
+//!
+//! A clothoid turns gradually from one curvature to another, so a vehicle
+//! following it turns its steering wheel at a constant rate.
+//! This makes it the usual transition between straights and bends of roads and tracks,
+//! which Bézier curves can only approximate.
+//! The points are given by the Fresnel integrals, which have no closed form
+//! and are computed with a power series or a continued fraction.
+
+use core::cmp::Ordering;
+
+use arc_length::{gauss_legendre, ParametricCurve};
+use float::Float;
+
+/// A clothoid segment, parameterized by arc length.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct Clothoid<S> {
+    /// The start point.
+    pub start: [S; 2],
+    /// The direction at the start, in radians.
+    pub heading: S,
+    /// The curvature at the start, positive when turning towards increasing angles.
+    pub curvature: S,
+    /// The change of the curvature per unit of length.
+    pub sharpness: S,
+    /// The length.
+    pub length: S,
+}
+
+impl<S: Float> Clothoid<S> {
+    /// Creates a clothoid from its start point, direction, curvature,
+    /// change of curvature and length.
+    pub fn new(start: [S; 2], heading: S, curvature: S, sharpness: S, length: S) -> Clothoid<S> {
+        Clothoid { start, heading, curvature, sharpness, length }
+    }
+
+    /// Creates the clothoid of `length` that turns from `start_curvature`
+    /// to `end_curvature`, such as from a straight into a bend.
+    pub fn from_curvatures(start: [S; 2], heading: S, start_curvature: S, end_curvature: S,
+                           length: S) -> Clothoid<S> {
+        let sharpness = if length == S::zero() { S::zero() } else { (end_curvature - start_curvature) / length };
+        Clothoid::new(start, heading, start_curvature, sharpness, length)
+    }
+
+    /// Returns the curvature at distance `s` along the curve.
+    pub fn curvature_at(&self, s: S) -> S {
+        self.curvature + self.sharpness * s
+    }
+
+    /// Returns the direction at distance `s` along the curve, in radians.
+    pub fn heading_at(&self, s: S) -> S {
+        self.heading + (self.curvature + self.sharpness * s * S::from_f64(0.5)) * s
+    }
+
+    /// Returns the point at distance `s` along the curve.
+    ///
+    /// The Fresnel integrals are shifted to the point where the curvature is zero.
+    /// When that point is so far away that its angle loses precision,
+    /// which includes circular arcs and straight lines,
+    /// the direction is integrated with Gauss-Legendre quadrature instead.
+    pub fn point_at_length(&self, s: S) -> [S; 2] {
+        let (zero, half) = (S::zero(), S::from_f64(0.5));
+        let [x0, y0] = self.start;
+        let (k, c) = (self.curvature, self.sharpness);
+        let abs_c = if c < zero { -c } else { c };
+        let shift = k * k * half / abs_c;
+        let (dx, dy) = if shift.partial_cmp(&max_fresnel_shift()) == Some(Ordering::Less) {
+            // With `v = a (u + k / c)`, the direction is `phi + sign(c) pi v^2 / 2`.
+            let a = (abs_c / S::pi()).sqrt();
+            let phi = self.heading - k * k * half / c;
+            let (c0, s0) = fresnel(a * k / c);
+            let (c1, s1) = fresnel(a * (s + k / c));
+            let (dc, ds) = ((c1 - c0) / a, (s1 - s0) / a);
+            let ds = if c < zero { -ds } else { ds };
+            (phi.cos() * dc - phi.sin() * ds, phi.sin() * dc + phi.cos() * ds)
+        } else {
+            self.integrate(s)
+        };
+        [x0 + dx, y0 + dy]
+    }
+
+    /// Integrates the direction from the start to distance `s`,
+    /// in parts that each turn by at most an eighth of a turn.
+    fn integrate(&self, s: S) -> (S, S) {
+        let abs = |x: S| if x < S::zero() { -x } else { x };
+        let turn = abs(self.curvature * s) + abs(self.sharpness * s * s * S::from_f64(0.5));
+        let parts = (turn / (S::pi() * S::from_f64(0.25))).to_f64();
+        let parts = if parts < 1.0 { 1 } else if parts < MAX_PARTS as f64 { parts as u32 + 1 } else { MAX_PARTS };
+        let step = s / S::from_f64(f64::from(parts));
+        (0..parts).fold((S::zero(), S::zero()), |(x, y), i| {
+            let (a, b) = (step * S::from_f64(f64::from(i)), step * S::from_f64(f64::from(i + 1)));
+            (x + gauss_legendre(&|u| self.heading_at(u).cos(), a, b),
+             y + gauss_legendre(&|u| self.heading_at(u).sin(), a, b))
+        })
+    }
+
+    /// Returns the point at `t`, from the start at 0 to the end at 1.
+    pub fn sample(&self, t: &S) -> [S; 2] {
+        self.point_at_length(*t * self.length)
+    }
+
+    /// Returns the end point.
+    pub fn end(&self) -> [S; 2] {
+        self.point_at_length(self.length)
+    }
+
+    /// Returns the direction at the end, in radians.
+    pub fn end_heading(&self) -> S {
+        self.heading_at(self.length)
+    }
+
+    /// Returns the curvature at the end.
+    pub fn end_curvature(&self) -> S {
+        self.curvature_at(self.length)
+    }
+}
+
+impl<S: Float> ParametricCurve for Clothoid<S> {
+    type Point = [S; 2];
+    type Scalar = S;
+
+    #[inline(always)]
+    fn point_at(&self, t: S) -> [S; 2] {
+        self.sample(&t)
+    }
+
+    #[inline(always)]
+    fn speed_at(&self, _t: S) -> S {
+        if self.length < S::zero() { -self.length } else { self.length }
+    }
+}
+
+/// Returns the largest angle of the point of zero curvature, relative to the start,
+/// for which the Fresnel integrals are used.
+///
+/// The error grows with the angle times the precision of `S`, so this keeps about
+/// half of its digits: the limit is about `1e6` for `f64`, but only `45` for `f32`.
+fn max_fresnel_shift<S: Float>() -> S {
+    S::one() / (S::from_f64(64.0) * S::epsilon().sqrt())
+}
+
+/// The maximum number of parts when integrating the direction.
+const MAX_PARTS: u32 = 4096;
+
+/// The largest argument for which the Fresnel integrals are summed as a power series.
+const FRESNEL_SERIES_MAX: f64 = 1.5;
+
+/// The maximum number of terms of the series or the continued fraction.
+const FRESNEL_TERMS: u32 = 100;
+
+/// Returns the Fresnel integrals `C(x)` and `S(x)`,
+/// the integrals of `cos(pi t^2 / 2)` and `sin(pi t^2 / 2)` from 0 to `x`.
+///
+/// Small arguments use the power series, and large ones the continued fraction
+/// of the complementary error function, as in Numerical Recipes.
+fn fresnel<S: Float>(x: S) -> (S, S) {
+    let (zero, one) = (S::zero(), S::one());
+    let epsilon = S::from_f64(1e-16);
+    let half_pi = S::pi() * S::from_f64(0.5);
+    let ax = if x < zero { -x } else { x };
+    let (c, s) = if ax.partial_cmp(&S::from_f64(FRESNEL_SERIES_MAX)) != Some(Ordering::Greater) {
+        // The terms of the series alternate between the two integrals.
+        let fact = half_pi * ax * ax;
+        let (mut sum_c, mut sum_s) = (ax, zero);
+        let (mut term, mut sign, mut n) = (ax, one, S::from_f64(3.0));
+        for k in 1..FRESNEL_TERMS {
+            term = term * fact / S::from_f64(f64::from(k));
+            let value = sign * term / n;
+            if k % 2 == 1 {
+                sum_s = sum_s + value;
+                sign = -sign;
+            } else {
+                sum_c = sum_c + value;
+            }
+            let sum = if k % 2 == 1 { sum_s } else { sum_c };
+            if term < (if sum < zero { -sum } else { sum }) * epsilon {
+                break;
+            }
+            n = n + S::from_f64(2.0);
+        }
+        (sum_c, sum_s)
+    } else {
+        // Lentz's method for the continued fraction, in complex numbers.
+        let pix2 = S::pi() * ax * ax;
+        let tiny = S::from_f64(1e-30);
+        let mut b = (one, -pix2);
+        let mut cc = (one / tiny, zero);
+        let mut d = inverse(b);
+        let mut h = d;
+        let mut n = -one;
+        for _ in 1..FRESNEL_TERMS {
+            n = n + S::from_f64(2.0);
+            let a = -n * (n + one);
+            b = (b.0 + S::from_f64(4.0), b.1);
+            d = inverse((a * d.0 + b.0, a * d.1 + b.1));
+            let c_inv = inverse(cc);
+            cc = (b.0 + a * c_inv.0, b.1 + a * c_inv.1);
+            let del = multiply(cc, d);
+            h = multiply(h, del);
+            let (re, im) = (del.0 - one, del.1);
+            if (if re < zero { -re } else { re }) + (if im < zero { -im } else { im }) < epsilon {
+                break;
+            }
+        }
+        h = multiply(h, (ax, -ax));
+        let half = S::from_f64(0.5);
+        let e = multiply(((half * pix2).cos(), (half * pix2).sin()), h);
+        let (re, im) = (one - e.0, -e.1);
+        (half * (re - im), half * (re + im))
+    };
+    if x < zero { (-c, -s) } else { (c, s) }
+}
+
+/// Multiplies two complex numbers.
+fn multiply<S: Float>((a, b): (S, S), (c, d): (S, S)) -> (S, S) {
+    (a * c - b * d, a * d + b * c)
+}
+
+/// Returns the inverse of a complex number.
+fn inverse<S: Float>((a, b): (S, S)) -> (S, S) {
+    let norm = a * a + b * b;
+    (a / norm, -b / norm)
+}
+
+#[cfg(test)]
+fn simpson(clothoid: &Clothoid<f64>, s: f64) -> [f64; 2] {
+    let n = 20000;
+    let h = s / n as f64;
+    let (mut x, mut y) = (0.0, 0.0);
+    for i in 0..=n {
+        let w = if i == 0 || i == n { 1.0 } else if i % 2 == 1 { 4.0 } else { 2.0 };
+        let angle = clothoid.heading_at(i as f64 * h);
+        x += w * angle.cos();
+        y += w * angle.sin();
+    }
+    [clothoid.start[0] + x * h / 3.0, clothoid.start[1] + y * h / 3.0]
+}
+
+#[test]
+fn clothoid_fresnel() {
+    let cases: [(f64, f64, f64); 5] = [(0.5, 0.4923442258714464, 0.064732432859961),
+                 (1.0, 0.7798934003768228, 0.4382591473903548),
+                 (1.5, 0.4452611760398215, 0.697504960082093),
+                 (2.0, 0.4882534060753408, 0.3434156783636982),
+                 (5.0, 0.5636311887040122, 0.4991913819171169)];
+    for &(x, c, s) in &cases {
+        let (fc, fs) = fresnel(x);
+        assert!((fc - c).abs() < 1e-12 && (fs - s).abs() < 1e-12);
+        assert_eq!(fresnel(-x), (-fc, -fs));
+    }
+    assert_eq!(fresnel(0.0f64), (0.0, 0.0));
+    let (c, s) = fresnel(1e4f64);
+    assert!((c - 0.5).abs() < 1e-4 && (s - 0.5).abs() < 1e-4);
+}
+
+#[test]
+fn clothoid_points() {
+    let curves = [Clothoid::new([0.0f64, 0.0], 0.0, 0.0, 1.0, 3.0),
+                  Clothoid::new([1.0, 2.0], 1.0, 0.5, -0.3, 5.0),
+                  Clothoid::new([0.0, 0.0], 0.0, 2.0, 0.1, 4.0),
+                  Clothoid::new([0.0, 0.0], 0.5, 1.0, 1e-9, 6.0),
+                  Clothoid::new([0.0, 0.0], 0.5, 1e-3, 0.0, 6.0)];
+    for clothoid in &curves {
+        for i in 0..=8 {
+            let s = clothoid.length * i as f64 / 8.0;
+            let (p, q) = (clothoid.point_at_length(s), simpson(clothoid, s));
+            assert!((p[0] - q[0]).abs() < 1e-9 && (p[1] - q[1]).abs() < 1e-9);
+        }
+    }
+    // The direction of the curve follows the heading.
+    let clothoid = curves[1];
+    let (p, q) = (clothoid.point_at_length(2.0 - 1e-6), clothoid.point_at_length(2.0 + 1e-6));
+    let angle = (q[1] - p[1]).atan2(q[0] - p[0]);
+    assert!((angle - clothoid.heading_at(2.0)).abs() < 1e-6);
+}
+
+#[test]
+fn clothoid_arcs_and_lines() {
+    let line = Clothoid::new([1.0f64, 1.0], 0.0, 0.0, 0.0, 2.0);
+    assert_eq!(line.end(), [3.0, 1.0]);
+    // A circle of radius 2 around `[0, 2]`.
+    let circle = Clothoid::new([0.0f64, 0.0], 0.0, 0.5, 0.0, 2.0);
+    for i in 0..=8 {
+        let p = circle.sample(&(i as f64 / 8.0));
+        assert!((p[0].hypot(p[1] - 2.0) - 2.0).abs() < 1e-12);
+    }
+    // A transition from a straight into a bend.
+    let transition = Clothoid::from_curvatures([0.0f64, 0.0], 0.0, 0.0, 0.5, 4.0);
+    assert_eq!((transition.curvature_at(0.0), transition.end_curvature()), (0.0, 0.5));
+    assert_eq!(transition.end_heading(), 1.0);
+    assert_eq!(Clothoid::from_curvatures([0.0f64, 0.0], 0.0, 0.0, 0.5, 0.0).end(), [0.0, 0.0]);
+    let prepared = ::PreparedCurve::new(transition);
+    assert!((prepared.length() - 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn clothoid_f32() {
+    // The point of zero curvature is 500 radians away, too far for the Fresnel integrals in `f32`.
+    let [x, y] = Clothoid::new([0.0f32, 0.0], 0.3, 1.0, 1e-3, 6.0).end();
+    let [u, v] = Clothoid::new([0.0f64, 0.0], 0.3, 1.0, 1e-3, 6.0).end();
+    assert!((f64::from(x) - u).abs() < 1e-6 && (f64::from(y) - v).abs() < 1e-6);
+    assert!(max_fresnel_shift::<f32>() < 1e2 && max_fresnel_shift::<f64>() > 1e6);
+}
@@ -0,0 +1,342 @@
+//! Shader source generation, so curves evaluate the same on the GPU
+//!
+//! Each generator returns the source of a function taking a progress `t`
+//! and returning the eased value, ready to paste into a GLSL or WGSL shader.
+
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bake::EaseTable;
+use bezier_ease::CubicBezierEase;
+use ease::{EaseFunction, Easing, StepPosition};
+
+/// The language of generated shader source.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderLanguage {
+    /// OpenGL Shading Language.
+    Glsl,
+    /// WebGPU Shading Language.
+    Wgsl,
+}
+
+impl ShaderLanguage {
+    fn signature(self, name: &str, arg: &str) -> String {
+        match self {
+            ShaderLanguage::Glsl => format!("float {}(float {})", name, arg),
+            ShaderLanguage::Wgsl => format!("fn {}({}: f32) -> f32", name, arg),
+        }
+    }
+
+    fn declare(self) -> &'static str {
+        match self {
+            ShaderLanguage::Glsl => "float",
+            ShaderLanguage::Wgsl => "let",
+        }
+    }
+
+    fn declare_var(self) -> &'static str {
+        match self {
+            ShaderLanguage::Glsl => "float",
+            ShaderLanguage::Wgsl => "var",
+        }
+    }
+
+    fn repeat(self, count: u32) -> String {
+        match self {
+            ShaderLanguage::Glsl => format!("for (int i = 0; i < {}; i++)", count),
+            ShaderLanguage::Wgsl => format!("for (var i = 0; i < {}; i++)", count),
+        }
+    }
+}
+
+const PI: &str = "3.141592653589793";
+const PI_2: &str = "6.283185307179586";
+
+/// Returns the body of a bounce out function of `p`, returning early from each branch,
+/// with the same segments and coefficients as `Ease::bounce_out`.
+fn bounce_out() -> String {
+    use ease::bounce::*;
+
+    format!("if (p < {:?}) {{ return {:?} * p * p; }}\n\
+             if (p < {:?}) {{ return {:?} * (p - {:?}) * (p - {:?}) + {:?}; }}\n\
+             if (p < {:?}) {{ return {:?} * (p - {:?}) * (p - {:?}) + {:?}; }}\n\
+             return ({:?} * (1.0 - p) - {:?}) * (1.0 - p) + 1.0;",
+            T1, A1, T2, A2, H2, H2, M2, T3, A3, H3, H3, M3, A4, B4)
+}
+
+/// Returns the statements computing an ease function of the clamped progress `p`.
+///
+/// `$let` stands for a declaration and `$bounce` for the bounce out helper.
+fn ease_body(f: EaseFunction) -> String {
+    use ease::EaseFunction::*;
+
+    let body = match f {
+        QuadraticIn => "return p * p;",
+        QuadraticOut => "return -(p * (p - 2.0));",
+        QuadraticInOut => "if (p < 0.5) { return 2.0 * p * p; }
+return (-2.0 * p * p) + (4.0 * p) - 1.0;",
+
+        CubicIn => "return p * p * p;",
+        CubicOut => "$let f = p - 1.0;
+return f * f * f + 1.0;",
+        CubicInOut => "if (p < 0.5) { return 4.0 * p * p * p; }
+$let f = (2.0 * p) - 2.0;
+return 0.5 * f * f * f + 1.0;",
+
+        QuarticIn => "return p * p * p * p;",
+        QuarticOut => "$let f = p - 1.0;
+return f * f * f * (1.0 - p) + 1.0;",
+        QuarticInOut => "if (p < 0.5) { return 8.0 * p * p * p * p; }
+$let f = p - 1.0;
+return -8.0 * f * f * f * f + 1.0;",
+
+        QuinticIn => "return p * p * p * p * p;",
+        QuinticOut => "$let f = p - 1.0;
+return f * f * f * f * f + 1.0;",
+        QuinticInOut => "if (p < 0.5) { return 16.0 * p * p * p * p * p; }
+$let f = (2.0 * p) - 2.0;
+return 0.5 * f * f * f * f * f + 1.0;",
+
+        SineIn => "return sin((p - 1.0) * $PI_2) + 1.0;",
+        SineOut => "return sin(p * $PI_2);",
+        SineInOut => "return 0.5 * (1.0 - cos(p * $PI));",
+
+        CircularIn => "return 1.0 - sqrt(1.0 - (p * p));",
+        CircularOut => "return sqrt((2.0 - p) * p);",
+        CircularInOut => "if (p < 0.5) { return 0.5 * (1.0 - sqrt(1.0 - 4.0 * (p * p))); }
+return 0.5 * (sqrt(-((2.0 * p) - 3.0) * ((2.0 * p) - 1.0)) + 1.0);",
+
+        ExponentialIn => "if (p <= 0.0) { return 0.0; }
+return pow(2.0, 10.0 * (p - 1.0));",
+        ExponentialOut => "if (p >= 1.0) { return 1.0; }
+return 1.0 - pow(2.0, -10.0 * p);",
+        ExponentialInOut => "if (p <= 0.0) { return 0.0; }
+if (p >= 1.0) { return 1.0; }
+if (p < 0.5) { return 0.5 * pow(2.0, (20.0 * p) - 10.0); }
+return -0.5 * pow(2.0, (-20.0 * p) + 10.0) + 1.0;",
+
+        ElasticIn => "return sin(13.0 * $PI_2 * p) * pow(2.0, 10.0 * (p - 1.0));",
+        ElasticOut => "return sin(-13.0 * $PI_2 * (p + 1.0)) * pow(2.0, -10.0 * p) + 1.0;",
+        ElasticInOut => "if (p < 0.5) { return 0.5 * sin(13.0 * $PI_2 * (2.0 * p)) * pow(2.0, 10.0 * ((2.0 * p) - 1.0)); }
+return 0.5 * (sin(-13.0 * $PI_2 * ((2.0 * p - 1.0) + 1.0)) * pow(2.0, -10.0 * (2.0 * p - 1.0)) + 2.0);",
+
+        BackIn => "return p * p * p - p * sin(p * $PI);",
+        BackOut => "$let f = 1.0 - p;
+return 1.0 - (f * f * f - f * sin(f * $PI));",
+        BackInOut => "if (p < 0.5) {
+    $let f = 2.0 * p;
+    return 0.5 * (f * f * f - f * sin(f * $PI));
+}
+$let f = 1.0 - (2.0 * p - 1.0);
+return 0.5 * (1.0 - (f * f * f - f * sin(f * $PI))) + 0.5;",
+
+        BounceIn => "return 1.0 - $bounce(1.0 - p);",
+        BounceOut => "return $bounce(p);",
+        BounceInOut => "if (p < 0.5) { return 0.5 * (1.0 - $bounce(1.0 - p * 2.0)); }
+return 0.5 * $bounce(p * 2.0 - 1.0) + 0.5;",
+    };
+    body.replace("$PI_2", PI_2).replace("$PI", PI)
+}
+
+fn function(lang: ShaderLanguage, name: &str, arg: &str, body: &str) -> String {
+    let mut s = format!("{} {{\n", lang.signature(name, arg));
+    for line in body.lines() {
+        s.push_str("    ");
+        s.push_str(line);
+        s.push('\n');
+    }
+    s.push_str("}\n");
+    s
+}
+
+/// Generates a function named `name` evaluating an ease function.
+///
+/// The bounce functions also get a helper named `name` followed by `_bounce_out`.
+pub fn ease_function(lang: ShaderLanguage, f: EaseFunction, name: &str) -> String {
+    let decl = lang.declare();
+    let clamp = format!("{} p = clamp(t, 0.0, 1.0);\n", decl);
+    let bounce = format!("{}_bounce_out", name);
+    let body = ease_body(f).replace("$let", decl).replace("$bounce", &bounce);
+
+    let mut s = String::new();
+    if body.contains(&bounce) {
+        s = function(lang, &bounce, "p", &bounce_out()) + "\n";
+    }
+    s + &function(lang, name, "t", &(clamp + &body))
+}
+
+/// Generates a function named `name` evaluating an easing like `Easing::calc`.
+///
+/// A cubic Bézier easing solves for the curve parameter with a fixed number of steps,
+/// 20 of bisection and 2 of Newton's method, and gets helpers named `name`
+/// followed by `_bezier` and `_bezier_derivative`.
+pub fn easing(lang: ShaderLanguage, easing: Easing, name: &str) -> String {
+    match easing {
+        Easing::Linear => function(lang, name, "t", "return t;"),
+        Easing::Function(f) => ease_function(lang, f, name),
+        Easing::CubicBezier(c) => cubic_bezier_ease(lang, c, name),
+        Easing::Steps(count, position) => steps(lang, count, position, name),
+    }
+}
+
+fn cubic_bezier_ease(lang: ShaderLanguage, c: CubicBezierEase, name: &str) -> String {
+    let (decl, var) = (lang.declare(), lang.declare_var());
+    let (bezier, derivative) = (format!("{}_bezier", name), format!("{}_bezier_derivative", name));
+    let signature = |name: &str| match lang {
+        ShaderLanguage::Glsl => format!("float {}(float a, float b, float t)", name),
+        ShaderLanguage::Wgsl => format!("fn {}(a: f32, b: f32, t: f32) -> f32", name),
+    };
+    let helper = |name: &str, body: &str| format!("{} {{\n    {} u = 1.0 - t;\n    {}\n}}\n\n",
+                                                  signature(name), decl, body);
+    let x = |t: &str| format!("{}({:?}, {:?}, {})", bezier, c.x1, c.x2, t);
+    let body = format!(
+        "if (p <= 0.0) {{ return 0.0; }}\n\
+         if (p >= 1.0) {{ return 1.0; }}\n\
+         {var} lo = 0.0;\n\
+         {var} hi = 1.0;\n\
+         {bisect} {{\n    {decl} s = 0.5 * (lo + hi);\n    if ({x_s} < p) {{ lo = s; }} else {{ hi = s; }}\n}}\n\
+         {var} s = 0.5 * (lo + hi);\n\
+         {newton} {{\n    {decl} d = {derivative}({x1:?}, {x2:?}, s);\n    \
+         if (abs(d) > 1e-6) {{ s = clamp(s - ({x_s} - p) / d, lo, hi); }}\n}}\n\
+         return {bezier}({y1:?}, {y2:?}, s);",
+        var = var, decl = decl, bisect = lang.repeat(20), newton = lang.repeat(2), x_s = x("s"),
+        derivative = derivative, bezier = bezier, x1 = c.x1, x2 = c.x2, y1 = c.y1, y2 = c.y2,
+    );
+    helper(&bezier, "return 3.0 * u * u * t * a + 3.0 * u * t * t * b + t * t * t;")
+        + &helper(&derivative, "return 3.0 * u * u * a + 6.0 * u * t * (b - a) + 3.0 * t * t * (1.0 - b);")
+        + &function(lang, name, "t", &format!("{} p = clamp(t, 0.0, 1.0);\n{}", decl, body))
+}
+
+fn steps(lang: ShaderLanguage, count: u32, position: StepPosition, name: &str) -> String {
+    let count = f64::from(count.max(1));
+    let jumps = match position {
+        StepPosition::Start | StepPosition::End => count,
+        StepPosition::None => (count - 1.0).max(1.0),
+        StepPosition::Both => count + 1.0,
+    };
+    let mut body = format!("{} step = floor(t * {:?});\n", lang.declare_var(), count);
+    if let StepPosition::Start | StepPosition::Both = position {
+        body.push_str("step = step + 1.0;\n");
+    }
+    body.push_str(&format!("if (t >= 0.0 && step < 0.0) {{ step = 0.0; }}\n\
+                            if (t <= 1.0 && step > {:?}) {{ step = {:?}; }}\n\
+                            return step / {:?};", jumps, jumps, jumps));
+    function(lang, name, "t", &body)
+}
+
+/// Generates a function named `name` evaluating a cubic Bézier curve
+/// through `x0` and `x3`, with control points `x1` and `x2`.
+///
+/// The curve is evaluated at `t` directly. For a CSS-style easing
+/// of progress, see `easing` with `Easing::CubicBezier`.
+pub fn cubic_bezier(lang: ShaderLanguage, [x0, x1, x2, x3]: [f32; 4], name: &str) -> String {
+    let body = format!(
+        "{} u = 1.0 - t;\nreturn u * u * u * {:?} + 3.0 * u * u * t * {:?} + 3.0 * u * t * t * {:?} + t * t * t * {:?};",
+        lang.declare(), x0, x1, x2, x3
+    );
+    function(lang, name, "t", &body)
+}
+
+/// Generates a lookup table named `name` followed by `_lut`,
+/// and a function named `name` sampling it like `EaseTable::sample`.
+pub fn ease_table(lang: ShaderLanguage, table: &EaseTable, name: &str) -> String {
+    let samples = table.samples();
+    let n = samples.len();
+    match *samples {
+        [] => return function(lang, name, "t", "return t;"),
+        [v] => return function(lang, name, "t", &format!("return {:?};", v)),
+        _ => {}
+    }
+
+    let lut = format!("{}_lut", name);
+    let values = samples.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>().join(", ");
+    let (decl, body) = match lang {
+        ShaderLanguage::Glsl => (
+            format!("const float {}[{}] = float[{}]({});\n\n", lut, n, n, values),
+            format!("float x = clamp(t, 0.0, 1.0) * {:?};\nint i = min(int(x), {});\n\
+                     return mix({}[i], {}[i + 1], x - float(i));",
+                    (n - 1) as f32, n - 2, lut, lut),
+        ),
+        ShaderLanguage::Wgsl => (
+            format!("var<private> {}: array<f32, {}> = array<f32, {}>({});\n\n", lut, n, n, values),
+            format!("let x = clamp(t, 0.0, 1.0) * {:?};\nlet i = min(u32(x), {}u);\n\
+                     return mix({}[i], {}[i + 1u], x - f32(i));",
+                    (n - 1) as f32, n - 2, lut, lut),
+        ),
+    };
+    decl + &function(lang, name, "t", &body)
+}
+
+#[test]
+fn codegen_glsl() {
+    let src = ease_function(ShaderLanguage::Glsl, EaseFunction::CubicOut, "cubic_out");
+    assert_eq!(src, "float cubic_out(float t) {
+    float p = clamp(t, 0.0, 1.0);
+    float f = p - 1.0;
+    return f * f * f + 1.0;
+}
+");
+
+    let src = ease_function(ShaderLanguage::Glsl, EaseFunction::BounceIn, "bounce");
+    assert!(src.starts_with("float bounce_bounce_out(float p) {\n"));
+    assert!(src.contains("return 1.0 - bounce_bounce_out(1.0 - p);"));
+}
+
+#[test]
+fn codegen_wgsl() {
+    let src = ease_function(ShaderLanguage::Wgsl, EaseFunction::BackInOut, "back");
+    assert!(src.starts_with("fn back(t: f32) -> f32 {\n    let p = clamp(t, 0.0, 1.0);\n"));
+    assert!(!src.contains("float"));
+
+    let table = EaseTable::new(EaseFunction::QuadraticIn, 3);
+    let src = ease_table(ShaderLanguage::Wgsl, &table, "quad");
+    assert!(src.starts_with("var<private> quad_lut: array<f32, 3> = array<f32, 3>(0.0, 0.25, 1.0);\n"));
+    assert!(src.contains("let i = min(u32(x), 1u);"));
+
+    let src = cubic_bezier(ShaderLanguage::Wgsl, [0.0, 0.25, 0.75, 1.0], "curve");
+    assert!(src.contains("3.0 * u * t * t * 0.75"));
+}
+
+
+#[test]
+fn codegen_easing() {
+    let ease = Easing::CubicBezier(CubicBezierEase::new(0.42, 0.0, 0.58, 1.0));
+    let src = easing(ShaderLanguage::Glsl, ease, "ease");
+    assert!(src.starts_with("float ease_bezier(float a, float b, float t) {\n"));
+    assert!(src.contains("float ease_bezier_derivative(float a, float b, float t) {\n"));
+    assert!(src.contains("for (int i = 0; i < 20; i++) {\n        float s = 0.5 * (lo + hi);\n"));
+    assert!(src.contains("if (ease_bezier(0.42, 0.58, s) < p) { lo = s; } else { hi = s; }"));
+    assert!(src.contains("return ease_bezier(0.0, 1.0, s);\n}\n"));
+    let src = easing(ShaderLanguage::Wgsl, ease, "ease");
+    assert!(src.contains("    var lo = 0.0;\n") && src.contains("for (var i = 0; i < 2; i++) {"));
+    assert!(!src.contains("float"));
+
+    assert_eq!(easing(ShaderLanguage::Wgsl, Easing::Steps(4, StepPosition::Both), "steps"), "fn steps(t: f32) -> f32 {
+    var step = floor(t * 4.0);
+    step = step + 1.0;
+    if (t >= 0.0 && step < 0.0) { step = 0.0; }
+    if (t <= 1.0 && step > 5.0) { step = 5.0; }
+    return step / 5.0;
+}
+");
+    let src = easing(ShaderLanguage::Glsl, Easing::Steps(0, StepPosition::End), "steps");
+    assert!(src.contains("float step = floor(t * 1.0);\n    if") && src.contains("return step / 1.0;"));
+    assert_eq!(easing(ShaderLanguage::Glsl, Easing::Linear, "linear"), "float linear(float t) {\n    return t;\n}\n");
+    assert_eq!(easing(ShaderLanguage::Glsl, Easing::Function(EaseFunction::CubicOut), "cubic_out"),
+               ease_function(ShaderLanguage::Glsl, EaseFunction::CubicOut, "cubic_out"));
+}
+
+#[test]
+fn codegen_bounce_constants() {
+    // The shader segments are formatted from the same constants as `Ease::bounce_out`.
+    use ease::bounce::*;
+
+    let src = ease_function(ShaderLanguage::Glsl, EaseFunction::BounceOut, "b");
+    for &c in &[T1, T2, T3, A1, A2, H2, M2, A3, H3, M3, A4, B4] {
+        assert!(src.contains(&format!("{:?}", c)), "{}", c);
+    }
+    assert!(src.contains("if (p < 0.36363636363636365) { return 7.5625 * p * p; }"));
+    assert!(src.contains("return (10.8 * (1.0 - p) - 1.08) * (1.0 - p) + 1.0;"));
+}
@@ -0,0 +1,223 @@
+//! Conic sections as rational quadratic Bézier curves
+//!
+//! A quadratic Bézier curve can only trace a parabola.
+//! Giving the middle control point a weight makes it trace any conic section exactly:
+//! an ellipse for weights below one, a parabola at one and a hyperbola above one.
+//! The curve is evaluated in homogeneous coordinates, where the middle point
+//! is multiplied by its weight, and divided by the interpolated weight at the end.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use float::Float;
+use spatial::Spatial;
+
+/// A rational quadratic Bézier curve, with a weight for the middle control point.
+///
+/// The weights of the ends are one, which every rational quadratic curve can be scaled to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RationalQuadraticBezier<T: Spatial> {
+    /// The control points, from the start to the end.
+    pub points: [T; 3],
+    /// The weight of the middle control point, which should be positive.
+    pub weight: T::Scalar,
+}
+
+impl<T: Spatial> RationalQuadraticBezier<T> {
+    /// Creates a curve from `x0` to `x2`, pulled towards `x1` by `weight`.
+    pub fn new(x0: T, x1: T, x2: T, weight: T::Scalar) -> RationalQuadraticBezier<T> {
+        RationalQuadraticBezier { points: [x0, x1, x2], weight }
+    }
+
+    /// Returns the start point.
+    pub fn start(&self) -> &T {
+        let [ref x0, _, _] = self.points;
+        x0
+    }
+
+    /// Returns the end point.
+    pub fn end(&self) -> &T {
+        let [_, _, ref x2] = self.points;
+        x2
+    }
+}
+
+impl<T> RationalQuadraticBezier<T>
+    where T: Spatial + Clone, T::Scalar: Float
+{
+    /// Returns the point at `t`.
+    pub fn sample(&self, t: &T::Scalar) -> T {
+        let [ref x0, ref x1, ref x2] = self.points;
+        let one = T::Scalar::one();
+        let (a, b, c) = ((one - *t) * (one - *t), (one - *t) * *t * T::Scalar::from_f64(2.0) * self.weight, *t * *t);
+        x0.scale(&a).add(&x1.scale(&b)).add(&x2.scale(&c)).scale(&(one / (a + b + c)))
+    }
+
+    /// Splits the curve at `t` with de Casteljau's algorithm in homogeneous coordinates,
+    /// into the curves before and after `t`.
+    pub fn split(&self, t: &T::Scalar) -> (RationalQuadraticBezier<T>, RationalQuadraticBezier<T>) {
+        let [ref x0, ref x1, ref x2] = self.points;
+        let one = T::Scalar::one();
+        let w = self.weight;
+        // The weights of the new middle points, and of the point at `t`.
+        let (w01, w12) = (one + (w - one) * *t, w + (one - w) * *t);
+        let wt = w01 + (w12 - w01) * *t;
+        let x01 = x0.scale(&(one - *t)).add(&x1.scale(&(w * *t))).scale(&(one / w01));
+        let x12 = x1.scale(&(w * (one - *t))).add(&x2.scale(t)).scale(&(one / w12));
+        let x = self.sample(t);
+        // Scaling the homogeneous weights to one at the ends divides the middle one by `sqrt(wt)`.
+        let root = wt.sqrt();
+        (RationalQuadraticBezier::new(x0.clone(), x01, x.clone(), w01 / root),
+         RationalQuadraticBezier::new(x, x12, x2.clone(), w12 / root))
+    }
+}
+
+impl<S> RationalQuadraticBezier<[S; 2]>
+    where S: Float + Spatial<Scalar = S>
+{
+    /// Returns the arc of the ellipse around `center` with `radii` along the axes,
+    /// from `start_angle` to `end_angle` in radians.
+    ///
+    /// Increasing angles run from the first axis towards the second.
+    /// Returns `None` unless the arc is shorter than half a turn,
+    /// which `ellipse_arcs` splits longer arcs into.
+    pub fn ellipse_arc(center: [S; 2], radii: [S; 2], start_angle: S, end_angle: S)
+                       -> Option<RationalQuadraticBezier<[S; 2]>> {
+        let half = (end_angle - start_angle) * S::from_f64(0.5);
+        let quarter = S::pi() * S::from_f64(0.5);
+        if half.partial_cmp(&quarter) != Some(Ordering::Less) || half.partial_cmp(&-quarter) != Some(Ordering::Greater) {
+            return None;
+        }
+        let [cx, cy] = center;
+        let [rx, ry] = radii;
+        let point = |angle: S, scale: S| [cx + rx * angle.cos() * scale, cy + ry * angle.sin() * scale];
+        // The tangents at the ends meet at the middle angle, further out by the weight.
+        let weight = half.cos();
+        Some(RationalQuadraticBezier::new(point(start_angle, S::one()),
+                                          point(start_angle + half, S::one() / weight),
+                                          point(end_angle, S::one()),
+                                          weight))
+    }
+
+    /// Returns the arc of the circle around `center` with `radius`,
+    /// from `start_angle` to `end_angle` in radians,
+    /// or `None` unless the arc is shorter than half a turn.
+    pub fn circle_arc(center: [S; 2], radius: S, start_angle: S, end_angle: S)
+                      -> Option<RationalQuadraticBezier<[S; 2]>> {
+        RationalQuadraticBezier::ellipse_arc(center, [radius, radius], start_angle, end_angle)
+    }
+
+    /// Returns the arc of the ellipse around `center` with `radii` along the axes,
+    /// from `start_angle` to `end_angle` in radians, as curves of at most a quarter turn.
+    ///
+    /// Returns no curves if an angle is not finite.
+    /// Sweeps of more than 1024 turns are reduced by whole turns to less than one turn,
+    /// since the extra turns only go around the ellipse again.
+    #[cfg(feature = "alloc")]
+    pub fn ellipse_arcs(center: [S; 2], radii: [S; 2], start_angle: S, end_angle: S)
+                        -> Vec<RationalQuadraticBezier<[S; 2]>> {
+        let (sweep, n) = match quarter_turns(end_angle - start_angle) {
+            Some(quarters) => quarters,
+            None => return Vec::new(),
+        };
+        let step = sweep / S::from_f64(n as f64);
+        (0..n).filter_map(|i| {
+            let angle = start_angle + step * S::from_f64(i as f64);
+            RationalQuadraticBezier::ellipse_arc(center, radii, angle, angle + step)
+        }).collect()
+    }
+}
+
+/// The most whole turns that an arc is split into curves for,
+/// after which its sweep is reduced by whole turns.
+#[cfg(feature = "alloc")]
+const MAX_ARC_TURNS: u32 = 1024;
+
+/// Returns the sweep, reduced by whole turns beyond `MAX_ARC_TURNS`,
+/// and the number of parts of at most a quarter turn to split it into,
+/// or `None` if the sweep is not finite.
+#[cfg(feature = "alloc")]
+pub(crate) fn quarter_turns<S: Float>(sweep: S) -> Option<(S, u64)> {
+    let (zero, quarter) = (S::zero(), S::pi() * S::from_f64(0.5));
+    let full = quarter * S::from_f64(4.0);
+    let size = if sweep < zero { -sweep } else { sweep };
+    if !size.to_f64().is_finite() {
+        return None;
+    }
+    let size = if size > full * S::from_f64(f64::from(MAX_ARC_TURNS)) {
+        let rest = size - (size / full).floor() * full;
+        // Rounding can leave a whole turn or a little below zero.
+        if rest < zero || rest >= full { zero } else { rest }
+    } else {
+        size
+    };
+    let sweep = if sweep < zero { -size } else { size };
+    let n = -(-(size / quarter)).floor().to_f64();
+    Some((sweep, if n > 1.0 { n as u64 } else { 1 }))
+}
+
+#[test]
+fn conic_circle() {
+    let arc = RationalQuadraticBezier::circle_arc([1.0f64, 2.0], 3.0, 0.0, ::core::f64::consts::FRAC_PI_2).unwrap();
+    assert_eq!(arc.start(), &[4.0, 2.0]);
+    assert!((arc.end()[0] - 1.0).abs() < 1e-12 && (arc.end()[1] - 5.0).abs() < 1e-12);
+    assert!((arc.weight - 0.5f64.sqrt()).abs() < 1e-12);
+    for i in 0..=16 {
+        let p = arc.sample(&(i as f64 / 16.0));
+        assert!(((p[0] - 1.0).hypot(p[1] - 2.0) - 3.0).abs() < 1e-12);
+    }
+    // The middle of the parameter is the middle of the arc.
+    let p = arc.sample(&0.5);
+    assert!((p[0] - 1.0 - 1.5 * 2.0f64.sqrt()).abs() < 1e-12);
+    // Clockwise arcs, and arcs of half a turn or more.
+    let arc = RationalQuadraticBezier::circle_arc([0.0f64, 0.0], 1.0, 0.0, -3.0).unwrap();
+    assert!(arc.sample(&0.5)[1] < 0.0);
+    assert!(RationalQuadraticBezier::circle_arc([0.0f64, 0.0], 1.0, 0.0, ::core::f64::consts::PI).is_none());
+    assert!(RationalQuadraticBezier::circle_arc([0.0f64, 0.0], 1.0, 0.0, f64::NAN).is_none());
+}
+
+#[test]
+fn conic_ellipse_split() {
+    let arc = RationalQuadraticBezier::ellipse_arc([0.0f64, 0.0], [4.0, 1.0], 0.5, 2.5).unwrap();
+    for i in 0..=16 {
+        let p = arc.sample(&(i as f64 / 16.0));
+        assert!(((p[0] / 4.0).powi(2) + p[1].powi(2) - 1.0).abs() < 1e-12);
+    }
+    let (a, b) = arc.split(&0.3);
+    assert_eq!((a.start(), b.end()), (arc.start(), arc.end()));
+    assert_eq!(a.end(), b.start());
+    for i in 0..=8 {
+        let s = i as f64 / 8.0;
+        let (p, q) = (a.sample(&s), b.sample(&s));
+        assert!(((p[0] / 4.0).powi(2) + p[1].powi(2) - 1.0).abs() < 1e-12);
+        assert!(((q[0] / 4.0).powi(2) + q[1].powi(2) - 1.0).abs() < 1e-12);
+    }
+    // A weight of one is a quadratic Bézier curve.
+    let conic = RationalQuadraticBezier::new(0.0f32, 4.0, 0.0, 1.0);
+    assert_eq!(conic.sample(&0.5), ::QuadraticBezier::new(0.0, 4.0, 0.0).sample(&0.5));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn conic_ellipse_arcs() {
+    use core::f64::consts::PI;
+
+    let arcs = RationalQuadraticBezier::ellipse_arcs([0.0f64, 0.0], [2.0, 1.0], 0.0, 2.0 * PI);
+    assert_eq!(arcs.len(), 4);
+    assert!(arcs.windows(2).all(|w| {
+        let (p, q) = (w[0].end(), w[1].start());
+        (p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12
+    }));
+    assert!((arcs[3].end()[0] - 2.0).abs() < 1e-12 && arcs[3].end()[1].abs() < 1e-12);
+    assert_eq!(RationalQuadraticBezier::ellipse_arcs([0.0f64, 0.0], [2.0, 1.0], 0.0, -1.0).len(), 1);
+    assert_eq!(RationalQuadraticBezier::ellipse_arcs([0.0f64, 0.0], [2.0, 1.0], 1.0, 1.0).len(), 1);
+    assert!(RationalQuadraticBezier::ellipse_arcs([0.0f64, 0.0], [2.0, 1.0], 0.0, f64::INFINITY).is_empty());
+    // Huge sweeps are reduced by whole turns, and still end at the same angle.
+    assert_eq!(RationalQuadraticBezier::ellipse_arcs([0.0f64, 0.0], [2.0, 1.0], 0.0, 2000.0 * PI).len(), 4000);
+    let arcs = RationalQuadraticBezier::ellipse_arcs([0.0f64, 0.0], [2.0, 1.0], 0.0, 1e12);
+    assert!(!arcs.is_empty() && arcs.len() <= 4);
+    let end = arcs[arcs.len() - 1].end();
+    assert!((end[0] - 2.0 * 1e12f64.cos()).abs() < 1e-3 && (end[1] - 1e12f64.sin()).abs() < 1e-3);
+    assert!(RationalQuadraticBezier::ellipse_arcs([0.0f32, 0.0], [2.0, 1.0], 0.0, -1e30).len() <= 4);
+}
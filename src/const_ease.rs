@@ -0,0 +1,476 @@
+//! Easings as `const fn`
+//!
+//! The polynomial easings match the `Ease` methods bit for bit,
+//! and can be evaluated in `const` items, such as lookup tables
+//! or tuning constants computed at compile time.
+//!
+//! `sin`, `powf` and `sqrt` are not available in `const fn`,
+//! so `ease_f64` evaluates the transcendental easings with portable approximations.
+//! These are accurate to a few units in the last place, but not bit for bit.
+//! `bake_lut` bakes a table of any ease function into the binary,
+//! and the `bake_lut!` macro does the same for custom curves.
+//! Curves that cannot be evaluated in a `const` are baked on first use
+//! into a `LazyLut` instead, with the `std` feature.
+
+#[cfg(feature = "std")]
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+use deterministic::{cos, exp2, sin, sqrt};
+use ease::{bounce, EaseFunction};
+
+/// Defines the const easings for a float type.
+macro_rules! const_eases {
+    ($t: ident;
+     $clamp: ident, $quadratic_in: ident, $quadratic_out: ident, $quadratic_in_out: ident,
+     $cubic_in: ident, $cubic_out: ident, $cubic_in_out: ident,
+     $quartic_in: ident, $quartic_out: ident, $quartic_in_out: ident,
+     $quintic_in: ident, $quintic_out: ident, $quintic_in_out: ident,
+     $smoothstep: ident, $bounce_in: ident, $bounce_out: ident, $bounce_in_out: ident) => (
+        const fn $clamp(p: $t) -> $t {
+            if p > 1.0 { 1.0 } else if p < 0.0 { 0.0 } else { p }
+        }
+
+        #[doc = concat!("Quadratic ease in for `", stringify!($t), "`.")]
+        pub const fn $quadratic_in(p: $t) -> $t {
+            let p = $clamp(p);
+            p * p
+        }
+
+        #[doc = concat!("Quadratic ease out for `", stringify!($t), "`.")]
+        pub const fn $quadratic_out(p: $t) -> $t {
+            let p = $clamp(p);
+            -(p * (p - 2.0))
+        }
+
+        #[doc = concat!("Quadratic ease in and out for `", stringify!($t), "`.")]
+        pub const fn $quadratic_in_out(p: $t) -> $t {
+            let p = $clamp(p);
+            if p < 0.5 {
+                2.0 * p * p
+            } else {
+                (-2.0 * p * p) + (4.0 * p) - 1.0
+            }
+        }
+
+        #[doc = concat!("Cubic ease in for `", stringify!($t), "`.")]
+        pub const fn $cubic_in(p: $t) -> $t {
+            let p = $clamp(p);
+            p * p * p
+        }
+
+        #[doc = concat!("Cubic ease out for `", stringify!($t), "`.")]
+        pub const fn $cubic_out(p: $t) -> $t {
+            let p = $clamp(p);
+            let f = p - 1.0;
+            f * f * f + 1.0
+        }
+
+        #[doc = concat!("Cubic ease in and out for `", stringify!($t), "`.")]
+        pub const fn $cubic_in_out(p: $t) -> $t {
+            let p = $clamp(p);
+            if p < 0.5 {
+                4.0 * p * p * p
+            } else {
+                let f = (2.0 * p) - 2.0;
+                0.5 * f * f * f + 1.0
+            }
+        }
+
+        #[doc = concat!("Quartic ease in for `", stringify!($t), "`.")]
+        pub const fn $quartic_in(p: $t) -> $t {
+            let p = $clamp(p);
+            p * p * p * p
+        }
+
+        #[doc = concat!("Quartic ease out for `", stringify!($t), "`.")]
+        pub const fn $quartic_out(p: $t) -> $t {
+            let p = $clamp(p);
+            let f = p - 1.0;
+            f * f * f * (1.0 - p) + 1.0
+        }
+
+        #[doc = concat!("Quartic ease in and out for `", stringify!($t), "`.")]
+        pub const fn $quartic_in_out(p: $t) -> $t {
+            let p = $clamp(p);
+            if p < 0.5 {
+                8.0 * p * p * p * p
+            } else {
+                let f = p - 1.0;
+                -8.0 * f * f * f * f + 1.0
+            }
+        }
+
+        #[doc = concat!("Quintic ease in for `", stringify!($t), "`.")]
+        pub const fn $quintic_in(p: $t) -> $t {
+            let p = $clamp(p);
+            p * p * p * p * p
+        }
+
+        #[doc = concat!("Quintic ease out for `", stringify!($t), "`.")]
+        pub const fn $quintic_out(p: $t) -> $t {
+            let p = $clamp(p);
+            let f = p - 1.0;
+            f * f * f * f * f + 1.0
+        }
+
+        #[doc = concat!("Quintic ease in and out for `", stringify!($t), "`.")]
+        pub const fn $quintic_in_out(p: $t) -> $t {
+            let p = $clamp(p);
+            if p < 0.5 {
+                16.0 * p * p * p * p * p
+            } else {
+                let f = (2.0 * p) - 2.0;
+                0.5 * f * f * f * f * f + 1.0
+            }
+        }
+
+        #[doc = concat!("Smoothstep, `3p^2 - 2p^3`, for `", stringify!($t), "`.")]
+        pub const fn $smoothstep(p: $t) -> $t {
+            let p = $clamp(p);
+            p * p * (3.0 - 2.0 * p)
+        }
+
+        #[doc = concat!("Bounce ease in for `", stringify!($t), "`.")]
+        pub const fn $bounce_in(p: $t) -> $t {
+            let p = $clamp(p);
+            1.0 - $bounce_out(1.0 - p)
+        }
+
+        #[doc = concat!("Bounce ease out for `", stringify!($t), "`.")]
+        pub const fn $bounce_out(p: $t) -> $t {
+            let p = $clamp(p);
+            if p < bounce::T1 as $t {
+                bounce::A1 as $t * p * p
+            } else if p < bounce::T2 as $t {
+                let d = p - bounce::H2 as $t;
+                bounce::A2 as $t * d * d + bounce::M2 as $t
+            } else if p < bounce::T3 as $t {
+                let d = p - bounce::H3 as $t;
+                bounce::A3 as $t * d * d + bounce::M3 as $t
+            } else {
+                let q = 1.0 - p;
+                (bounce::A4 as $t * q - bounce::B4 as $t) * q + 1.0
+            }
+        }
+
+        #[doc = concat!("Bounce ease in and out for `", stringify!($t), "`.")]
+        pub const fn $bounce_in_out(p: $t) -> $t {
+            let p = $clamp(p);
+            if p < 0.5 {
+                0.5 * $bounce_in(p * 2.0)
+            } else {
+                0.5 * $bounce_out(p * 2.0 - 1.0) + 0.5
+            }
+        }
+    )
+}
+
+const_eases!(f32;
+    clamp_f32, quadratic_in_f32, quadratic_out_f32, quadratic_in_out_f32,
+    cubic_in_f32, cubic_out_f32, cubic_in_out_f32,
+    quartic_in_f32, quartic_out_f32, quartic_in_out_f32,
+    quintic_in_f32, quintic_out_f32, quintic_in_out_f32,
+    smoothstep_f32, bounce_in_f32, bounce_out_f32, bounce_in_out_f32);
+
+const_eases!(f64;
+    clamp_f64, quadratic_in_f64, quadratic_out_f64, quadratic_in_out_f64,
+    cubic_in_f64, cubic_out_f64, cubic_in_out_f64,
+    quartic_in_f64, quartic_out_f64, quartic_in_out_f64,
+    quintic_in_f64, quintic_out_f64, quintic_in_out_f64,
+    smoothstep_f64, bounce_in_f64, bounce_out_f64, bounce_in_out_f64);
+
+use core::f64::consts::{PI, TAU};
+
+const fn clamp(p: f64) -> f64 {
+    clamp_f64(p)
+}
+
+const fn sine_in(p: f64) -> f64 {
+    sin((clamp(p) - 1.0) * TAU) + 1.0
+}
+
+const fn sine_out(p: f64) -> f64 {
+    sin(clamp(p) * TAU)
+}
+
+const fn sine_in_out(p: f64) -> f64 {
+    0.5 * (1.0 - cos(clamp(p) * PI))
+}
+
+const fn circular_in(p: f64) -> f64 {
+    let p = clamp(p);
+    1.0 - sqrt(1.0 - (p * p))
+}
+
+const fn circular_out(p: f64) -> f64 {
+    let p = clamp(p);
+    sqrt((2.0 - p) * p)
+}
+
+const fn circular_in_out(p: f64) -> f64 {
+    let p = clamp(p);
+    if p < 0.5 {
+        0.5 * (1.0 - sqrt(1.0 - 4.0 * (p * p)))
+    } else {
+        0.5 * (sqrt(-((2.0 * p) - 3.0) * ((2.0 * p) - 1.0)) + 1.0)
+    }
+}
+
+const fn exponential_in(p: f64) -> f64 {
+    let p = clamp(p);
+    if p <= 0.0 { 0.0 } else { exp2(10.0 * (p - 1.0)) }
+}
+
+const fn exponential_out(p: f64) -> f64 {
+    let p = clamp(p);
+    if p >= 1.0 { 1.0 } else { 1.0 - exp2(-10.0 * p) }
+}
+
+const fn exponential_in_out(p: f64) -> f64 {
+    if p <= 0.0 {
+        0.0
+    } else if p >= 1.0 {
+        1.0
+    } else if p < 0.5 {
+        0.5 * exp2((20.0 * p) - 10.0)
+    } else {
+        -0.5 * exp2((-20.0 * p) + 10.0) + 1.0
+    }
+}
+
+const fn elastic_in(p: f64) -> f64 {
+    let p = clamp(p);
+    sin(13.0 * TAU * p) * exp2(10.0 * (p - 1.0))
+}
+
+const fn elastic_out(p: f64) -> f64 {
+    let p = clamp(p);
+    sin(-13.0 * TAU * (p + 1.0)) * exp2(-10.0 * p) + 1.0
+}
+
+const fn elastic_in_out(p: f64) -> f64 {
+    let p = clamp(p);
+    if p < 0.5 {
+        0.5 * sin(13.0 * TAU * (2.0 * p)) * exp2(10.0 * ((2.0 * p) - 1.0))
+    } else {
+        0.5 * (sin(-13.0 * TAU * ((2.0 * p - 1.0) + 1.0)) * exp2(-10.0 * (2.0 * p - 1.0)) + 2.0)
+    }
+}
+
+const fn back(f: f64) -> f64 {
+    f * f * f - f * sin(f * PI)
+}
+
+const fn back_in(p: f64) -> f64 {
+    back(clamp(p))
+}
+
+const fn back_out(p: f64) -> f64 {
+    1.0 - back(1.0 - clamp(p))
+}
+
+const fn back_in_out(p: f64) -> f64 {
+    let p = clamp(p);
+    if p < 0.5 {
+        0.5 * back(2.0 * p)
+    } else {
+        0.5 * (1.0 - back(1.0 - (2.0 * p - 1.0))) + 0.5
+    }
+}
+
+/// Evaluates any ease function for `f64`.
+///
+/// Polynomial and bounce easings match `Ease` bit for bit.
+/// The others are computed with portable approximations of `sin`, `cos`,
+/// `sqrt` and `powf`, and may differ from `Ease` in the last few bits.
+pub const fn ease_f64(f: EaseFunction, p: f64) -> f64 {
+    use ease::EaseFunction::*;
+
+    match f {
+        QuadraticIn => quadratic_in_f64(p),
+        QuadraticOut => quadratic_out_f64(p),
+        QuadraticInOut => quadratic_in_out_f64(p),
+        CubicIn => cubic_in_f64(p),
+        CubicOut => cubic_out_f64(p),
+        CubicInOut => cubic_in_out_f64(p),
+        QuarticIn => quartic_in_f64(p),
+        QuarticOut => quartic_out_f64(p),
+        QuarticInOut => quartic_in_out_f64(p),
+        QuinticIn => quintic_in_f64(p),
+        QuinticOut => quintic_out_f64(p),
+        QuinticInOut => quintic_in_out_f64(p),
+        SineIn => sine_in(p),
+        SineOut => sine_out(p),
+        SineInOut => sine_in_out(p),
+        CircularIn => circular_in(p),
+        CircularOut => circular_out(p),
+        CircularInOut => circular_in_out(p),
+        ExponentialIn => exponential_in(p),
+        ExponentialOut => exponential_out(p),
+        ExponentialInOut => exponential_in_out(p),
+        ElasticIn => elastic_in(p),
+        ElasticOut => elastic_out(p),
+        ElasticInOut => elastic_in_out(p),
+        BackIn => back_in(p),
+        BackOut => back_out(p),
+        BackInOut => back_in_out(p),
+        BounceIn => bounce_in_f64(p),
+        BounceOut => bounce_out_f64(p),
+        BounceInOut => bounce_in_out_f64(p),
+    }
+}
+
+/// Bakes `N` evenly spaced samples of an ease function, including both ends,
+/// like `EaseTable::new`.
+///
+/// The samples are computed in `f64` with `ease_f64` and rounded once,
+/// so a table in a `const` or `static` costs nothing at runtime.
+///
+/// ```
+/// use interpolation::EaseFunction;
+/// use interpolation::const_ease::bake_lut;
+///
+/// static SINE: [f32; 5] = bake_lut(EaseFunction::SineInOut);
+/// assert_eq!(SINE[0], 0.0);
+/// assert_eq!(SINE[4], 1.0);
+/// ```
+pub const fn bake_lut<const N: usize>(f: EaseFunction) -> [f32; N] {
+    let mut lut = [0.0; N];
+    let mut rest: &mut [f32] = &mut lut;
+    let mut i = 0;
+    while let [v, tail @ ..] = rest {
+        let p = if N > 1 { i as f64 / (N - 1) as f64 } else { 0.0 };
+        *v = ease_f64(f, p) as f32;
+        rest = tail;
+        i += 1;
+    }
+    lut
+}
+
+/// A table of `N` evenly spaced samples of a curve, including both ends,
+/// baked on first use, for curves that cannot be evaluated in a `const`.
+///
+/// It can be a `static`, and is usually created with `bake_lut!(lazy n, |p| ...)`.
+/// Later uses only read the samples.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct LazyLut<const N: usize> {
+    curve: fn(f64) -> f64,
+    table: OnceLock<[f32; N]>,
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> LazyLut<N> {
+    /// Creates a table of `curve`, a function of progress from 0 to 1.
+    pub const fn new(curve: fn(f64) -> f64) -> LazyLut<N> {
+        LazyLut { curve, table: OnceLock::new() }
+    }
+
+    /// Returns the samples, baking them on the first call.
+    pub fn get(&self) -> &[f32; N] {
+        self.table.get_or_init(|| {
+            let mut lut = [0.0; N];
+            for (i, v) in lut.iter_mut().enumerate() {
+                let p = if N > 1 { i as f64 / (N - 1) as f64 } else { 0.0 };
+                *v = (self.curve)(p) as f32;
+            }
+            lut
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> Deref for LazyLut<N> {
+    type Target = [f32; N];
+
+    fn deref(&self) -> &[f32; N] {
+        self.get()
+    }
+}
+
+// Fused multiply-add rounds differently, and is not available in `const fn`.
+#[cfg(not(feature = "fma"))]
+#[test]
+fn const_eases_match_runtime() {
+    use ease::{Ease, EaseFunction};
+
+    const HALF: f32 = cubic_in_out_f32(0.25);
+    assert_eq!(HALF, 0.0625);
+
+    type Const = (EaseFunction, fn(f32) -> f32, fn(f64) -> f64);
+    let fns: [Const; 15] = [
+        (EaseFunction::QuadraticIn, quadratic_in_f32, quadratic_in_f64),
+        (EaseFunction::QuadraticOut, quadratic_out_f32, quadratic_out_f64),
+        (EaseFunction::QuadraticInOut, quadratic_in_out_f32, quadratic_in_out_f64),
+        (EaseFunction::CubicIn, cubic_in_f32, cubic_in_f64),
+        (EaseFunction::CubicOut, cubic_out_f32, cubic_out_f64),
+        (EaseFunction::CubicInOut, cubic_in_out_f32, cubic_in_out_f64),
+        (EaseFunction::QuarticIn, quartic_in_f32, quartic_in_f64),
+        (EaseFunction::QuarticOut, quartic_out_f32, quartic_out_f64),
+        (EaseFunction::QuarticInOut, quartic_in_out_f32, quartic_in_out_f64),
+        (EaseFunction::QuinticIn, quintic_in_f32, quintic_in_f64),
+        (EaseFunction::QuinticOut, quintic_out_f32, quintic_out_f64),
+        (EaseFunction::QuinticInOut, quintic_in_out_f32, quintic_in_out_f64),
+        (EaseFunction::BounceIn, bounce_in_f32, bounce_in_f64),
+        (EaseFunction::BounceOut, bounce_out_f32, bounce_out_f64),
+        (EaseFunction::BounceInOut, bounce_in_out_f32, bounce_in_out_f64),
+    ];
+    for i in -10..=1010 {
+        let (p32, p64) = (i as f32 / 1000.0, i as f64 / 1000.0);
+        for &(f, c32, c64) in &fns {
+            assert_eq!(c32(p32).to_bits(), p32.calc(f).to_bits(), "{:?} {}", f, p32);
+            assert_eq!(c64(p64).to_bits(), p64.calc(f).to_bits(), "{:?} {}", f, p64);
+        }
+        let mut v = [p32];
+        ::batch::smoothstep_slice(&mut v);
+        assert_eq!(smoothstep_f32(p32), v[0]);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn const_lut() {
+    use bake::EaseTable;
+    use ease::Ease;
+
+    const QUAD: [f32; 5] = bake_lut(EaseFunction::QuadraticIn);
+    assert_eq!(QUAD, [0.0, 0.0625, 0.25, 0.5625, 1.0]);
+    assert_eq!(bake_lut::<1>(EaseFunction::QuadraticIn), [0.0]);
+    assert!(bake_lut::<0>(EaseFunction::QuadraticIn).is_empty());
+
+    for &f in EaseFunction::ALL.iter() {
+        for i in -10..=1010 {
+            let p = i as f64 / 1000.0;
+            let (c, r) = (ease_f64(f, p), p.calc(f));
+            assert!((c - r).abs() < 1e-13, "{:?} {} {} {}", f, p, c, r);
+        }
+        let lut: [f32; 33] = bake_lut(f);
+        for (i, a) in lut.iter().enumerate() {
+            let r = (i as f64 / 32.0).calc(f);
+            assert!((*a as f64 - r).abs() <= 1e-7 * r.abs().max(1.0), "{:?}", f);
+        }
+        if f == EaseFunction::CubicOut {
+            assert_eq!(&lut[..], EaseTable::new(f, 33).samples());
+        }
+    }
+
+    const CUSTOM: [f32; 3] = bake_lut!(3, |p| p * p * p);
+    assert_eq!(CUSTOM, [0.0, 0.125, 1.0]);
+    static ELASTIC: [f32; 16] = bake_lut!(16, ElasticOut);
+    assert_eq!(ELASTIC, bake_lut(EaseFunction::ElasticOut));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn lazy_lut() {
+    static SINE: LazyLut<5> = bake_lut!(lazy 5, |p| (p * ::core::f64::consts::FRAC_PI_2).sin());
+    assert_eq!(SINE[0], 0.0);
+    assert_eq!(SINE[2], ::core::f32::consts::FRAC_1_SQRT_2);
+    assert_eq!(SINE[4], 1.0);
+    assert!(::core::ptr::eq(SINE.get(), &*SINE));
+    let single: LazyLut<1> = bake_lut!(lazy 1, |p| p + 0.5);
+    assert_eq!(*single, [0.5]);
+    assert!(LazyLut::<0>::new(|p| p).is_empty());
+}
@@ -0,0 +1,91 @@
+//! Parsing of CSS timing functions
+
+use bezier_ease::CubicBezierEase;
+use ease::{Easing, StepPosition};
+
+impl Easing {
+    /// Parses a CSS `transition-timing-function` or `animation-timing-function`.
+    ///
+    /// Accepts the keywords `linear`, `ease`, `ease-in`, `ease-out`, `ease-in-out`,
+    /// `step-start` and `step-end`, and the functions `cubic-bezier(x1, y1, x2, y2)`
+    /// and `steps(n, position)`.
+    /// Returns `None` for anything the CSS specification rejects,
+    /// such as Bézier `x` values outside `[0, 1]`.
+    pub fn from_css(value: &str) -> Option<Easing> {
+        let value = value.trim();
+        let bezier = |x1, y1, x2, y2| Some(CubicBezierEase::new(x1, y1, x2, y2).into());
+        let keyword = |k: &str| value.eq_ignore_ascii_case(k);
+        match () {
+            _ if keyword("linear") => return Some(Easing::Linear),
+            _ if keyword("ease") => return bezier(0.25, 0.1, 0.25, 1.0),
+            _ if keyword("ease-in") => return bezier(0.42, 0.0, 1.0, 1.0),
+            _ if keyword("ease-out") => return bezier(0.0, 0.0, 0.58, 1.0),
+            _ if keyword("ease-in-out") => return bezier(0.42, 0.0, 0.58, 1.0),
+            _ if keyword("step-start") => return Some(Easing::Steps(1, StepPosition::Start)),
+            _ if keyword("step-end") => return Some(Easing::Steps(1, StepPosition::End)),
+            _ => {}
+        }
+
+        let open = value.find('(')?;
+        let name = value[..open].trim_end();
+        let args = value[open + 1..].strip_suffix(')')?;
+        let mut args = args.split(',').map(|a| a.trim());
+        let args = [args.next(), args.next(), args.next(), args.next(), args.next()];
+        match args {
+            [Some(x1), Some(y1), Some(x2), Some(y2), None] if name.eq_ignore_ascii_case("cubic-bezier") => {
+                let (x1, y1, x2, y2): (f64, f64, f64, f64) =
+                    (x1.parse().ok()?, y1.parse().ok()?, x2.parse().ok()?, y2.parse().ok()?);
+                let finite = x1.is_finite() && y1.is_finite() && x2.is_finite() && y2.is_finite();
+                if !finite || !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+                    return None;
+                }
+                bezier(x1, y1, x2, y2)
+            }
+            [Some(count), None, ..] if name.eq_ignore_ascii_case("steps") => steps(count, StepPosition::End),
+            [Some(count), Some(position), None, ..] if name.eq_ignore_ascii_case("steps") => {
+                let position = match () {
+                    _ if position.eq_ignore_ascii_case("jump-start") => StepPosition::Start,
+                    _ if position.eq_ignore_ascii_case("start") => StepPosition::Start,
+                    _ if position.eq_ignore_ascii_case("jump-end") => StepPosition::End,
+                    _ if position.eq_ignore_ascii_case("end") => StepPosition::End,
+                    _ if position.eq_ignore_ascii_case("jump-none") => StepPosition::None,
+                    _ if position.eq_ignore_ascii_case("jump-both") => StepPosition::Both,
+                    _ => return None,
+                };
+                steps(count, position)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn steps(count: &str, position: StepPosition) -> Option<Easing> {
+    let count: u32 = count.parse().ok()?;
+    let min = if position == StepPosition::None { 2 } else { 1 };
+    if count < min {
+        return None;
+    }
+    Some(Easing::Steps(count, position))
+}
+
+#[test]
+fn easing_from_css() {
+    assert_eq!(Easing::from_css("linear"), Some(Easing::Linear));
+    assert_eq!(Easing::from_css(" Ease-In-Out "),
+               Some(CubicBezierEase::new(0.42, 0.0, 0.58, 1.0).into()));
+    assert_eq!(Easing::from_css("cubic-bezier(.17,.67,.83,.67)"),
+               Some(CubicBezierEase::new(0.17, 0.67, 0.83, 0.67).into()));
+    assert_eq!(Easing::from_css("cubic-bezier(1.5, 0, 0, 1)"), None);
+    assert_eq!(Easing::from_css("steps(4, end)"), Some(Easing::Steps(4, StepPosition::End)));
+    assert_eq!(Easing::from_css("steps(1, jump-none)"), None);
+    assert_eq!(Easing::from_css("bounce"), None);
+
+    let steps = Easing::from_css("steps(4, end)").unwrap();
+    assert_eq!(steps.calc(0.3f32), 0.25);
+    assert_eq!(steps.calc(1.0f32), 1.0);
+    let steps = Easing::from_css("steps(4, jump-both)").unwrap();
+    assert_eq!(steps.calc(0.0f64), 0.2);
+    assert_eq!(steps.calc(1.0f64), 1.0);
+    let steps = Easing::from_css("step-start").unwrap();
+    assert_eq!(steps.calc(0.0f64), 1.0);
+}
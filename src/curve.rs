@@ -0,0 +1,154 @@
+//! Scalar curves through keyframes with tangents
+
+use alloc::vec::Vec;
+
+use bezier_ease::CubicBezierEase;
+use error::{self, check_increasing};
+use float::mul_add;
+
+/// A keyframe of an `AnimationCurve`.
+///
+/// Tangents are slopes in value per unit of time.
+/// An infinite tangent holds the value, like a stepped key.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CurveKey {
+    /// The time of the key.
+    pub time: f32,
+    /// The value at the key.
+    pub value: f32,
+    /// The slope arriving at the key.
+    pub in_tangent: f32,
+    /// The slope leaving the key.
+    pub out_tangent: f32,
+    /// The length of the in handle, as a fraction of the previous segment.
+    /// `None` uses one third, which matches a Hermite curve.
+    pub in_weight: Option<f32>,
+    /// The length of the out handle, as a fraction of the next segment.
+    /// `None` uses one third, which matches a Hermite curve.
+    pub out_weight: Option<f32>,
+}
+
+impl CurveKey {
+    /// Creates a key with unweighted tangents.
+    pub fn new(time: f32, value: f32, in_tangent: f32, out_tangent: f32) -> CurveKey {
+        CurveKey { time, value, in_tangent, out_tangent, in_weight: None, out_weight: None }
+    }
+}
+
+/// A scalar curve through keys with tangents, such as a tuning curve
+/// authored in a game engine.
+///
+/// Segments are cubic Hermite curves, or cubic Bézier curves in time
+/// when one of their handles is weighted.
+/// Times outside the key range hold the first or last value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationCurve {
+    keys: Vec<CurveKey>,
+}
+
+impl AnimationCurve {
+    /// Creates a curve from keys, sorted by time.
+    pub fn new(mut keys: Vec<CurveKey>) -> AnimationCurve {
+        keys.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap_or(::core::cmp::Ordering::Equal));
+        AnimationCurve { keys }
+    }
+
+    /// Creates a curve from keys, checking that there is at least one key
+    /// and that the keys are in increasing time order, instead of sorting them.
+    pub fn try_new(keys: Vec<CurveKey>) -> error::Result<AnimationCurve> {
+        let times: Vec<f32> = keys.iter().map(|k| k.time).collect();
+        check_increasing(&times)?;
+        Ok(AnimationCurve { keys })
+    }
+
+    /// Returns the keys in time order.
+    pub fn keys(&self) -> &[CurveKey] {
+        &self.keys
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the value at `time`, or `None` if there are no keys.
+    pub fn sample(&self, time: f32) -> Option<f32> {
+        let i = self.keys.iter().take_while(|k| k.time <= time).count();
+        let (a, b) = match (i.checked_sub(1).and_then(|j| self.keys.get(j)), self.keys.get(i)) {
+            (Some(a), Some(b)) => (a, b),
+            (Some(a), None) => return Some(a.value),
+            (None, b) => return b.map(|b| b.value),
+        };
+        let (m0, m1) = (a.out_tangent, b.in_tangent);
+        if !m0.is_finite() || !m1.is_finite() {
+            return Some(a.value);
+        }
+        let td = b.time - a.time;
+        let t = (time - a.time) / td;
+        if a.out_weight.is_none() && b.in_weight.is_none() {
+            let (t2, t3) = (t * t, t * t * t);
+            let v = mul_add(m0 * td, t3 - 2.0 * t2 + t, a.value * (2.0 * t3 - 3.0 * t2 + 1.0));
+            let v = mul_add(b.value, -2.0 * t3 + 3.0 * t2, v);
+            return Some(mul_add(m1 * td, t3 - t2, v));
+        }
+
+        // Weighted handles move the control points in time,
+        // so the curve parameter at `time` is solved for first.
+        let w0 = a.out_weight.unwrap_or(1.0 / 3.0);
+        let w1 = b.in_weight.unwrap_or(1.0 / 3.0);
+        let ease = CubicBezierEase::new(w0 as f64, 0.0, 1.0 - w1 as f64, 1.0);
+        let s = ease.solve(t as f64) as f32;
+        let y1 = a.value + w0 * td * m0;
+        let y2 = b.value - w1 * td * m1;
+        let u = 1.0 - s;
+        Some(u * u * u * a.value + 3.0 * u * u * s * y1 + 3.0 * u * s * s * y2 + s * s * s * b.value)
+    }
+}
+
+#[test]
+fn animation_curve_hermite() {
+    let curve = AnimationCurve::new(vec![
+        CurveKey::new(2.0, 2.0, 1.0, 1.0),
+        CurveKey::new(0.0, 0.0, 1.0, 1.0),
+        CurveKey::new(3.0, 5.0, 0.0, f32::INFINITY),
+        CurveKey::new(4.0, 0.0, 0.0, 0.0),
+    ]);
+    assert_eq!(curve.keys()[0].time, 0.0);
+    assert_eq!(curve.sample(-1.0), Some(0.0));
+    assert_eq!(curve.sample(1.0), Some(1.0));
+    assert_eq!(curve.sample(3.5), Some(5.0));
+    assert_eq!(curve.sample(9.0), Some(0.0));
+    assert_eq!(AnimationCurve::new(vec![]).sample(0.0), None);
+}
+
+#[test]
+fn animation_curve_weighted() {
+    let mut a = CurveKey::new(0.0, 0.0, 0.0, 2.0);
+    let mut b = CurveKey::new(2.0, 1.0, -1.0, 0.0);
+    let hermite = AnimationCurve::new(vec![a, b]);
+    a.out_weight = Some(1.0 / 3.0);
+    b.in_weight = Some(1.0 / 3.0);
+    let weighted = AnimationCurve::new(vec![a, b]);
+    for &t in &[0.25, 0.7, 1.3, 1.9] {
+        let (h, w) = (hermite.sample(t).unwrap(), weighted.sample(t).unwrap());
+        assert!((h - w).abs() < 1e-5);
+    }
+    b.in_weight = Some(0.9);
+    assert!(AnimationCurve::new(vec![a, b]).sample(1.0) != hermite.sample(1.0));
+}
+
+#[test]
+fn animation_curve_try_new() {
+    use error::InterpolationError::*;
+
+    let keys = vec![CurveKey::new(1.0, 0.0, 0.0, 0.0), CurveKey::new(0.0, 1.0, 0.0, 0.0)];
+    assert_eq!(AnimationCurve::try_new(keys.clone()).err(), Some(NotIncreasing { index: 1 }));
+    assert_eq!(AnimationCurve::try_new(vec![]).err(), Some(Empty));
+    let curve = AnimationCurve::try_new(keys.into_iter().rev().collect()).unwrap();
+    assert_eq!(curve.sample(1.0), Some(0.0));
+}
@@ -0,0 +1,96 @@
+//! Rendering curves, easings and tracks to images for visual inspection
+//!
+//! The image format follows the extension of the path: `.svg` writes an SVG,
+//! anything else a bitmap such as PNG.
+//! Plots have no labels, so no fonts are needed.
+
+use std::error::Error;
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use bake::BakedTrack;
+use ease::{Ease, EaseFunction};
+use record::record;
+
+/// The size of the images in pixels.
+pub const SIZE: (u32, u32) = (640, 480);
+
+/// Plots several series of `(x, y)` samples, each with its own color.
+pub fn plot_series<P: AsRef<Path>>(path: P, series: &[Vec<(f64, f64)>]) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    if path.extension().is_some_and(|e| e == "svg") {
+        draw(SVGBackend::new(path, SIZE).into_drawing_area(), series)
+    } else {
+        draw(BitMapBackend::new(path, SIZE).into_drawing_area(), series)
+    }
+}
+
+/// Plots `n` samples of `f` from zero to one.
+pub fn plot_fn<P, F>(path: P, n: usize, f: F) -> Result<(), Box<dyn Error>>
+    where P: AsRef<Path>, F: FnMut(f64) -> f64
+{
+    plot_series(path, &[record(n, f)])
+}
+
+/// Plots ease functions from zero to one, with a sample per pixel.
+pub fn plot_eases<P: AsRef<Path>>(path: P, functions: &[EaseFunction]) -> Result<(), Box<dyn Error>> {
+    let series: Vec<_> = functions.iter()
+        .map(|&f| record(SIZE.0 as usize, |t: f64| t.calc(f)))
+        .collect();
+    plot_series(path, &series)
+}
+
+/// Plots the values of a baked track over time.
+pub fn plot_track<P: AsRef<Path>>(path: P, track: &BakedTrack<f32>) -> Result<(), Box<dyn Error>> {
+    let samples = track.samples().into_iter()
+        .map(|(t, v)| (t as f64, v as f64))
+        .collect();
+    plot_series(path, &[samples])
+}
+
+fn draw<DB>(root: DrawingArea<DB, Shift>, series: &[Vec<(f64, f64)>]) -> Result<(), Box<dyn Error>>
+    where DB: DrawingBackend, DB::ErrorType: 'static
+{
+    let points = || series.iter().flat_map(|s| s.iter());
+    let (mut x0, mut x1, mut y0, mut y1) = (0.0f64, 1.0f64, 0.0f64, 1.0f64);
+    for &(x, y) in points() {
+        x0 = x0.min(x);
+        x1 = x1.max(x);
+        y0 = y0.min(y);
+        y1 = y1.max(y);
+    }
+    let pad = (y1 - y0) * 0.05;
+
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .margin(10)
+        .build_cartesian_2d(x0..x1, (y0 - pad)..(y1 + pad))?;
+    chart.draw_series(LineSeries::new(vec![(x0, 0.0), (x1, 0.0)], &BLACK.mix(0.3)))?;
+    chart.draw_series(LineSeries::new(vec![(x0, 1.0), (x1, 1.0)], &BLACK.mix(0.3)))?;
+    for (i, s) in series.iter().enumerate() {
+        chart.draw_series(LineSeries::new(s.iter().cloned(), &Palette99::pick(i)))?;
+    }
+    root.present()?;
+    Ok(())
+}
+
+#[test]
+fn debug_plot_files() {
+    use std::env;
+    use std::fs;
+
+    let dir = env::temp_dir();
+    let svg = dir.join("interpolation_debug_plot.svg");
+    plot_eases(&svg, &[EaseFunction::ElasticOut, EaseFunction::BackInOut]).unwrap();
+    assert!(fs::read_to_string(&svg).unwrap().contains("<polyline"));
+    fs::remove_file(&svg).unwrap();
+
+    let png = dir.join("interpolation_debug_plot.png");
+    plot_fn(&png, 64, |t| t * t).unwrap();
+    assert!(fs::metadata(&png).unwrap().len() > 0);
+    plot_track(&png, &BakedTrack::bake(1.0, 0.5, 8, |t| t.sin())).unwrap();
+    assert!(fs::metadata(&png).unwrap().len() > 0);
+    fs::remove_file(&png).unwrap();
+}
@@ -0,0 +1,172 @@
+//! Math functions built from basic arithmetic only
+//!
+//! Addition, multiplication, division and square root are correctly rounded
+//! on every IEEE 754 target, but `sin`, `cos` and `powf` come from the platform
+//! math library, which differs between operating systems and targets.
+//! These replacements use only the correctly rounded operations,
+//! so they give bit-identical results everywhere.
+//! They are computed in `f64` and are accurate to a few units in the last place
+//! for the arguments used by easings.
+//!
+//! The functions are `const`, so they are also used to evaluate
+//! the transcendental easings at compile time.
+
+/// Taylor coefficients of `sin(r) / r` in `r^2`, from `r^14` down.
+const SIN: [f64; 8] = [
+    -7.647163731819816e-13, 1.6059043836821613e-10, -2.505210838544172e-08,
+    2.7557319223985893e-06, -0.0001984126984126984, 0.008333333333333333,
+    -0.16666666666666666, 1.0,
+];
+
+/// Taylor coefficients of `cos(r)` in `r^2`, from `r^16` down.
+const COS: [f64; 9] = [
+    4.779477332387385e-14, -1.1470745597729725e-11, 2.08767569878681e-09,
+    -2.755731922398589e-07, 2.48015873015873e-05, -0.001388888888888889,
+    0.041666666666666664, -0.5, 1.0,
+];
+
+/// Taylor coefficients of `2^f`, from `f^13` down.
+const EXP2: [f64; 14] = [
+    1.3691488853904124e-12, 2.5678435993488196e-11, 4.44553827187081e-10,
+    7.054911620801121e-09, 1.0178086009239696e-07, 1.3215486790144305e-06,
+    1.5252733804059838e-05, 0.00015403530393381606, 0.0013333558146428441,
+    0.009618129107628477, 0.055504108664821576, 0.2402265069591007,
+    ::core::f64::consts::LN_2, 1.0,
+];
+
+/// The first 33 bits of `pi/2`, so multiples of it by a quadrant are exact.
+const FRAC_PI_2_HI: f64 = 1.5707963267341256;
+/// The rest of `pi/2`.
+const FRAC_PI_2_LO: f64 = 6.077100506506192e-11;
+
+/// Evaluates a polynomial in `x` with coefficients from the highest degree.
+#[inline(always)]
+const fn poly(x: f64, mut coefficients: &[f64]) -> f64 {
+    let mut acc = 0.0;
+    while let [c, ref rest @ ..] = *coefficients {
+        acc = acc * x + c;
+        coefficients = rest;
+    }
+    acc
+}
+
+/// Rounds half away from zero, like `f64::round`, which is not in `core`.
+#[inline(always)]
+const fn round(x: f64) -> f64 {
+    // Beyond 2^52 every float is an integer, and NaN passes through.
+    if x.is_nan() || x.abs() >= 4503599627370496.0 {
+        return x;
+    }
+    let t = x as i64 as f64;
+    let d = x - t;
+    if d >= 0.5 {
+        t + 1.0
+    } else if d <= -0.5 {
+        t - 1.0
+    } else {
+        t
+    }
+}
+
+/// Splits `x` into a quadrant and a remainder within an eighth turn of it,
+/// and computes the sine and cosine of the remainder.
+#[inline(always)]
+const fn reduce(x: f64) -> (i64, f64, f64) {
+    let q = round(x * ::core::f64::consts::FRAC_2_PI);
+    let r = x - q * FRAC_PI_2_HI - q * FRAC_PI_2_LO;
+    let r2 = r * r;
+    (q as i64, r * poly(r2, &SIN), poly(r2, &COS))
+}
+
+/// Computes the sine of `x` in radians.
+pub const fn sin(x: f64) -> f64 {
+    let (q, s, c) = reduce(x);
+    match q & 3 {
+        0 => s,
+        1 => c,
+        2 => -s,
+        _ => -c,
+    }
+}
+
+/// Computes the cosine of `x` in radians.
+pub const fn cos(x: f64) -> f64 {
+    let (q, s, c) = reduce(x);
+    match q & 3 {
+        0 => c,
+        1 => -s,
+        2 => -c,
+        _ => s,
+    }
+}
+
+/// Returns `2^n` for `n` from -1022 to 1023, built from the bits.
+#[inline(always)]
+const fn pow2(n: i32) -> f64 {
+    f64::from_bits(((n + 1023) as u64) << 52)
+}
+
+/// Computes `2^x`.
+pub const fn exp2(x: f64) -> f64 {
+    let n = round(x);
+    if x.is_nan() {
+        return x;
+    }
+    if n > 1024.0 {
+        return f64::INFINITY;
+    }
+    if n < -1075.0 {
+        return 0.0;
+    }
+    // Powers of two beyond the normal range are built from two halves.
+    let n = n as i32;
+    poly(x - n as f64, &EXP2) * pow2(n / 2) * pow2(n - n / 2)
+}
+
+/// Computes the square root of `x` with Newton's method.
+///
+/// Unlike `f64::sqrt`, this is not always correctly rounded,
+/// but it can be evaluated at compile time.
+pub const fn sqrt(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 || x == f64::INFINITY {
+        return x;
+    }
+    // Halving the exponent gives a guess within a factor of two.
+    let mut y = f64::from_bits((x.to_bits() >> 1) + (1023 << 51));
+    let mut i = 0;
+    while i < 8 {
+        y = 0.5 * (y + x / y);
+        i += 1;
+    }
+    y
+}
+
+#[test]
+fn deterministic_accuracy() {
+    for i in -2000..=2000 {
+        let x = i as f64 / 10.0;
+        assert!((sin(x) - x.sin()).abs() < 1e-14, "sin {}", x);
+        assert!((cos(x) - x.cos()).abs() < 1e-14, "cos {}", x);
+        let x = i as f64 / 100.0;
+        let e = 2f64.powf(x);
+        assert!((exp2(x) - e).abs() <= e * 1e-15, "exp2 {}", x);
+    }
+    assert_eq!(exp2(-10.0), 1.0 / 1024.0);
+    assert_eq!(exp2(0.0), 1.0);
+    assert_eq!(sin(0.0), 0.0);
+    assert!(sin(f64::NAN).is_nan());
+    assert!(exp2(f64::NAN).is_nan());
+    assert_eq!(exp2(2000.0), f64::INFINITY);
+    for i in 0..=1000 {
+        let x = i as f64 / 7.0;
+        assert!((sqrt(x) - x.sqrt()).abs() <= x.sqrt() * 1e-15, "sqrt {}", x);
+    }
+    assert_eq!(sqrt(4.0), 2.0);
+    assert!(sqrt(-1.0).is_nan());
+    assert_eq!(exp2(-1074.0), 5e-324);
+    let e = 2f64.powf(1023.5);
+    assert!((exp2(1023.5) - e).abs() <= e * 1e-15);
+}
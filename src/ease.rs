@@ -1,49 +1,281 @@
 //! A module contains implementation of ease functions.
 
+use num_traits::{Float, FloatConst};
+
+use bezier_ease::CubicBezierEase;
+use float;
+use float::mul_add;
+use lerp::Lerp;
+use progress::ProgressMode;
+
+/// The standard ease functions.
+///
+/// The discriminants are fixed and match the order of `EaseFunction::ALL`,
+/// so the enum can be passed through the C interface.
 #[allow(missing_docs)]
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[repr(u32)]
 pub enum EaseFunction {
-    QuadraticIn,
-    QuadraticOut,
-    QuadraticInOut,
+    QuadraticIn = 0,
+    QuadraticOut = 1,
+    QuadraticInOut = 2,
 
-    CubicIn,
-    CubicOut,
-    CubicInOut,
+    CubicIn = 3,
+    CubicOut = 4,
+    CubicInOut = 5,
 
-    QuarticIn,
-    QuarticOut,
-    QuarticInOut,
+    QuarticIn = 6,
+    QuarticOut = 7,
+    QuarticInOut = 8,
 
-    QuinticIn,
-    QuinticOut,
-    QuinticInOut,
+    QuinticIn = 9,
+    QuinticOut = 10,
+    QuinticInOut = 11,
 
-    SineIn,
-    SineOut,
-    SineInOut,
+    SineIn = 12,
+    SineOut = 13,
+    SineInOut = 14,
 
-    CircularIn,
-    CircularOut,
-    CircularInOut,
+    CircularIn = 15,
+    CircularOut = 16,
+    CircularInOut = 17,
 
-    ExponentialIn,
-    ExponentialOut,
-    ExponentialInOut,
+    ExponentialIn = 18,
+    ExponentialOut = 19,
+    ExponentialInOut = 20,
 
-    ElasticIn,
-    ElasticOut,
-    ElasticInOut,
+    ElasticIn = 21,
+    ElasticOut = 22,
+    ElasticInOut = 23,
 
-    BackIn,
-    BackOut,
-    BackInOut,
+    BackIn = 24,
+    BackOut = 25,
+    BackInOut = 26,
 
-    BounceIn,
-    BounceOut,
-    BounceInOut,
+    BounceIn = 27,
+    BounceOut = 28,
+    BounceInOut = 29,
 }
 
+impl EaseFunction {
+    /// Every ease function, in declaration order.
+    pub const ALL: [EaseFunction; 30] = [
+        EaseFunction::QuadraticIn, EaseFunction::QuadraticOut, EaseFunction::QuadraticInOut,
+        EaseFunction::CubicIn, EaseFunction::CubicOut, EaseFunction::CubicInOut,
+        EaseFunction::QuarticIn, EaseFunction::QuarticOut, EaseFunction::QuarticInOut,
+        EaseFunction::QuinticIn, EaseFunction::QuinticOut, EaseFunction::QuinticInOut,
+        EaseFunction::SineIn, EaseFunction::SineOut, EaseFunction::SineInOut,
+        EaseFunction::CircularIn, EaseFunction::CircularOut, EaseFunction::CircularInOut,
+        EaseFunction::ExponentialIn, EaseFunction::ExponentialOut, EaseFunction::ExponentialInOut,
+        EaseFunction::ElasticIn, EaseFunction::ElasticOut, EaseFunction::ElasticInOut,
+        EaseFunction::BackIn, EaseFunction::BackOut, EaseFunction::BackInOut,
+        EaseFunction::BounceIn, EaseFunction::BounceOut, EaseFunction::BounceInOut,
+    ];
+
+    /// Evaluates the function for `f32`.
+    ///
+    /// Unlike `Ease::calc`, this names no type parameter,
+    /// so it can be stored as a plain function pointer.
+    #[inline]
+    pub fn calc_f32(self, p: f32) -> f32 {
+        p.calc(self)
+    }
+
+    /// Evaluates the function for `f32`, computing in `f64`
+    /// and rounding once at the end.
+    ///
+    /// This avoids the rounding errors of the intermediate `f32` steps,
+    /// which can make slow animations wobble near the ends.
+    #[inline]
+    pub fn calc_f32_wide(self, p: f32) -> f32 {
+        (p as f64).calc(self) as f32
+    }
+
+    /// Evaluates the function for `f64`.
+    ///
+    /// See `calc_f32`.
+    #[inline]
+    pub fn calc_f64(self, p: f64) -> f64 {
+        p.calc(self)
+    }
+
+    /// Eases `t` and interpolates from `start` to `end` with the result.
+    ///
+    /// For example, `CubicOut.calc_between(&20.0, &340.0, t)`
+    /// eases from 20 to 340 as `t` goes from zero to one.
+    #[inline]
+    pub fn calc_between<T>(self, start: &T, end: &T, t: T::Scalar) -> T
+        where T: Lerp, T::Scalar: Ease
+    {
+        start.lerp(end, &t.calc(self))
+    }
+
+    /// Looks up an ease function by name.
+    ///
+    /// Case, `_` and `-` are ignored, so `"CubicInOut"`, `"cubicInOut"`
+    /// and `"cubic_in_out"` are all accepted.
+    pub fn from_name(name: &str) -> Option<EaseFunction> {
+        // The longest name fits, and longer input cannot match.
+        let mut buf = [0u8; 16];
+        let mut len = 0;
+        for c in name.bytes().filter(|&c| c != b'_' && c != b'-') {
+            *buf.get_mut(len)? = c.to_ascii_lowercase();
+            len += 1;
+        }
+        Some(match ::core::str::from_utf8(buf.get(..len)?).ok()? {
+            "quadraticin" => EaseFunction::QuadraticIn,
+            "quadraticout" => EaseFunction::QuadraticOut,
+            "quadraticinout" => EaseFunction::QuadraticInOut,
+            "cubicin" => EaseFunction::CubicIn,
+            "cubicout" => EaseFunction::CubicOut,
+            "cubicinout" => EaseFunction::CubicInOut,
+            "quarticin" => EaseFunction::QuarticIn,
+            "quarticout" => EaseFunction::QuarticOut,
+            "quarticinout" => EaseFunction::QuarticInOut,
+            "quinticin" => EaseFunction::QuinticIn,
+            "quinticout" => EaseFunction::QuinticOut,
+            "quinticinout" => EaseFunction::QuinticInOut,
+            "sinein" => EaseFunction::SineIn,
+            "sineout" => EaseFunction::SineOut,
+            "sineinout" => EaseFunction::SineInOut,
+            "circularin" => EaseFunction::CircularIn,
+            "circularout" => EaseFunction::CircularOut,
+            "circularinout" => EaseFunction::CircularInOut,
+            "exponentialin" => EaseFunction::ExponentialIn,
+            "exponentialout" => EaseFunction::ExponentialOut,
+            "exponentialinout" => EaseFunction::ExponentialInOut,
+            "elasticin" => EaseFunction::ElasticIn,
+            "elasticout" => EaseFunction::ElasticOut,
+            "elasticinout" => EaseFunction::ElasticInOut,
+            "backin" => EaseFunction::BackIn,
+            "backout" => EaseFunction::BackOut,
+            "backinout" => EaseFunction::BackInOut,
+            "bouncein" => EaseFunction::BounceIn,
+            "bounceout" => EaseFunction::BounceOut,
+            "bounceinout" => EaseFunction::BounceInOut,
+            _ => return None,
+        })
+    }
+}
+
+/// An easing curve applied to a normalized progress value.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum Easing {
+    /// Progress is passed through unchanged.
+    Linear,
+    /// One of the standard ease functions.
+    Function(EaseFunction),
+    /// A curve shaped by cubic Bézier control points.
+    CubicBezier(CubicBezierEase),
+    /// Jumps between `count` equal steps, like CSS `steps()`.
+    Steps(u32, StepPosition),
+}
+
+/// Where the jumps of `Easing::Steps` happen, like the CSS step positions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum StepPosition {
+    /// Jumps at the start of each step, so the first step is above zero.
+    Start,
+    /// Jumps at the end of each step, so the last step is below one.
+    End,
+    /// Jumps between steps only, holding zero and one for a step each.
+    None,
+    /// Jumps at both ends, adding a step.
+    Both,
+}
+
+impl Easing {
+    /// Calculate the eased value, normalized
+    ///
+    /// NaN gives NaN, and infinite input is handled like in `Ease`,
+    /// except that `Linear` returns the input unchanged
+    /// and `Steps` keeps stepping outside the range from zero to one.
+    pub fn calc<T: Ease + float::Float>(self, t: T) -> T {
+        #[cfg(feature = "check-finite")]
+        debug_assert!(t.to_f64().is_finite(), "easing input is NaN or infinite");
+        match self {
+            Easing::Linear => t,
+            Easing::Function(f) => t.calc(f),
+            Easing::CubicBezier(c) => T::from_f64(c.calc(t.to_f64())),
+            Easing::Steps(count, position) => T::from_f64(steps(count, position, t.to_f64())),
+        }
+    }
+
+    /// Eases `t` and interpolates from `start` to `end` with the result.
+    ///
+    /// See `EaseFunction::calc_between`.
+    pub fn calc_between<T>(self, start: &T, end: &T, t: T::Scalar) -> T
+        where T: Lerp, T::Scalar: Ease + float::Float
+    {
+        start.lerp(end, &self.calc(t))
+    }
+
+    /// Calculate the eased value in `f64`, rounding once to `T` at the end.
+    ///
+    /// See `EaseFunction::calc_f32_wide`.
+    pub fn calc_wide<T: float::Float>(self, t: T) -> T {
+        T::from_f64(self.calc(t.to_f64()))
+    }
+
+    /// Calculate the eased value of progress mapped into range with `mode`,
+    /// such as wrapped progress for looping content.
+    pub fn calc_progress<T: Ease + float::Float>(self, mode: ProgressMode, t: T) -> T {
+        self.calc(mode.apply(t))
+    }
+
+    /// Replaces every value in `values` with its eased value.
+    pub fn calc_slice<T: Ease + float::Float>(self, values: &mut [T]) {
+        for v in values.iter_mut() {
+            *v = self.calc(*v);
+        }
+    }
+}
+
+fn steps(count: u32, position: StepPosition, t: f64) -> f64 {
+    let count = count.max(1) as f64;
+    let mut step = (t * count).floor();
+    if let StepPosition::Start | StepPosition::Both = position {
+        step += 1.0;
+    }
+    if t >= 0.0 && step < 0.0 {
+        step = 0.0;
+    }
+    let jumps = match position {
+        StepPosition::Start | StepPosition::End => count,
+        StepPosition::None => (count - 1.0).max(1.0),
+        StepPosition::Both => count + 1.0,
+    };
+    if t <= 1.0 && step > jumps {
+        step = jumps;
+    }
+    step / jumps
+}
+
+impl From<EaseFunction> for Easing {
+    fn from(f: EaseFunction) -> Easing {
+        Easing::Function(f)
+    }
+}
+
+impl From<CubicBezierEase> for Easing {
+    fn from(c: CubicBezierEase) -> Easing {
+        Easing::CubicBezier(c)
+    }
+}
+
+/// Ease functions of progress from zero to one.
+///
+/// Progress below zero, including negative infinity, is treated as zero,
+/// and progress above one, including infinity, is treated as one.
+/// NaN gives NaN for every function.
+/// With the `check-finite` feature, NaN and infinite progress
+/// are flagged with a debug assertion instead, to find where they come from.
 #[allow(missing_docs)]
 pub trait Ease {
     /// Calculate the eased value, normalized
@@ -86,304 +318,572 @@ pub trait Ease {
     fn back_in_out(self) -> Self;
 
     fn bounce_in(self) -> Self;
+    /// Bounces like a dropped ball, in four parabolas that end at exactly 0 and 1.
+    ///
+    /// Each parabola is evaluated around its lowest point. This deliberately changes
+    /// results from the expanded parabolas of earlier versions, by up to 1.5 ULP in `f32`,
+    /// and the new values are pinned by regression tests.
     fn bounce_out(self) -> Self;
     fn bounce_in_out(self) -> Self;
 }
 
-macro_rules! impl_ease_trait_for {
-    ($T: ident) => (
-        mod $T {
-            pub const PI_2: $T = 6.28318530717958647692528676655900576;
-
-            pub fn clamp(p: $T) -> $T {
-                match () {
-                    _ if p > 1.0 => 1.0,
-                    _ if p < 0.0 => 0.0,
-                    _ => p
-                }
-            }
+/// Converts a constant to the scalar type.
+///
+/// Every constant is a single literal or `const`, never an expression of
+/// converted values, so for `f32` and `f64` the conversion folds to an immediate.
+#[inline(always)]
+fn k<T: Float>(v: f64) -> T {
+    T::from(v).unwrap_or_else(T::nan)
+}
+
+/// The segment ends and coefficients of `bounce_out`.
+///
+/// Each segment is a parabola `a (p - h)^2 + m` around its lowest point,
+/// except the last one, which is `(a q - b) q + 1` with `q = 1 - p`,
+/// so that the ends map to exactly 0 and 1.
+pub(crate) mod bounce {
+    pub const T1: f64 = 0.36363636363636365;
+    pub const T2: f64 = 0.7272727272727273;
+    pub const T3: f64 = 0.9;
+    pub const A1: f64 = 7.5625;
+    pub const A2: f64 = 9.075;
+    pub const H2: f64 = 0.5454545454545454;
+    pub const M2: f64 = 0.7;
+    pub const A3: f64 = 12.066481994459833;
+    pub const H3: f64 = 0.8136363636363636;
+    pub const M3: f64 = 0.91;
+    pub const A4: f64 = 10.8;
+    pub const B4: f64 = 1.08;
+}
+
+/// Flags NaN and infinite input in debug builds, with the `check-finite` feature.
+#[inline(always)]
+fn check<T: Float>(p: T) -> T {
+    #[cfg(feature = "check-finite")]
+    debug_assert!(p.is_finite(), "easing input is NaN or infinite");
+    p
+}
+
+#[inline(always)]
+fn clamp<T: Float>(p: T) -> T {
+    let p = check(p);
+    match () {
+        _ if p > T::one() => T::one(),
+        _ if p < T::zero() => T::zero(),
+        _ => p
+    }
+}
+
+/// Computes the sine, from `deterministic` with the `deterministic` feature.
+#[inline(always)]
+fn sin<T: Float>(x: T) -> T {
+    #[cfg(feature = "deterministic")]
+    return k(::deterministic::sin(x.to_f64().unwrap_or(f64::NAN)));
+    #[cfg(not(feature = "deterministic"))]
+    return x.sin();
+}
+
+/// Computes the cosine, from `deterministic` with the `deterministic` feature.
+#[inline(always)]
+fn cos<T: Float>(x: T) -> T {
+    #[cfg(feature = "deterministic")]
+    return k(::deterministic::cos(x.to_f64().unwrap_or(f64::NAN)));
+    #[cfg(not(feature = "deterministic"))]
+    return x.cos();
+}
+
+/// Computes `2^x`, from `deterministic` with the `deterministic` feature.
+#[inline(always)]
+fn exp2<T: Float>(x: T) -> T {
+    #[cfg(feature = "deterministic")]
+    return k(::deterministic::exp2(x.to_f64().unwrap_or(f64::NAN)));
+    #[cfg(not(feature = "deterministic"))]
+    return k::<T>(2.0).powf(x);
+}
+
+#[inline(always)]
+fn pi_2<T: Float + FloatConst>() -> T {
+    T::PI() * k(2.0)
+}
+
+/// Implemented for every `num_traits::Float` that also provides `FloatConst`,
+/// which covers `f32`, `f64` and third party scalar types.
+impl<T> Ease for T
+    where T: Float + FloatConst
+{
+    fn calc(self, f: EaseFunction) -> Self {
+        match f {
+            EaseFunction::QuadraticIn => self.quadratic_in(),
+            EaseFunction::QuadraticOut => self.quadratic_out(),
+            EaseFunction::QuadraticInOut => self.quadratic_in_out(),
+
+            EaseFunction::CubicIn => self.cubic_in(),
+            EaseFunction::CubicOut => self.cubic_out(),
+            EaseFunction::CubicInOut => self.cubic_in_out(),
+
+            EaseFunction::QuarticIn => self.quartic_in(),
+            EaseFunction::QuarticOut => self.quartic_out(),
+            EaseFunction::QuarticInOut => self.quartic_in_out(),
+
+            EaseFunction::QuinticIn => self.quintic_in(),
+            EaseFunction::QuinticOut => self.quintic_out(),
+            EaseFunction::QuinticInOut => self.quintic_in_out(),
+
+            EaseFunction::SineIn => self.sine_in(),
+            EaseFunction::SineOut => self.sine_out(),
+            EaseFunction::SineInOut => self.sine_in_out(),
+
+            EaseFunction::CircularIn => self.circular_in(),
+            EaseFunction::CircularOut => self.circular_out(),
+            EaseFunction::CircularInOut => self.circular_in_out(),
+
+            EaseFunction::ExponentialIn => self.exponential_in(),
+            EaseFunction::ExponentialOut => self.exponential_out(),
+            EaseFunction::ExponentialInOut => self.exponential_in_out(),
+
+            EaseFunction::ElasticIn => self.elastic_in(),
+            EaseFunction::ElasticOut => self.elastic_out(),
+            EaseFunction::ElasticInOut => self.elastic_in_out(),
+
+            EaseFunction::BackIn => self.back_in(),
+            EaseFunction::BackOut => self.back_out(),
+            EaseFunction::BackInOut => self.back_in_out(),
+
+            EaseFunction::BounceIn => self.bounce_in(),
+            EaseFunction::BounceOut => self.bounce_out(),
+            EaseFunction::BounceInOut => self.bounce_in_out(),
+        }
+    }
+
+    fn quadratic_in(self) -> Self {
+        let p = clamp(self);
+        p * p
+    }
+
+    fn quadratic_out(self) -> Self {
+        let p = clamp(self);
+        -(p * (p - k(2.0)))
+    }
+
+    fn quadratic_in_out(self) -> Self {
+        let p = clamp(self);
+        if p < k(0.5) {
+            k::<T>(2.0) * p * p
+        } else {
+            mul_add(k::<T>(-2.0) * p, p, k::<T>(4.0) * p) - T::one()
+        }
+    }
+
+
+    fn cubic_in(self) -> Self {
+        let p = clamp(self);
+        p * p * p
+    }
+
+    fn cubic_out(self) -> Self {
+        let p = clamp(self);
+        let f = p - T::one();
+        mul_add(f * f, f, T::one())
+    }
+
+    fn cubic_in_out(self) -> Self {
+        let p = clamp(self);
+        if p < k(0.5) {
+            k::<T>(4.0) * p * p * p
+        } else {
+            let f = (k::<T>(2.0) * p) - k(2.0);
+            mul_add(k::<T>(0.5) * f * f, f, T::one())
+        }
+    }
+
+
+    fn quartic_in(self) -> Self {
+        let p = clamp(self);
+        p * p * p * p
+    }
+
+    fn quartic_out(self) -> Self {
+        let p = clamp(self);
+        let f = p - T::one();
+        mul_add(f * f * f, T::one() - p, T::one())
+    }
+
+    fn quartic_in_out(self) -> Self {
+        let p = clamp(self);
+        if p < k(0.5) {
+            k::<T>(8.0) * p * p * p * p
+        } else {
+            let f = p - T::one();
+            mul_add(k::<T>(-8.0) * f * f * f, f, T::one())
+        }
+    }
+
+
+    fn quintic_in(self) -> Self {
+        let p = clamp(self);
+        p * p * p * p * p
+    }
+
+    fn quintic_out(self) -> Self {
+        let p = clamp(self);
+        let f = p - T::one();
+        mul_add(f * f * f * f, f, T::one())
+    }
+
+    fn quintic_in_out(self) -> Self {
+        let p = clamp(self);
+        if p < k(0.5) {
+            k::<T>(16.0) * p * p * p * p * p
+        } else {
+            let f = (k::<T>(2.0) * p) - k(2.0);
+            mul_add(k::<T>(0.5) * f * f * f * f, f, T::one())
+        }
+    }
+
+
+    fn sine_in(self) -> Self {
+        let p = clamp(self);
+        sin((p - T::one()) * pi_2()) + T::one()
+    }
+
+    fn sine_out(self) -> Self {
+        let p = clamp(self);
+        sin(p * pi_2())
+    }
+
+    fn sine_in_out(self) -> Self {
+        let p = clamp(self);
+        k::<T>(0.5) * (T::one() - cos(p * T::PI()))
+    }
+
+
+    fn circular_in(self) -> Self {
+        let p = clamp(self);
+        T::one() - (T::one() - (p * p)).sqrt()
+    }
+
+    fn circular_out(self) -> Self {
+        let p = clamp(self);
+        ((k::<T>(2.0) - p) * p).sqrt()
+    }
+
+    fn circular_in_out(self) -> Self {
+        let p = clamp(self);
+        if p < k(0.5) {
+            k::<T>(0.5) * (T::one() - (T::one() - k::<T>(4.0) * (p * p)).sqrt())
+        } else {
+            k::<T>(0.5) * ((-((k::<T>(2.0) * p) - k(3.0)) * ((k::<T>(2.0) * p) - T::one())).sqrt() + T::one())
+        }
+    }
+
+
+    fn exponential_in(self) -> Self {
+        let p = clamp(self);
+        if p <= T::zero() {
+            T::zero()
+        } else {
+            exp2(k::<T>(10.0) * (p - T::one()))
+        }
+    }
+
+    fn exponential_out(self) -> Self {
+        let p = clamp(self);
+        if p >= T::one() {
+            T::one()
+        } else {
+            T::one() - exp2(k::<T>(-10.0) * p)
+        }
+    }
+
+    fn exponential_in_out(self) -> Self {
+        check(self);
+        if self <= T::zero() {
+            return T::zero();
+        }
+        if self >= T::one() {
+            return T::one();
+        }
+
+        if self < k(0.5) {
+            k::<T>(0.5) * exp2((k::<T>(20.0) * self) - k(10.0))
+        } else {
+            k::<T>(-0.5) * exp2((k::<T>(-20.0) * self) + k(10.0)) + T::one()
+        }
+    }
+
+
+    fn elastic_in(self) -> Self {
+        let p = clamp(self);
+        sin(k::<T>(13.0) * pi_2() * p) * exp2(k::<T>(10.0) * (p - T::one()))
+    }
+
+    fn elastic_out(self) -> Self {
+        let p = clamp(self);
+        sin(k::<T>(-13.0) * pi_2() * (p + T::one())) * exp2(k::<T>(-10.0) * p) + T::one()
+    }
+
+    fn elastic_in_out(self) -> Self {
+        let p = clamp(self);
+        let two = k::<T>(2.0);
+        if p < k(0.5) {
+            k::<T>(0.5) * sin(k::<T>(13.0) * pi_2() * (two * p)) * exp2(k::<T>(10.0) * ((two * p) - T::one()))
+        } else {
+            k::<T>(0.5) * (sin(k::<T>(-13.0) * pi_2() * ((two * p - T::one()) + T::one()))
+                           * exp2(k::<T>(-10.0) * (two * p - T::one())) + two)
+        }
+    }
+
+
+    fn back_in(self) -> Self {
+        let p = clamp(self);
+        mul_add(p * p, p, -(p * sin(p * T::PI())))
+    }
+
+    fn back_out(self) -> Self {
+        let p = clamp(self);
+        let f = T::one() - p;
+        T::one() - mul_add(f * f, f, -(f * sin(f * T::PI())))
+    }
+
+    fn back_in_out(self) -> Self {
+        let p = clamp(self);
+        let two = k::<T>(2.0);
+        let half = k::<T>(0.5);
+        if p < half {
+            let f = two * p;
+            half * mul_add(f * f, f, -(f * sin(f * T::PI())))
+        } else {
+            let f = T::one() - (two * p - T::one());
+            half * (T::one() - mul_add(f * f, f, -(f * sin(f * T::PI())))) + half
+        }
+    }
+
+
+    fn bounce_in(self) -> Self {
+        let p = clamp(self);
+        T::one() - Ease::bounce_out(T::one() - p)
+    }
+
+    fn bounce_out(self) -> Self {
+        // Around the lowest points, the parabolas have no cancellation
+        // and stay within about one f32 ULP, where expanded they lost up to 27.
+        let p = clamp(self);
+        if p < k(bounce::T1) {
+            k::<T>(bounce::A1) * p * p
+        } else if p < k(bounce::T2) {
+            let d = p - k(bounce::H2);
+            mul_add(k::<T>(bounce::A2) * d, d, k(bounce::M2))
+        } else if p < k(bounce::T3) {
+            let d = p - k(bounce::H3);
+            mul_add(k::<T>(bounce::A3) * d, d, k(bounce::M3))
+        } else {
+            let q = T::one() - p;
+            mul_add(mul_add(k(bounce::A4), q, -k::<T>(bounce::B4)), q, T::one())
+        }
+    }
+
+    fn bounce_in_out(self) -> Self {
+        let p = clamp(self);
+        let half = k::<T>(0.5);
+        if p < half {
+            half * Ease::bounce_in(p * k(2.0))
+        } else {
+            half * Ease::bounce_out(p * k(2.0) - T::one()) + half
+        }
+    }
+}
+
+#[cfg(not(all(feature = "check-finite", debug_assertions)))]
+#[test]
+fn ease_non_finite() {
+    for &f in EaseFunction::ALL.iter() {
+        assert!(f32::NAN.calc(f).is_nan(), "{:?}", f);
+        assert!(f64::NAN.calc(f).is_nan(), "{:?}", f);
+        assert_eq!(f32::INFINITY.calc(f), 1.0f32.calc(f), "{:?}", f);
+        assert_eq!(f64::INFINITY.calc(f), 1.0f64.calc(f), "{:?}", f);
+        assert_eq!(f32::NEG_INFINITY.calc(f), 0.0f32.calc(f), "{:?}", f);
+        assert_eq!(f64::NEG_INFINITY.calc(f), 0.0f64.calc(f), "{:?}", f);
+    }
+
+    let bezier = Easing::CubicBezier(CubicBezierEase::new(0.42, 0.0, 0.58, 1.0));
+    assert!(bezier.calc(f64::NAN).is_nan());
+    assert_eq!(bezier.calc(f64::INFINITY), 1.0);
+    assert_eq!(bezier.calc(f64::NEG_INFINITY), 0.0);
+    let steps = Easing::Steps(4, StepPosition::End);
+    assert!(steps.calc(f64::NAN).is_nan());
+    assert_eq!(steps.calc(f64::INFINITY), f64::INFINITY);
+    assert!(Easing::Linear.calc(f32::NAN).is_nan());
+    assert_eq!(Easing::Linear.calc(f32::NEG_INFINITY), f32::NEG_INFINITY);
+}
+
+#[cfg(all(feature = "check-finite", debug_assertions))]
+#[test]
+#[should_panic]
+fn ease_check_finite() {
+    let _ = f32::NAN.calc(EaseFunction::QuadraticIn);
+}
+
+// The bit patterns are for separate multiplication and addition.
+#[cfg(not(feature = "fma"))]
+#[test]
+fn bounce_golden() {
+    let ps = [0.0f64, 0.1, 0.3, 0.36, 0.5, 0.7, 0.75, 0.85, 0.95, 1.0];
+    let golden_f32: [[u32; 10]; 3] = [
+        [0x00000000, 0x00000000, 0x3daa7f08, 0x3e602218, 0x3e900000,
+         0x3ea3851c, 0x3f070000, 0x3f5470a5, 0x3f7b28f6, 0x3f800000],
+        [0x00000000, 0x3d9ae148, 0x3f2e3d72, 0x3f7ae7d7, 0x3f380000,
+         0x3f6ab01f, 0x3f757821, 0x3f6d0b6f, 0x3f791687, 0x3f800000],
+        [0x00000000, 0x3d33b988, 0x3d5d2f10, 0x3e506f6c, 0x3f000000,
+         0x3f722d0f, 0x3f5c0000, 0x3f755811, 0x3f800000, 0x3f800000],
+    ];
+    let golden_f64: [[u64; 10]; 3] = [
+        [0x0000000000000000, 0x0000000000000000, 0x3fb54fdf3b645a20, 0x3fcc044284dfce30,
+         0x3fd2000000000002, 0x3fd470a3d70a3d6e, 0x3fe0e00000000000, 0x3fea8e147ae147ae,
+         0x3fef651eb851eb85, 0x3ff0000000000000],
+        [0x0000000000000000, 0x3fb35c28f5c28f5d, 0x3fe5c7ae147ae147, 0x3fef5cfaacd9e83d,
+         0x3fe6ffffffffffff, 0x3fed5604189374bc, 0x3feeaf041ceed6ba, 0x3feda16dcdade8e9,
+         0x3fef22d0e5604189, 0x3ff0000000000000],
+        [0x0000000000000000, 0x3fa67731456926c8, 0x3faba5e353f7cee8, 0x3fca0ded288ce702,
+         0x3fe0000000000000, 0x3fee45a1cac08313, 0x3feb800000000000, 0x3feeab020c49ba5e,
+         0x3ff0000000000000, 0x3ff0000000000000],
+    ];
+    let fs = [EaseFunction::BounceIn, EaseFunction::BounceOut, EaseFunction::BounceInOut];
+    for (i, &f) in fs.iter().enumerate() {
+        for (j, &p) in ps.iter().enumerate() {
+            assert_eq!((p as f32).calc(f).to_bits(), golden_f32[i][j], "{:?} {}", f, p);
+            assert_eq!(p.calc(f).to_bits(), golden_f64[i][j], "{:?} {}", f, p);
+        }
+    }
+}
+
+// Checksums of the bit patterns at 4097 evenly spaced inputs from 0 to 1,
+// which pin the values of the bounce parabolas around their lowest points.
+#[test]
+fn bounce_pinned() {
+    #[cfg(not(feature = "fma"))]
+    let pinned: [(u64, u64); 3] = [
+        (0x92ecfc476b7d6814, 0x33e13e786c6f4bd8),
+        (0x9d3978ad47d3fa4d, 0x36725717cd83ab9d),
+        (0x193fa58b6b24a121, 0x88583fdf1dba58e1),
+    ];
+    #[cfg(feature = "fma")]
+    let pinned: [(u64, u64); 3] = [
+        (0x30df13f6380145b2, 0xce6f4c38aa18f5e0),
+        (0x492f9eccf7640970, 0xbc2ef0c98f9d2165),
+        (0x93a89c0722260a25, 0x5ba2ade1b9459fa5),
+    ];
+    let checksum = |bits: &mut dyn Iterator<Item = u64>| {
+        bits.fold(0u64, |sum, x| sum.wrapping_mul(31).wrapping_add(x))
+    };
+    let fs = [EaseFunction::BounceIn, EaseFunction::BounceOut, EaseFunction::BounceInOut];
+    for (&f, &(a, b)) in fs.iter().zip(&pinned) {
+        assert_eq!(checksum(&mut (0..=4096).map(|i| u64::from((i as f32 / 4096.0).calc(f).to_bits()))), a, "{:?}", f);
+        assert_eq!(checksum(&mut (0..=4096).map(|i| (i as f64 / 4096.0).calc(f).to_bits())), b, "{:?}", f);
+    }
+}
+
+#[test]
+fn ease_function_calc_concrete() {
+    for &f in EaseFunction::ALL.iter() {
+        for i in 0..=100 {
+            let p = i as f64 / 100.0;
+            assert_eq!(f.calc_f32(p as f32).to_bits(), (p as f32).calc(f).to_bits());
+            assert_eq!(f.calc_f64(p).to_bits(), p.calc(f).to_bits());
+        }
+    }
+}
+
+#[test]
+fn ease_calc_wide() {
+    for &f in EaseFunction::ALL.iter() {
+        let (mut narrow, mut wide) = (0.0f64, 0.0f64);
+        for i in 0..=1000 {
+            let p = i as f32 / 1000.0;
+            let exact = (p as f64).calc(f);
+            assert_eq!(f.calc_f32_wide(p), exact as f32);
+            assert_eq!(Easing::Function(f).calc_wide(p), exact as f32);
+            narrow = narrow.max((p.calc(f) as f64 - exact).abs());
+            wide = wide.max((f.calc_f32_wide(p) as f64 - exact).abs());
+        }
+        assert!(wide <= narrow, "{:?}", f);
+    }
+}
+
+#[cfg(feature = "fma")]
+#[test]
+fn ease_fma_accuracy() {
+    for &f in EaseFunction::ALL.iter() {
+        for i in 0..=1000 {
+            let p = i as f32 / 1000.0;
+            let exact = (p as f64).calc(f);
+            assert!((p.calc(f) as f64 - exact).abs() < 1e-5, "{:?} {}", f, p);
         }
-        impl Ease for $T {
-            fn calc(self, f: EaseFunction) -> Self {
-                match f {
-                    EaseFunction::QuadraticIn => self.quadratic_in(),
-                    EaseFunction::QuadraticOut => self.quadratic_out(),
-                    EaseFunction::QuadraticInOut => self.quadratic_in_out(),
-
-                    EaseFunction::CubicIn => self.cubic_in(),
-                    EaseFunction::CubicOut => self.cubic_out(),
-                    EaseFunction::CubicInOut => self.cubic_in_out(),
-
-                    EaseFunction::QuarticIn => self.quartic_in(),
-                    EaseFunction::QuarticOut => self.quartic_out(),
-                    EaseFunction::QuarticInOut => self.quartic_in_out(),
-
-                    EaseFunction::QuinticIn => self.quintic_in(),
-                    EaseFunction::QuinticOut => self.quintic_out(),
-                    EaseFunction::QuinticInOut => self.quintic_in_out(),
-
-                    EaseFunction::SineIn => self.sine_in(),
-                    EaseFunction::SineOut => self.sine_out(),
-                    EaseFunction::SineInOut => self.sine_in_out(),
-
-                    EaseFunction::CircularIn => self.circular_in(),
-                    EaseFunction::CircularOut => self.circular_out(),
-                    EaseFunction::CircularInOut => self.circular_in_out(),
-
-                    EaseFunction::ExponentialIn => self.exponential_in(),
-                    EaseFunction::ExponentialOut => self.exponential_out(),
-                    EaseFunction::ExponentialInOut => self.exponential_in_out(),
-
-                    EaseFunction::ElasticIn => self.elastic_in(),
-                    EaseFunction::ElasticOut => self.elastic_out(),
-                    EaseFunction::ElasticInOut => self.elastic_in_out(),
-
-                    EaseFunction::BackIn => self.back_in(),
-                    EaseFunction::BackOut => self.back_out(),
-                    EaseFunction::BackInOut => self.back_in_out(),
-
-                    EaseFunction::BounceIn => self.bounce_in(),
-                    EaseFunction::BounceOut => self.bounce_out(),
-                    EaseFunction::BounceInOut => self.bounce_in_out(),
-                }
-            }
-
-            fn quadratic_in(self) -> Self {
-                let p = $T::clamp(self);
-                p * p
-            }
-
-            fn quadratic_out(self) -> Self {
-                let p = $T::clamp(self);
-                -(p * (p - 2.0))
-            }
-
-            fn quadratic_in_out(self) -> Self {
-                let p = $T::clamp(self);
-                if p < 0.5 {
-                    2.0 * p * p
-                } else {
-                    (-2.0 * p * p) + (4.0 * p) - 1.0
-                }
-            }
-
-
-            fn cubic_in(self) -> Self {
-                let p = $T::clamp(self);
-                p * p * p
-            }
-
-            fn cubic_out(self) -> Self {
-                let p = $T::clamp(self);
-                let f = p - 1.0;
-                f * f * f + 1.0
-            }
-
-            fn cubic_in_out(self) -> Self {
-                let p = $T::clamp(self);
-                if p < 0.5 {
-                    4.0 * p * p * p
-                } else {
-                    let f = (2.0 * p) - 2.0;
-                    0.5 * f * f * f + 1.0
-                }
-            }
-
-
-            fn quartic_in(self) -> Self {
-                let p = $T::clamp(self);
-                p * p * p * p
-            }
-
-            fn quartic_out(self) -> Self {
-                let p = $T::clamp(self);
-                let f = p - 1.0;
-                f * f * f * (1.0 - p) + 1.0
-            }
-
-            fn quartic_in_out(self) -> Self {
-                let p = $T::clamp(self);
-                if p < 0.5 {
-                    8.0 * p * p * p * p
-                } else {
-                    let f = p - 1.0;
-                    -8.0 * f * f * f * f + 1.0
-                }
-            }
-
-
-            fn quintic_in(self) -> Self {
-                let p = $T::clamp(self);
-                p * p * p * p * p
-            }
-
-            fn quintic_out(self) -> Self {
-                let p = $T::clamp(self);
-                let f = p - 1.0;
-                f * f * f * f * f + 1.0
-            }
-
-            fn quintic_in_out(self) -> Self {
-                let p = $T::clamp(self);
-                if p < 0.5  {
-                    16.0 * p * p * p * p * p
-                } else {
-                    let f = (2.0 * p) - 2.0;
-                    0.5 * f * f * f * f * f + 1.0
-                }
-            }
-
-
-            fn sine_in(self) -> Self {
-                use self::$T::PI_2;
-                let p = $T::clamp(self);
-                ((p - 1.0) * PI_2).sin() + 1.0
-            }
-
-            fn sine_out(self) -> Self {
-                use self::$T::PI_2;
-                let p = $T::clamp(self);
-                (p * PI_2).sin()
-            }
-
-            fn sine_in_out(self) -> Self {
-                use std::$T::consts::PI;
-                let p = $T::clamp(self);
-                0.5 * (1.0 - (p * PI).cos())
-            }
-
-
-            fn circular_in(self) -> Self {
-                let p = $T::clamp(self);
-                1.0 - (1.0 - (p * p)).sqrt()
-            }
-
-            fn circular_out(self) -> Self {
-                let p = $T::clamp(self);
-                ((2.0 - p) * p).sqrt()
-            }
-
-            fn circular_in_out(self) -> Self {
-                let p = $T::clamp(self);
-                if p < 0.5 {
-                    0.5 * (1.0 - (1.0 - 4.0 * (p * p)).sqrt())
-                } else {
-                    0.5 * ((-((2.0 * p) - 3.0) * ((2.0 * p) - 1.0)).sqrt() + 1.0)
-                }
-            }
-
-
-            fn exponential_in(self) -> Self {
-                if self <= 0.0 {
-                    0.0
-                } else {
-                    (2.0 as $T).powf(10.0 * (self.min(1.0) - 1.0))
-                }
-            }
-
-            fn exponential_out(self) -> Self {
-                if self >= 1.0 {
-                    1.0
-                } else {
-                    1.0 - (2.0 as $T).powf(-10.0 * self.max(0.0))
-                }
-            }
-
-            fn exponential_in_out(self) -> Self {
-                if self <= 0.0 {
-                    return 0.0;
-                }
-                if self >= 1.0 {
-                    return 1.0;
-                }
-
-                if self < 0.5  {
-                    0.5 * (2.0 as $T).powf((20.0 * self) - 10.0)
-                } else {
-                    -0.5 * (2.0 as $T).powf((-20.0 * self) + 10.0) + 1.0
-                }
-            }
-
-
-            fn elastic_in(self) -> Self {
-                use self::$T::PI_2;
-                let p = $T::clamp(self);
-                (13.0 * PI_2 * p).sin() * (2.0 as $T).powf(10.0 * (p - 1.0))
-            }
-
-            fn elastic_out(self) -> Self {
-                use self::$T::PI_2;
-                let p = $T::clamp(self);
-                (-13.0 * PI_2 * (p + 1.0)).sin() * (2.0 as $T).powf(-10.0 * p) + 1.0
-            }
-
-            fn elastic_in_out(self) -> Self {
-                use self::$T::PI_2;
-                let p = $T::clamp(self);
-                if p < 0.5 {
-                    0.5 * (13.0 * PI_2 * (2.0 * p)).sin() * (2.0 as $T).powf(10.0 * ((2.0 * p) - 1.0))
-                } else {
-                    0.5 * ((-13.0 * PI_2 * ((2.0 * p - 1.0) + 1.0)).sin()
-                           * (2.0 as $T).powf(-10.0 * (2.0 * p - 1.0)) + 2.0)
-                }
-            }
-
-
-            fn back_in(self) -> Self {
-                use std::$T::consts::PI;
-                let p = $T::clamp(self);
-                p * p * p - p * (p * PI).sin()
-            }
-
-            fn back_out(self) -> Self {
-                use std::$T::consts::PI;
-                let p = $T::clamp(self);
-                let f = 1.0 - p;
-                1.0 - (f * f * f - f * (f * PI).sin())
-            }
-
-            fn back_in_out(self) -> Self {
-                use std::$T::consts::PI;
-                let p = $T::clamp(self);
-                if p < 0.5 {
-                    let f = 2.0 * p;
-                    0.5 * (f * f * f - f * (f * PI).sin())
-                } else {
-                    let f = 1.0 - (2.0 * p - 1.0);
-                    0.5 * (1.0 - (f * f * f - f * (f * PI).sin())) + 0.5
-                }
-            }
-
-
-            fn bounce_in(self) -> Self {
-                let p = $T::clamp(self);
-                1.0 - Ease::bounce_out(1.0 - p)
-            }
-
-            fn bounce_out(self) -> Self {
-                let p = $T::clamp(self);
-                if p < 4.0 / 11.0 {
-                    (121.0 * p * p) / 16.0
-                } else if p < 8.0 / 11.0 {
-                    (363.0 / 40.0 * p * p) - (99.0 / 10.0 * p) + 17.0 / 5.0
-                } else if p < 9.0 / 10.0 {
-                    (4356.0 / 361.0 * p * p) - (35442.0 / 1805.0 * p) + 16061.0 / 1805.0
-                } else {
-                    (54.0 / 5.0 * p * p) - (513.0 / 25.0 * p) + 268.0 / 25.0
-                }
-            }
-
-            fn bounce_in_out(self) -> Self {
-                let p = $T::clamp(self);
-                if p < 0.5 {
-                    0.5 * Ease::bounce_in(p * 2.0)
-                } else {
-                    0.5 * Ease::bounce_out(p * 2.0 - 1.0) + 0.5
-                }
-            }
+    }
+}
+
+#[test]
+fn ease_function_from_name() {
+    assert_eq!(EaseFunction::from_name("CubicInOut"), Some(EaseFunction::CubicInOut));
+    assert_eq!(EaseFunction::from_name("cubicInOut"), Some(EaseFunction::CubicInOut));
+    assert_eq!(EaseFunction::from_name("bounce_out"), Some(EaseFunction::BounceOut));
+    assert_eq!(EaseFunction::from_name("ease-in"), None);
+}
+
+#[test]
+fn bounce_out_accuracy() {
+    // The expanded polynomials, evaluated in f64 as a reference for f32.
+    fn expanded(p: f64) -> f64 {
+        if p < 4.0 / 11.0 {
+            (121.0 * p * p) / 16.0
+        } else if p < 8.0 / 11.0 {
+            (363.0 / 40.0 * p * p) - (99.0 / 10.0 * p) + 17.0 / 5.0
+        } else if p < 9.0 / 10.0 {
+            (4356.0 / 361.0 * p * p) - (35442.0 / 1805.0 * p) + 16061.0 / 1805.0
+        } else {
+            (54.0 / 5.0 * p * p) - (513.0 / 25.0 * p) + 268.0 / 25.0
         }
-    )
+    }
+    for i in 0..=10_000 {
+        let p = i as f32 / 10_000.0;
+        let exact = expanded(p as f64);
+        let ulp = (exact as f32).abs().max(f32::MIN_POSITIVE) as f64 * f32::EPSILON as f64;
+        assert!((p.bounce_out() as f64 - exact).abs() <= 1.5 * ulp, "{}", p);
+    }
+    // The ends are exact.
+    for &f in &[EaseFunction::BounceIn, EaseFunction::BounceOut, EaseFunction::BounceInOut] {
+        assert_eq!((0.0f32.calc(f), 1.0f32.calc(f)), (0.0, 1.0));
+        assert_eq!((0.0f64.calc(f), 1.0f64.calc(f)), (0.0, 1.0));
+    }
+}
+
+#[cfg(feature = "deterministic")]
+#[test]
+fn deterministic_golden() {
+    use self::EaseFunction::*;
+
+    // Bit patterns at 0.1, 0.3 and 0.7, which must be the same on every platform.
+    let golden: [(EaseFunction, [u64; 3], [u32; 3]); 5] = [
+        (SineIn, [0x3ff96791823aad30, 0x3fff378709a22a80, 0x3fa90f1ecbbab010], [0x3fcb3c8c, 0x3ff9bc38, 0x3d487900]),
+        (SineInOut, [0x3f990f1ecbbab010, 0x3fca61b9f7154b42, 0x3fe96791823aad2e], [0x3cc878f0, 0x3e530dd2, 0x3f4b3c8c]),
+        (ExponentialInOut, [0x3f60000000000000, 0x3fa0000000000000, 0x3fef000000000000], [0x3b000000, 0x3d000000, 0x3f780000]),
+        (ElasticOut, [0x3fe0c878f65dd58a, 0x3ff12cf2304755a0, 0x3fefda61b9f7154b], [0x3f0643d8, 0x3f896794, 0x3f7ed30d]),
+        (BackInOut, [0xbfac0bee4cc3f4f0, 0xbfc6b2526d22e4fa, 0x3ff2d64a4da45c9f], [0xbd605f72, 0xbe359292, 0x3f96b252]),
+    ];
+    for &(f, a, b) in &golden {
+        for (i, &p) in [0.1f64, 0.3, 0.7].iter().enumerate() {
+            assert_eq!(p.calc(f).to_bits(), a[i], "{:?} {}", f, p);
+            assert_eq!((p as f32).calc(f).to_bits(), b[i], "{:?} {}", f, p);
+        }
+    }
+}
+
+#[test]
+fn ease_calc_between() {
+    let f = EaseFunction::QuadraticIn;
+    assert_eq!(f.calc_between(&20.0f32, &340.0, 0.5), 100.0);
+    assert_eq!(f.calc_between(&[0.0f64, 10.0], &[4.0, 2.0], 1.0), [4.0, 2.0]);
+    assert_eq!(f.calc_between(&0u8, &200, 0.5), 50);
+    assert_eq!(Easing::Linear.calc_between(&20.0f32, &340.0, 0.25), 100.0);
+    assert_eq!(Easing::Steps(2, StepPosition::End).calc_between(&0.0f64, &8.0, 0.75), 4.0);
 }
 
-impl_ease_trait_for!(f32);
-impl_ease_trait_for!(f64);
@@ -0,0 +1,365 @@
+//! Elliptical arcs between two points, as in SVG paths
+//!
+//! An SVG arc command gives the end points, the radii and rotation of the ellipse,
+//! and two flags that choose one of the four arcs that fit.
+//! `EllipticalArc` converts this to the center of the ellipse and the angles of the arc,
+//! following the implementation notes of the SVG specification,
+//! and samples the arc or converts it to cubic Bézier curves.
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+#[cfg(feature = "alloc")]
+use bezier::CubicBezier;
+#[cfg(feature = "alloc")]
+use conic::quarter_turns;
+use arc_length::ParametricCurve;
+use float::Float;
+
+/// An arc of an ellipse, parameterized by angle from the start to the end.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct EllipticalArc<S> {
+    from: [S; 2],
+    to: [S; 2],
+    center: [S; 2],
+    radii: [S; 2],
+    rotation: S,
+    start_angle: S,
+    sweep_angle: S,
+}
+
+impl<S: Float> EllipticalArc<S> {
+    /// Creates the arc from `from` to `to` on an ellipse with `radii`,
+    /// whose first axis is rotated by `rotation` in radians.
+    ///
+    /// Of the arcs that fit, `large_arc` picks one of more than half a turn,
+    /// and `sweep` one that runs towards increasing angles.
+    /// Radii that are too small to reach from one point to the other are scaled up.
+    /// As in SVG, a zero radius gives a straight line, as does an infinite or NaN radius,
+    /// and equal end points give no arc at all, but the point.
+    pub fn new(from: [S; 2], to: [S; 2], radii: [S; 2], rotation: S, large_arc: bool, sweep: bool)
+               -> EllipticalArc<S> {
+        let (zero, one, two) = (S::zero(), S::one(), S::from_f64(2.0));
+        let abs = |x: S| if x < zero { -x } else { x };
+        let ([x1, y1], [x2, y2]) = (from, to);
+        let [rx, ry] = radii;
+        let (mut rx, mut ry) = (abs(rx), abs(ry));
+        let line = EllipticalArc {
+            from, to,
+            center: [(x1 + x2) / two, (y1 + y2) / two],
+            radii: [zero, zero],
+            rotation, start_angle: zero, sweep_angle: zero,
+        };
+        let positive = |x: S| x.partial_cmp(&zero) == Some(Ordering::Greater) && x.to_f64().is_finite();
+        if from == to || !positive(rx) || !positive(ry) {
+            return line;
+        }
+        // The end points in the frame of the ellipse, around the middle between them.
+        let (cos, sin) = (rotation.cos(), rotation.sin());
+        let (dx, dy) = ((x1 - x2) / two, (y1 - y2) / two);
+        let (x, y) = (cos * dx + sin * dy, cos * dy - sin * dx);
+        let scale = x * x / (rx * rx) + y * y / (ry * ry);
+        if scale > one {
+            let root = scale.sqrt();
+            rx = rx * root;
+            ry = ry * root;
+        }
+        let (rx2, ry2) = (rx * rx, ry * ry);
+        let num = rx2 * ry2 - rx2 * y * y - ry2 * x * x;
+        let den = rx2 * y * y + ry2 * x * x;
+        let coef = if num > zero && den > zero { (num / den).sqrt() } else { zero };
+        let coef = if large_arc == sweep { -coef } else { coef };
+        let (cx, cy) = (coef * rx * y / ry, -coef * ry * x / rx);
+        let center = [cos * cx - sin * cy + (x1 + x2) / two, sin * cx + cos * cy + (y1 + y2) / two];
+        let start_angle = ((y - cy) / ry).atan2((x - cx) / rx);
+        let end_angle = ((-y - cy) / ry).atan2((-x - cx) / rx);
+        let turn = S::pi() * two;
+        let mut sweep_angle = end_angle - start_angle;
+        if sweep && sweep_angle < zero {
+            sweep_angle = sweep_angle + turn;
+        } else if !sweep && sweep_angle > zero {
+            sweep_angle = sweep_angle - turn;
+        }
+        EllipticalArc { from, to, center, radii: [rx, ry], rotation, start_angle, sweep_angle }
+    }
+
+    /// Creates the arc of the ellipse around `center` with `radii`,
+    /// whose first axis is rotated by `rotation`,
+    /// from `start_angle` through `sweep_angle`, all in radians.
+    pub fn from_center(center: [S; 2], radii: [S; 2], rotation: S, start_angle: S, sweep_angle: S)
+                       -> EllipticalArc<S> {
+        let mut arc = EllipticalArc {
+            from: center, to: center, center, radii, rotation, start_angle, sweep_angle,
+        };
+        arc.from = arc.point(start_angle);
+        arc.to = arc.point(start_angle + sweep_angle);
+        arc
+    }
+
+    /// Creates the arc of a circle with `radius` from `from` to `to`,
+    /// where `large_arc` and `sweep` pick one of the arcs that fit, as in `new`.
+    pub fn circular(from: [S; 2], to: [S; 2], radius: S, large_arc: bool, sweep: bool) -> EllipticalArc<S> {
+        EllipticalArc::new(from, to, [radius, radius], S::zero(), large_arc, sweep)
+    }
+
+    /// Creates the arc of a circle from `from` to `to` with a bulge factor,
+    /// as in the polylines of DXF files and many CAD tools.
+    ///
+    /// The bulge is the tangent of a quarter of the sweep angle,
+    /// which is positive towards increasing angles.
+    /// A bulge of zero gives a straight line, and one a half circle.
+    pub fn from_bulge(from: [S; 2], to: [S; 2], bulge: S) -> EllipticalArc<S> {
+        let ([x1, y1], [x2, y2]) = (from, to);
+        let chord = ((x2 - x1) * (x2 - x1) + (y2 - y1) * (y2 - y1)).sqrt();
+        let abs = if bulge < S::zero() { -bulge } else { bulge };
+        // The chord is `2 r sin(sweep / 2)`, and `sin(sweep / 2) = 2 b / (1 + b^2)`.
+        let radius = chord * (S::one() + bulge * bulge) / (S::from_f64(4.0) * abs);
+        EllipticalArc::circular(from, to, radius, abs > S::one(), bulge > S::zero())
+    }
+
+    /// Returns the start point.
+    pub fn from(&self) -> [S; 2] {
+        self.from
+    }
+
+    /// Returns the end point.
+    pub fn to(&self) -> [S; 2] {
+        self.to
+    }
+
+    /// Returns the center of the ellipse.
+    pub fn center(&self) -> [S; 2] {
+        self.center
+    }
+
+    /// Returns the radii of the ellipse, after scaling them up to fit,
+    /// or zero if the arc is a straight line.
+    pub fn radii(&self) -> [S; 2] {
+        self.radii
+    }
+
+    /// Returns the rotation of the first axis of the ellipse in radians.
+    pub fn rotation(&self) -> S {
+        self.rotation
+    }
+
+    /// Returns the angle of the start point on the ellipse in radians.
+    pub fn start_angle(&self) -> S {
+        self.start_angle
+    }
+
+    /// Returns the angle from the start to the end in radians,
+    /// which is positive towards increasing angles.
+    pub fn sweep_angle(&self) -> S {
+        self.sweep_angle
+    }
+
+    /// Returns `true` if the arc is a straight line, because of a zero radius or equal end points.
+    pub fn is_line(&self) -> bool {
+        let [rx, ry] = self.radii;
+        rx == S::zero() || ry == S::zero()
+    }
+
+    /// Returns the point at `t`, from the start at 0 to the end at 1,
+    /// at evenly spaced angles.
+    pub fn sample(&self, t: &S) -> [S; 2] {
+        if self.is_line() {
+            let ([x1, y1], [x2, y2]) = (self.from, self.to);
+            return [x1 + (x2 - x1) * *t, y1 + (y2 - y1) * *t];
+        }
+        self.point(self.start_angle + self.sweep_angle * *t)
+    }
+
+    /// Returns the point of the ellipse at `angle`.
+    fn point(&self, angle: S) -> [S; 2] {
+        let ([cx, cy], [rx, ry]) = (self.center, self.radii);
+        let (x, y) = (rx * angle.cos(), ry * angle.sin());
+        let (cos, sin) = (self.rotation.cos(), self.rotation.sin());
+        [cx + cos * x - sin * y, cy + sin * x + cos * y]
+    }
+
+    /// Returns the derivative of the point of the ellipse by `angle`.
+    fn derivative(&self, angle: S) -> [S; 2] {
+        let [rx, ry] = self.radii;
+        let (x, y) = (-rx * angle.sin(), ry * angle.cos());
+        let (cos, sin) = (self.rotation.cos(), self.rotation.sin());
+        [cos * x - sin * y, sin * x + cos * y]
+    }
+
+    /// Returns cubic Bézier curves of at most a quarter turn each, joined end to end,
+    /// that approximate the arc.
+    ///
+    /// The error is below `3e-4` times the larger radius.
+    /// A straight line gives one curve, and equal end points give none.
+    /// Sweeps of more than 1024 turns are reduced by whole turns to less than one turn.
+    #[cfg(feature = "alloc")]
+    pub fn to_cubics(&self) -> Vec<CubicBezier<[S; 2]>> {
+        let (one, three) = (S::one(), S::from_f64(3.0));
+        if self.is_line() {
+            if self.from == self.to {
+                return Vec::new();
+            }
+            let (third, two_thirds) = (self.sample(&(one / three)), self.sample(&(S::from_f64(2.0) / three)));
+            return vec![CubicBezier::new(self.from, third, two_thirds, self.to)];
+        }
+        let (sweep, n) = match quarter_turns(self.sweep_angle) {
+            Some(quarters) => quarters,
+            None => return Vec::new(),
+        };
+        let step = sweep / S::from_f64(n as f64);
+        // The handle length that puts the middle of each curve on the ellipse.
+        let quarter = step / S::from_f64(4.0);
+        let k = S::from_f64(4.0) / three * quarter.sin() / quarter.cos();
+        (0..n).map(|i| {
+            let a = self.start_angle + step * S::from_f64(i as f64);
+            let b = a + step;
+            let start = if i == 0 { self.from } else { self.point(a) };
+            let end = if i + 1 == n { self.to } else { self.point(b) };
+            let ([x0, y0], [x3, y3]) = (start, end);
+            let ([dx0, dy0], [dx3, dy3]) = (self.derivative(a), self.derivative(b));
+            CubicBezier::new(start, [x0 + dx0 * k, y0 + dy0 * k], [x3 - dx3 * k, y3 - dy3 * k], end)
+        }).collect()
+    }
+}
+
+impl<S: Float> ParametricCurve for EllipticalArc<S> {
+    type Point = [S; 2];
+    type Scalar = S;
+
+    #[inline(always)]
+    fn point_at(&self, t: S) -> [S; 2] {
+        self.sample(&t)
+    }
+
+    fn speed_at(&self, t: S) -> S {
+        let [dx, dy] = if self.is_line() {
+            let ([x1, y1], [x2, y2]) = (self.from, self.to);
+            [x2 - x1, y2 - y1]
+        } else {
+            let [dx, dy] = self.derivative(self.start_angle + self.sweep_angle * t);
+            [dx * self.sweep_angle, dy * self.sweep_angle]
+        };
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+#[cfg(test)]
+fn close(p: [f64; 2], q: [f64; 2]) -> bool {
+    (p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12
+}
+
+#[test]
+fn elliptical_arc_flags() {
+    use core::f64::consts::PI;
+
+    let arc = EllipticalArc::new([0.0f64, 0.0], [2.0, 0.0], [1.0, 1.0], 0.0, false, true);
+    assert!(close(arc.center(), [1.0, 0.0]));
+    assert!((arc.start_angle() - PI).abs() < 1e-12 && (arc.sweep_angle() - PI).abs() < 1e-12);
+    assert!(close(arc.sample(&0.5), [1.0, -1.0]));
+    let arc = EllipticalArc::new([0.0f64, 0.0], [2.0, 0.0], [1.0, 1.0], 0.0, false, false);
+    assert!(close(arc.sample(&0.5), [1.0, 1.0]));
+    // Radii that are too small are scaled up.
+    let arc = EllipticalArc::new([0.0f64, 0.0], [2.0, 0.0], [0.5, 0.5], 0.0, false, true);
+    assert!(close(arc.radii(), [1.0, 1.0]) && close(arc.sample(&0.5), [1.0, -1.0]));
+    // The four arcs between two points of two circles.
+    let small = EllipticalArc::new([1.0f64, 0.0], [0.0, 1.0], [1.0, 1.0], 0.0, false, true);
+    assert!(close(small.center(), [0.0, 0.0]) && (small.sweep_angle() - PI / 2.0).abs() < 1e-12);
+    let large = EllipticalArc::new([1.0f64, 0.0], [0.0, 1.0], [1.0, 1.0], 0.0, true, true);
+    assert!(close(large.center(), [1.0, 1.0]) && (large.sweep_angle() - 1.5 * PI).abs() < 1e-12);
+    let large = EllipticalArc::new([1.0f64, 0.0], [0.0, 1.0], [1.0, 1.0], 0.0, true, false);
+    assert!(close(large.center(), [0.0, 0.0]) && (large.sweep_angle() + 1.5 * PI).abs() < 1e-12);
+    assert!(close(large.sample(&0.0), [1.0, 0.0]) && close(large.sample(&1.0), [0.0, 1.0]));
+}
+
+#[test]
+fn elliptical_arc_rotated() {
+    use core::f64::consts::PI;
+
+    let rotation = PI / 6.0;
+    let arc = EllipticalArc::new([1.0f64, 2.0], [4.0, 3.0], [3.0, 1.0], rotation, true, false);
+    let (cos, sin) = (rotation.cos(), rotation.sin());
+    for i in 0..=16 {
+        let [x, y] = arc.sample(&(i as f64 / 16.0));
+        let [cx, cy] = arc.center();
+        let (u, v) = (cos * (x - cx) + sin * (y - cy), cos * (y - cy) - sin * (x - cx));
+        assert!(((u / 3.0).powi(2) + v.powi(2) - 1.0).abs() < 1e-12);
+    }
+    assert!(close(arc.sample(&0.0), [1.0, 2.0]) && close(arc.sample(&1.0), [4.0, 3.0]));
+    let same = EllipticalArc::from_center(arc.center(), arc.radii(), rotation, arc.start_angle(), arc.sweep_angle());
+    assert!(close(same.sample(&0.3), arc.sample(&0.3)));
+}
+
+#[test]
+fn elliptical_arc_degenerate() {
+    let line = EllipticalArc::new([0.0f64, 0.0], [2.0, 4.0], [0.0, 1.0], 0.0, false, true);
+    assert!(line.is_line());
+    assert_eq!(line.sample(&0.5), [1.0, 2.0]);
+    let point = EllipticalArc::new([1.0f64, 1.0], [1.0, 1.0], [1.0, 1.0], 0.0, false, true);
+    assert!(point.is_line());
+    assert_eq!(point.sample(&0.5), [1.0, 1.0]);
+    let arc = EllipticalArc::new([0.0f64, 0.0], [2.0, 0.0], [f64::NAN, 1.0], 0.0, false, true);
+    assert!(arc.is_line());
+}
+
+#[test]
+fn elliptical_arc_circular() {
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    let arc = EllipticalArc::circular([0.0f64, 0.0], [2.0, 0.0], 1.0, false, true);
+    assert_eq!(arc, EllipticalArc::new([0.0, 0.0], [2.0, 0.0], [1.0, 1.0], 0.0, false, true));
+    // Half circles, either way.
+    let arc = EllipticalArc::from_bulge([0.0f64, 0.0], [2.0, 0.0], 1.0);
+    assert!(close(arc.center(), [1.0, 0.0]) && (arc.sweep_angle() - PI).abs() < 1e-12);
+    assert!(close(arc.sample(&0.5), [1.0, -1.0]));
+    let arc = EllipticalArc::from_bulge([0.0f64, 0.0], [2.0, 0.0], -1.0);
+    assert!(close(arc.sample(&0.5), [1.0, 1.0]));
+    // Quarter and three quarter circles.
+    let bulge = (PI / 8.0).tan();
+    let arc = EllipticalArc::from_bulge([1.0f64, 0.0], [0.0, 1.0], bulge);
+    assert!(close(arc.center(), [0.0, 0.0]) && (arc.sweep_angle() - FRAC_PI_2).abs() < 1e-12);
+    let arc = EllipticalArc::from_bulge([1.0f64, 0.0], [0.0, 1.0], -1.0 / bulge);
+    assert!(close(arc.center(), [0.0, 0.0]) && (arc.sweep_angle() + 3.0 * FRAC_PI_2).abs() < 1e-12);
+    assert!((arc.radii()[0] - 1.0).abs() < 1e-12);
+    // A straight line.
+    let line = EllipticalArc::from_bulge([0.0f64, 0.0], [2.0, 0.0], 0.0);
+    assert!(line.is_line());
+    assert_eq!(line.sample(&0.25), [0.5, 0.0]);
+    assert!(EllipticalArc::from_bulge([0.0f64, 0.0], [2.0, 0.0], f64::NAN).is_line());
+    // Measured like other curves.
+    let arc = ::PreparedCurve::new(EllipticalArc::from_bulge([1.0f64, 0.0], [0.0, 1.0], -1.0 / bulge));
+    assert!((arc.length() - 3.0 * FRAC_PI_2).abs() < 1e-6);
+    assert!((::PreparedCurve::new(line).length() - 2.0).abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn elliptical_arc_to_cubics() {
+    let arc = EllipticalArc::new([1.0f64, 2.0], [4.0, 3.0], [3.0, 1.0], 0.5, true, false);
+    let cubics = arc.to_cubics();
+    let n = (arc.sweep_angle().abs() / core::f64::consts::FRAC_PI_2).ceil();
+    assert_eq!(cubics.len(), n as usize);
+    assert_eq!(cubics[0].points[0], arc.from());
+    assert_eq!(cubics.last().unwrap().points[3], arc.to());
+    assert!(cubics.windows(2).all(|w| close(w[0].points[3], w[1].points[0])));
+    // The curves stay on the ellipse.
+    let ([cx, cy], [rx, ry], (sin, cos)) = (arc.center(), arc.radii(), 0.5f64.sin_cos());
+    for cubic in &cubics {
+        for j in 0..=8 {
+            let [x, y] = cubic.sample(&(j as f64 / 8.0));
+            let (u, v) = (cos * (x - cx) + sin * (y - cy), cos * (y - cy) - sin * (x - cx));
+            assert!((((u / rx).powi(2) + (v / ry).powi(2)).sqrt() - 1.0).abs() < 3e-4);
+        }
+    }
+    let line = EllipticalArc::new([0.0f64, 0.0], [3.0, 0.0], [0.0, 0.0], 0.0, false, false);
+    assert_eq!(line.to_cubics(), vec![CubicBezier::new([0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 0.0])]);
+    assert!(EllipticalArc::new([1.0f64, 0.0], [1.0, 0.0], [1.0, 1.0], 0.0, false, false).to_cubics().is_empty());
+    // A huge sweep is reduced by whole turns instead of giving a curve per quarter turn.
+    let wound = EllipticalArc::from_center([0.0f64, 0.0], [2.0, 1.0], 0.3, 0.0, 1e12);
+    let cubics = wound.to_cubics();
+    assert!(!cubics.is_empty() && cubics.len() <= 4);
+    assert_eq!((cubics[0].points[0], cubics[cubics.len() - 1].points[3]), (wound.from(), wound.to()));
+}
@@ -0,0 +1,114 @@
+//! Errors of fallible constructors
+//!
+//! Constructors named `try_new` check their input and return an `InterpolationError`
+//! instead of building a value that evaluates to wrong or held output.
+//! The plain constructors stay infallible, so they can be used where the input
+//! is known to be valid.
+
+use core::fmt;
+
+/// An error for input that can not be interpolated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[non_exhaustive]
+pub enum InterpolationError {
+    /// There are no keys or control points.
+    Empty,
+    /// A list has `found` elements where `expected` are needed.
+    MismatchedLengths {
+        /// The number of elements needed.
+        expected: usize,
+        /// The number of elements given.
+        found: usize,
+    },
+    /// The key at `index` is not after the one before it,
+    /// the knot at `index` is before the one before it, or either is NaN.
+    NotIncreasing {
+        /// The index of the key or knot.
+        index: usize,
+    },
+    /// A parameter is out of its valid range.
+    InvalidParameter {
+        /// The name of the parameter.
+        name: &'static str,
+    },
+}
+
+/// The result of fallible constructors and evaluators.
+pub type Result<T> = ::core::result::Result<T, InterpolationError>;
+
+impl fmt::Display for InterpolationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InterpolationError::Empty => write!(f, "no keys or control points"),
+            InterpolationError::MismatchedLengths { expected, found } =>
+                write!(f, "expected {} elements, found {}", expected, found),
+            InterpolationError::NotIncreasing { index } =>
+                write!(f, "key {} is not after the previous key", index),
+            InterpolationError::InvalidParameter { name } =>
+                write!(f, "invalid parameter `{}`", name),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for InterpolationError {}
+
+/// Checks that `times` is not empty, has no NaN and is strictly increasing.
+#[cfg(feature = "alloc")]
+pub(crate) fn check_increasing<S: PartialOrd>(times: &[S]) -> Result<()> {
+    check_order(times, false)
+}
+
+/// Checks that `knots` is not empty, has no NaN and never decreases.
+#[cfg(feature = "alloc")]
+pub(crate) fn check_non_decreasing<S: PartialOrd>(knots: &[S]) -> Result<()> {
+    check_order(knots, true)
+}
+
+#[cfg(feature = "alloc")]
+fn check_order<S: PartialOrd>(values: &[S], allow_equal: bool) -> Result<()> {
+    use core::cmp::Ordering::{Equal, Less};
+
+    let mut prev = match values.first() {
+        Some(first) if first.partial_cmp(first).is_some() => first,
+        Some(_) => return Err(InterpolationError::NotIncreasing { index: 0 }),
+        None => return Err(InterpolationError::Empty),
+    };
+    for (i, v) in values.iter().enumerate().skip(1) {
+        match prev.partial_cmp(v) {
+            Some(Less) => {}
+            Some(Equal) if allow_equal => {}
+            _ => return Err(InterpolationError::NotIncreasing { index: i }),
+        }
+        prev = v;
+    }
+    Ok(())
+}
+
+/// Checks that a list has the expected length.
+#[cfg(feature = "alloc")]
+pub(crate) fn check_len(expected: usize, found: usize) -> Result<()> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(InterpolationError::MismatchedLengths { expected, found })
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn error_checks() {
+    use std::string::ToString;
+
+    assert_eq!(check_increasing::<f32>(&[]), Err(InterpolationError::Empty));
+    assert_eq!(check_increasing(&[0.0, 1.0, 1.0]), Err(InterpolationError::NotIncreasing { index: 2 }));
+    assert_eq!(check_increasing(&[f32::NAN]), Err(InterpolationError::NotIncreasing { index: 0 }));
+    assert_eq!(check_increasing(&[1.0f64]), Ok(()));
+    assert_eq!(check_increasing(&[0.0, f32::NAN]), Err(InterpolationError::NotIncreasing { index: 1 }));
+    assert_eq!(check_non_decreasing(&[0.0, 1.0, 1.0]), Ok(()));
+    assert_eq!(check_non_decreasing(&[0.0, 1.0, 0.5]), Err(InterpolationError::NotIncreasing { index: 2 }));
+    assert_eq!(check_non_decreasing(&[0.0, f64::NAN]), Err(InterpolationError::NotIncreasing { index: 1 }));
+    assert_eq!(check_len(3, 2), Err(InterpolationError::MismatchedLengths { expected: 3, found: 2 }));
+    assert_eq!(InterpolationError::NotIncreasing { index: 2 }.to_string(), "key 2 is not after the previous key");
+}
@@ -0,0 +1,110 @@
+//! Interpolation of `cgmath` vectors, points and quaternions
+
+use cgmath::{BaseFloat, EuclideanSpace, Point2, Point3, Quaternion, Vector2, Vector3, Vector4};
+
+use lerp::Lerp;
+use slerp::Slerp;
+use spatial::Spatial;
+
+macro_rules! impl_for_vector {
+    ($vector: ident) => (
+        impl<S: BaseFloat> Lerp for $vector<S> {
+            type Scalar = S;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &S) -> Self {
+                *self + (*other - *self) * *scalar
+            }
+        }
+
+        impl<S: BaseFloat> Spatial for $vector<S> {
+            type Scalar = S;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self { *self + *other }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self { *self - *other }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &S) -> Self { *self * *scalar }
+        }
+    )
+}
+
+impl_for_vector!(Vector2);
+impl_for_vector!(Vector3);
+impl_for_vector!(Vector4);
+
+macro_rules! impl_for_point {
+    ($point: ident) => (
+        impl<S: BaseFloat> Lerp for $point<S> {
+            type Scalar = S;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &S) -> Self {
+                *self + (*other - *self) * *scalar
+            }
+        }
+
+        impl<S: BaseFloat> Spatial for $point<S> {
+            type Scalar = S;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self { *self + other.to_vec() }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self { *self - other.to_vec() }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &S) -> Self { *self * *scalar }
+        }
+    )
+}
+
+impl_for_point!(Point2);
+impl_for_point!(Point3);
+
+impl<S: BaseFloat> Slerp for Quaternion<S> {
+    type Scalar = S;
+
+    fn slerp(&self, other: &Self, scalar: &S) -> Self {
+        Quaternion::slerp(*self, *other, *scalar)
+    }
+}
+
+/// Normalized linear interpolation, which is cheaper than `Slerp`
+/// but does not rotate with constant angular velocity.
+impl<S: BaseFloat> Lerp for Quaternion<S> {
+    type Scalar = S;
+
+    fn lerp(&self, other: &Self, scalar: &S) -> Self {
+        self.nlerp(*other, *scalar)
+    }
+}
+
+#[test]
+fn cgmath_vector_bezier() {
+    use cub_bez;
+
+    let a = Point2::new(0.0f32, 0.0);
+    let b = Point2::new(0.0, 1.0);
+    let c = Point2::new(1.0, 1.0);
+    let d = Point2::new(1.0, 0.0);
+    assert_eq!(cub_bez(&a, &b, &c, &d, &0.5), Point2::new(0.5, 0.75));
+    assert_eq!(Spatial::sub(&c, &b), Point2::new(1.0, 0.0));
+    assert_eq!(Lerp::lerp(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(2.0, 4.0, 6.0), &0.5f64),
+               Vector3::new(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn cgmath_quaternion() {
+    use cgmath::{Deg, Rotation3};
+
+    let a = Quaternion::from_angle_z(Deg(0.0f64));
+    let b = Quaternion::from_angle_z(Deg(90.0));
+    let q = Slerp::slerp(&a, &b, &0.5);
+    let expected = Quaternion::from_angle_z(Deg(45.0));
+    assert!((q.s - expected.s).abs() < 1e-12);
+    assert!((q.v.z - expected.v.z).abs() < 1e-12);
+}
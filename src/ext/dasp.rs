@@ -0,0 +1,136 @@
+//! Interpolation of `dasp` samples and frames
+//!
+//! Integer and float samples, and frames of up to five channels,
+//! already implement `Lerp` through the impls for numbers and arrays.
+//! This adds the packed 24 and 48 bit samples,
+//! and interpolators that resample dasp signals with the kernels of this crate.
+
+use dasp::frame::Frame;
+use dasp::interpolate::Interpolator;
+use dasp::sample::{Duplex, Sample};
+use dasp::sample::types::{I24, I48, U24, U48};
+
+use ease::Easing;
+use lerp::Lerp;
+
+/// Impls of `Lerp` for packed samples through their inner representation
+macro_rules! impl_lerp_for_packed {
+    ($ty: ident, $scalar: ident) => (
+        impl Lerp for $ty {
+            type Scalar = $scalar;
+
+            #[inline(always)]
+            fn lerp(&self, other: &$ty, scalar: &$scalar) -> $ty {
+                $ty::new_unchecked(self.inner().lerp(&other.inner(), scalar))
+            }
+        }
+    )
+}
+
+impl_lerp_for_packed!(I24, f32);
+impl_lerp_for_packed!(U24, f32);
+impl_lerp_for_packed!(I48, f64);
+impl_lerp_for_packed!(U48, f64);
+
+/// Interpolates between two frames with an easing curve applied to the position.
+///
+/// With `Easing::Linear` this matches dasp's linear interpolator.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EasedInterpolator<F> {
+    /// The easing of the position between frames.
+    pub easing: Easing,
+    left: F,
+    right: F,
+}
+
+impl<F> EasedInterpolator<F> {
+    /// Creates an interpolator, where `left` and `right` are the first two frames.
+    pub fn new(easing: Easing, left: F, right: F) -> EasedInterpolator<F> {
+        EasedInterpolator { easing, left, right }
+    }
+}
+
+impl<F> Interpolator for EasedInterpolator<F>
+    where F: Frame, F::Sample: Duplex<f64>
+{
+    type Frame = F;
+
+    fn interpolate(&self, x: f64) -> F {
+        let x = self.easing.calc(x);
+        self.left.zip_map(self.right, |l, r| {
+            l.to_sample::<f64>().lerp(&r.to_sample::<f64>(), &x).to_sample()
+        })
+    }
+
+    fn next_source_frame(&mut self, source_frame: F) {
+        self.left = self.right;
+        self.right = source_frame;
+    }
+}
+
+/// Interpolates between the middle two of four frames with a Catmull-Rom spline.
+///
+/// This is smoother than linear interpolation, at the cost of one frame of latency.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CubicInterpolator<F> {
+    frames: [F; 4],
+}
+
+impl<F> CubicInterpolator<F> {
+    /// Creates an interpolator from the first four frames.
+    /// Interpolation starts between the second and the third frame.
+    pub fn new(frames: [F; 4]) -> CubicInterpolator<F> {
+        CubicInterpolator { frames }
+    }
+}
+
+impl<F> Interpolator for CubicInterpolator<F>
+    where F: Frame, F::Sample: Duplex<f64>
+{
+    type Frame = F;
+
+    fn interpolate(&self, x: f64) -> F {
+        let [f0, f1, f2, f3] = self.frames;
+        let mut i = 0;
+        f1.map(|s1| {
+            let p0 = f0.channel(i).cloned().unwrap_or(s1).to_sample::<f64>();
+            let p2 = f2.channel(i).cloned().unwrap_or(s1).to_sample::<f64>();
+            let p3 = f3.channel(i).cloned().unwrap_or(s1).to_sample::<f64>();
+            let p1 = s1.to_sample::<f64>();
+            i += 1;
+            let c1 = 0.5 * (p2 - p0);
+            let c2 = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+            let c3 = 0.5 * (p3 - p0) + 1.5 * (p1 - p2);
+            (((c3 * x + c2) * x + c1) * x + p1).to_sample()
+        })
+    }
+
+    fn next_source_frame(&mut self, source_frame: F) {
+        self.frames = [self.frames[1], self.frames[2], self.frames[3], source_frame];
+    }
+}
+
+#[test]
+fn dasp_packed_samples() {
+    let a = I24::new_unchecked(-100);
+    let b = I24::new_unchecked(100);
+    assert_eq!(a.lerp(&b, &0.25), I24::new_unchecked(-50));
+}
+
+#[test]
+fn dasp_interpolators() {
+    use ease::EaseFunction;
+
+    let mut eased = EasedInterpolator::new(Easing::Linear, [0.0f32, 1.0], [1.0, 0.0]);
+    assert_eq!(eased.interpolate(0.25), [0.25, 0.75]);
+    eased.easing = EaseFunction::QuadraticIn.into();
+    assert_eq!(eased.interpolate(0.5), [0.25, 0.75]);
+    eased.next_source_frame([2.0, 2.0]);
+    assert_eq!(eased.interpolate(0.0), [1.0, 0.0]);
+
+    // A line is reproduced exactly.
+    let mut cubic = CubicInterpolator::new([0i16, 10, 20, 30]);
+    assert_eq!(cubic.interpolate(0.5), 15);
+    cubic.next_source_frame(40);
+    assert_eq!(cubic.interpolate(0.5), 25);
+}
@@ -0,0 +1,87 @@
+//! Interpolation of `euclid` geometry, preserving the unit types
+
+use euclid::{Point2D, Rect, Size2D, Vector2D};
+
+use lerp::Lerp;
+use spatial::Spatial;
+
+/// Transitive impls of `Lerp` and `Spatial` for two-component euclid types
+macro_rules! impl_for_euclid {
+    ($ty: ident; $a: ident, $b: ident) => (
+        impl<T, U> Lerp for $ty<T, U> where T: Lerp {
+            type Scalar = T::Scalar;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &T::Scalar) -> Self {
+                $ty::new(self.$a.lerp(&other.$a, scalar), self.$b.lerp(&other.$b, scalar))
+            }
+        }
+
+        impl<T, U> Spatial for $ty<T, U> where T: Spatial {
+            type Scalar = T::Scalar;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self {
+                $ty::new(self.$a.add(&other.$a), self.$b.add(&other.$b))
+            }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self {
+                $ty::new(self.$a.sub(&other.$a), self.$b.sub(&other.$b))
+            }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &T::Scalar) -> Self {
+                $ty::new(self.$a.scale(scalar), self.$b.scale(scalar))
+            }
+        }
+    )
+}
+
+impl_for_euclid!(Point2D; x, y);
+impl_for_euclid!(Vector2D; x, y);
+impl_for_euclid!(Size2D; width, height);
+
+/// Interpolates the origin and the size.
+impl<T, U> Lerp for Rect<T, U> where T: Lerp {
+    type Scalar = T::Scalar;
+
+    #[inline(always)]
+    fn lerp(&self, other: &Self, scalar: &T::Scalar) -> Self {
+        Rect::new(self.origin.lerp(&other.origin, scalar), self.size.lerp(&other.size, scalar))
+    }
+}
+
+impl<T, U> Spatial for Rect<T, U> where T: Spatial {
+    type Scalar = T::Scalar;
+
+    #[inline(always)]
+    fn add(&self, other: &Self) -> Self {
+        Rect::new(self.origin.add(&other.origin), self.size.add(&other.size))
+    }
+
+    #[inline(always)]
+    fn sub(&self, other: &Self) -> Self {
+        Rect::new(self.origin.sub(&other.origin), self.size.sub(&other.size))
+    }
+
+    #[inline(always)]
+    fn scale(&self, scalar: &T::Scalar) -> Self {
+        Rect::new(self.origin.scale(scalar), self.size.scale(scalar))
+    }
+}
+
+#[test]
+fn euclid_rect() {
+    struct ScreenSpace;
+
+    let a: Rect<f32, ScreenSpace> = Rect::new(Point2D::new(0.0, 0.0), Size2D::new(10.0, 10.0));
+    let b = Rect::new(Point2D::new(10.0, 20.0), Size2D::new(20.0, 30.0));
+    // euclid has inherent `lerp` methods, which take precedence over the trait.
+    let r = Lerp::lerp(&a, &b, &0.5);
+    assert_eq!(r.origin, Point2D::new(5.0, 10.0));
+    assert_eq!(r.size, Size2D::new(15.0, 20.0));
+
+    let v: Vector2D<f64, ScreenSpace> = Vector2D::new(0.0, 0.0);
+    assert_eq!(v.add(&Vector2D::new(1.0, 2.0)).scale(&2.0), Vector2D::new(2.0, 4.0));
+}
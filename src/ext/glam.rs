@@ -0,0 +1,118 @@
+//! Interpolation of `glam` vectors, quaternions and matrices
+
+use glam::{DMat4, DQuat, DVec2, DVec3, DVec4, Mat4, Quat, Vec2, Vec3, Vec3A, Vec4};
+
+use lerp::Lerp;
+use slerp::Slerp;
+use spatial::Spatial;
+
+macro_rules! impl_for_vector {
+    ($vector: ident, $float: ident) => (
+        impl Lerp for $vector {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &$float) -> Self {
+                $vector::lerp(*self, *other, *scalar)
+            }
+        }
+
+        impl Spatial for $vector {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self { *self + *other }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self { *self - *other }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &$float) -> Self { *self * *scalar }
+        }
+    )
+}
+
+impl_for_vector!(Vec2, f32);
+impl_for_vector!(Vec3, f32);
+impl_for_vector!(Vec3A, f32);
+impl_for_vector!(Vec4, f32);
+impl_for_vector!(DVec2, f64);
+impl_for_vector!(DVec3, f64);
+impl_for_vector!(DVec4, f64);
+
+macro_rules! impl_for_quat {
+    ($quat: ident, $float: ident) => (
+        impl Slerp for $quat {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn slerp(&self, other: &Self, scalar: &$float) -> Self {
+                $quat::slerp(*self, *other, *scalar)
+            }
+        }
+
+        /// Normalized linear interpolation, which is cheaper than `Slerp`
+        /// but does not rotate with constant angular velocity.
+        impl Lerp for $quat {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &$float) -> Self {
+                $quat::lerp(*self, *other, *scalar)
+            }
+        }
+    )
+}
+
+impl_for_quat!(Quat, f32);
+impl_for_quat!(DQuat, f64);
+
+macro_rules! impl_for_mat {
+    ($mat: ident, $float: ident) => (
+        /// Interpolates every element of the matrix linearly.
+        /// To interpolate a rigid transform, decompose it into a `Transform` instead.
+        impl Lerp for $mat {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &$float) -> Self {
+                *self + (*other - *self) * *scalar
+            }
+        }
+
+        impl Spatial for $mat {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self { *self + *other }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self { *self - *other }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &$float) -> Self { *self * *scalar }
+        }
+    )
+}
+
+impl_for_mat!(Mat4, f32);
+impl_for_mat!(DMat4, f64);
+
+#[test]
+fn glam_transform() {
+    use transform::Transform;
+
+    let a = Transform { translation: Vec3::ZERO, rotation: Quat::IDENTITY, scale: Vec3::ONE };
+    let b = Transform {
+        translation: Vec3::new(2.0, 0.0, 0.0),
+        rotation: Quat::from_rotation_z(::std::f32::consts::FRAC_PI_2),
+        scale: Vec3::splat(3.0),
+    };
+    let m = Lerp::lerp(&a, &b, &0.5);
+    assert_eq!(m.translation, Vec3::new(1.0, 0.0, 0.0));
+    assert_eq!(m.scale, Vec3::splat(2.0));
+    assert!(m.rotation.angle_between(Quat::from_rotation_z(::std::f32::consts::FRAC_PI_4)) < 1e-3);
+
+    let identity = Lerp::lerp(&Mat4::ZERO, &Mat4::IDENTITY, &1.0);
+    assert_eq!(identity, Mat4::IDENTITY);
+}
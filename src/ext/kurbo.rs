@@ -0,0 +1,205 @@
+//! Interpolation of `kurbo` points, vectors, sizes and curves
+//!
+//! Points work with the generic Bézier functions of this crate,
+//! and curves are interpolated point by point to morph between shapes.
+//! Curves convert to and from `QuadraticBezier` and `CubicBezier`,
+//! and `BezPath` to and from `Path`.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use kurbo::{BezPath, PathEl};
+use kurbo::{CubicBez, Line, Point, QuadBez, Size, Vec2};
+
+use bezier::{CubicBezier, QuadraticBezier};
+use lerp::Lerp;
+#[cfg(feature = "alloc")]
+use path::{Path, PathPoint};
+use spatial::Spatial;
+
+/// Field-wise impls of `Lerp` and `Spatial` for two-component kurbo types
+macro_rules! impl_for_kurbo {
+    ($ty: ident; $a: ident, $b: ident) => (
+        impl Lerp for $ty {
+            type Scalar = f64;
+
+            #[inline(always)]
+            fn lerp(&self, other: &$ty, scalar: &f64) -> $ty {
+                $ty::new(self.$a.lerp(&other.$a, scalar), self.$b.lerp(&other.$b, scalar))
+            }
+        }
+
+        impl Spatial for $ty {
+            type Scalar = f64;
+
+            #[inline(always)]
+            fn add(&self, other: &$ty) -> $ty {
+                $ty::new(self.$a + other.$a, self.$b + other.$b)
+            }
+
+            #[inline(always)]
+            fn sub(&self, other: &$ty) -> $ty {
+                $ty::new(self.$a - other.$a, self.$b - other.$b)
+            }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &f64) -> $ty {
+                $ty::new(self.$a * scalar, self.$b * scalar)
+            }
+        }
+    )
+}
+
+impl_for_kurbo!(Point; x, y);
+impl_for_kurbo!(Vec2; x, y);
+impl_for_kurbo!(Size; width, height);
+
+/// Point-wise impls of `Lerp` for kurbo curve segments
+macro_rules! impl_lerp_for_kurbo_segment {
+    ($ty: ident; $($p: ident),+) => (
+        impl Lerp for $ty {
+            type Scalar = f64;
+
+            #[inline(always)]
+            fn lerp(&self, other: &$ty, scalar: &f64) -> $ty {
+                $ty { $($p: Lerp::lerp(&self.$p, &other.$p, scalar)),+ }
+            }
+        }
+    )
+}
+
+impl_lerp_for_kurbo_segment!(Line; p0, p1);
+impl_lerp_for_kurbo_segment!(QuadBez; p0, p1, p2);
+impl_lerp_for_kurbo_segment!(CubicBez; p0, p1, p2, p3);
+
+/// Conversions between kurbo curve segments and the curves of this crate
+macro_rules! impl_from_kurbo_segment {
+    ($ty: ident, $curve: ident; $($p: ident),+) => (
+        impl From<$ty> for $curve<Point> {
+            fn from(segment: $ty) -> $curve<Point> {
+                $curve { points: [$(segment.$p),+] }
+            }
+        }
+
+        impl From<$curve<Point>> for $ty {
+            fn from(curve: $curve<Point>) -> $ty {
+                let [$($p),+] = curve.points;
+                $ty { $($p),+ }
+            }
+        }
+    )
+}
+
+impl_from_kurbo_segment!(QuadBez, QuadraticBezier; p0, p1, p2);
+impl_from_kurbo_segment!(CubicBez, CubicBezier; p0, p1, p2, p3);
+
+/// Converts a path to a `BezPath` of one subpath of cubic segments.
+#[cfg(feature = "alloc")]
+impl From<Path<Point>> for BezPath {
+    fn from(path: Path<Point>) -> BezPath {
+        let points = path.points();
+        let mut bez = BezPath::new();
+        if let Some(first) = points.first() {
+            bez.move_to(first.position);
+        }
+        for (a, b) in points.iter().zip(points.iter().skip(1)) {
+            bez.curve_to(a.out_control, b.in_control, b.position);
+        }
+        bez
+    }
+}
+
+/// Converts a `BezPath` to a path, with lines and quadratic segments raised to cubic ones.
+///
+/// A `Path` is one connected curve, so subpaths are joined by straight segments,
+/// and closed subpaths end with a segment back to their start.
+#[cfg(feature = "alloc")]
+impl From<BezPath> for Path<Point> {
+    fn from(bez: BezPath) -> Path<Point> {
+        let mut points: Vec<PathPoint<Point>> = Vec::new();
+        let mut start = None;
+        for el in bez.iter() {
+            let current = points.last().map(|p| p.position);
+            let (out_control, in_control, position) = match el {
+                PathEl::MoveTo(p) => {
+                    start = Some(p);
+                    if current != Some(p) {
+                        points.push(PathPoint::corner(p));
+                    }
+                    continue;
+                }
+                PathEl::LineTo(p) => (current.unwrap_or(p), p, p),
+                PathEl::QuadTo(p1, p2) => {
+                    let CubicBez { p1, p2, p3, .. } = QuadBez::new(current.unwrap_or(p1), p1, p2).raise();
+                    (p1, p2, p3)
+                }
+                PathEl::CurveTo(p1, p2, p3) => (p1, p2, p3),
+                PathEl::ClosePath => match start {
+                    Some(p) if current != Some(p) => (current.unwrap_or(p), p, p),
+                    _ => continue,
+                },
+            };
+            match points.last_mut() {
+                Some(last) => last.out_control = out_control,
+                None => points.push(PathPoint::corner(out_control)),
+            }
+            points.push(PathPoint { position, in_control, out_control: position });
+        }
+        Path::new(points)
+    }
+}
+
+#[test]
+fn kurbo_cubic() {
+    use kurbo::ParamCurve;
+
+    let c = CubicBez::new((0.0, 0.0), (1.0, 2.0), (3.0, 2.0), (4.0, 0.0));
+    let p = ::cub_bez(&c.p0, &c.p1, &c.p2, &c.p3, &0.25);
+    assert!((p - c.eval(0.25)).hypot() < 1e-12);
+
+    let d = CubicBez::new((0.0, 2.0), (1.0, 4.0), (3.0, 4.0), (4.0, 2.0));
+    assert_eq!(Lerp::lerp(&c, &d, &0.5).p1, Point::new(1.0, 3.0));
+
+    let curve = CubicBezier::from(c);
+    assert!((curve.sample(&0.25) - c.eval(0.25)).hypot() < 1e-12);
+    assert_eq!(CubicBez::from(curve), c);
+    let q = QuadBez::new((0.0, 0.0), (1.0, 2.0), (2.0, 0.0));
+    assert_eq!(QuadBez::from(QuadraticBezier::from(q)), q);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn kurbo_bez_path() {
+    use kurbo::{ParamCurve, ParamCurveNearest, Shape};
+
+    let mut bez = BezPath::new();
+    bez.move_to((0.0, 0.0));
+    bez.line_to((2.0, 0.0));
+    bez.quad_to((3.0, 1.0), (2.0, 2.0));
+    bez.curve_to((1.0, 3.0), (0.0, 3.0), (0.0, 2.0));
+    bez.close_path();
+    let path = Path::from(bez.clone());
+    assert_eq!(path.segment_count(), 4);
+    // The curves are the same, although lines are not parameterized evenly.
+    for (i, segment) in bez.segments().enumerate() {
+        assert_eq!(path.sample(i as f64), Some(segment.eval(0.0)));
+        for j in 1..=4 {
+            let p = path.sample(i as f64 + j as f64 / 4.0).unwrap();
+            assert!(segment.nearest(p, 1e-12).distance_sq < 1e-20);
+        }
+    }
+    // Back to a `BezPath` of cubic segments along the same curve.
+    let back = BezPath::from(path);
+    assert_eq!(back.elements().len(), 5);
+    assert!((back.area() - bez.area()).abs() < 1e-12);
+    assert_eq!(Path::from(back).points().len(), 5);
+    // Subpaths are joined, and an empty path stays empty.
+    let mut bez = BezPath::new();
+    bez.move_to((0.0, 0.0));
+    bez.line_to((1.0, 0.0));
+    bez.move_to((1.0, 1.0));
+    bez.line_to((0.0, 1.0));
+    assert_eq!(Path::from(bez).sample(1.5), Some(Point::new(1.0, 0.5)));
+    assert!(BezPath::from(Path::from(BezPath::new())).elements().is_empty());
+}
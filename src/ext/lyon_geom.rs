@@ -0,0 +1,77 @@
+//! Interpolation of `lyon_geom` segments
+//!
+//! Points are `euclid` types, so this feature also enables `euclid`.
+//! Segments are interpolated point by point to morph between shapes
+//! before they are flattened or tessellated.
+//! Curves convert to and from `QuadraticBezier` and `CubicBezier`.
+
+use lyon_geom::{CubicBezierSegment, LineSegment, Point, QuadraticBezierSegment};
+
+use bezier::{CubicBezier, QuadraticBezier};
+use lerp::Lerp;
+
+/// Point-wise impls of `Lerp` for lyon segments
+macro_rules! impl_lerp_for_lyon_segment {
+    ($ty: ident; $($p: ident),+) => (
+        impl<S> Lerp for $ty<S> where S: Lerp {
+            type Scalar = S::Scalar;
+
+            #[inline(always)]
+            fn lerp(&self, other: &$ty<S>, scalar: &S::Scalar) -> $ty<S> {
+                $ty { $($p: Lerp::lerp(&self.$p, &other.$p, scalar)),+ }
+            }
+        }
+    )
+}
+
+impl_lerp_for_lyon_segment!(LineSegment; from, to);
+impl_lerp_for_lyon_segment!(QuadraticBezierSegment; from, ctrl, to);
+impl_lerp_for_lyon_segment!(CubicBezierSegment; from, ctrl1, ctrl2, to);
+
+/// Conversions between lyon segments and the curves of this crate
+macro_rules! impl_from_lyon_segment {
+    ($ty: ident, $curve: ident; $($p: ident),+) => (
+        impl<S> From<$ty<S>> for $curve<Point<S>> {
+            fn from(segment: $ty<S>) -> $curve<Point<S>> {
+                $curve { points: [$(segment.$p),+] }
+            }
+        }
+
+        impl<S> From<$curve<Point<S>>> for $ty<S> {
+            fn from(curve: $curve<Point<S>>) -> $ty<S> {
+                let [$($p),+] = curve.points;
+                $ty { $($p),+ }
+            }
+        }
+    )
+}
+
+impl_from_lyon_segment!(QuadraticBezierSegment, QuadraticBezier; from, ctrl, to);
+impl_from_lyon_segment!(CubicBezierSegment, CubicBezier; from, ctrl1, ctrl2, to);
+
+#[test]
+fn lyon_cubic() {
+    use lyon_geom::point;
+
+    let c = CubicBezierSegment {
+        from: point(0.0f32, 0.0),
+        ctrl1: point(1.0, 2.0),
+        ctrl2: point(3.0, 2.0),
+        to: point(4.0, 0.0),
+    };
+    let p = ::cub_bez(&c.from, &c.ctrl1, &c.ctrl2, &c.to, &0.5);
+    assert!((p - c.sample(0.5)).length() < 1e-5);
+
+    let line = LineSegment { from: point(0.0f32, 0.0), to: point(2.0, 2.0) };
+    let other = LineSegment { from: point(2.0f32, 0.0), to: point(4.0, 2.0) };
+    assert_eq!(Lerp::lerp(&line, &other, &0.5).from, point(1.0, 0.0));
+
+    let curve = CubicBezier::from(c);
+    assert!((curve.sample(&0.5) - c.sample(0.5)).length() < 1e-5);
+    // The segments have an inherent `from` method, so `Into` is used instead.
+    let segment: CubicBezierSegment<f32> = curve.into();
+    assert_eq!(segment, c);
+    let q = QuadraticBezierSegment { from: point(0.0f32, 0.0), ctrl: point(1.0, 2.0), to: point(2.0, 0.0) };
+    let segment: QuadraticBezierSegment<f32> = QuadraticBezier::from(q).into();
+    assert_eq!(segment, q);
+}
@@ -0,0 +1,83 @@
+//! Interpolation of `mint` vectors, points and quaternions
+//!
+//! Math libraries that convert to and from `mint` types
+//! get interpolation support through these impls.
+
+use mint::{Point2, Point3, Quaternion, Vector2, Vector3, Vector4};
+
+use lerp::Lerp;
+use slerp::Slerp;
+use spatial::Spatial;
+
+/// Transitive impls of `Lerp` and `Spatial` for mint types, given their fields
+macro_rules! impl_for_mint {
+    ($ty: ident; $($field: ident),*) => (
+        impl<T> Lerp for $ty<T> where T: Lerp {
+            type Scalar = T::Scalar;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &T::Scalar) -> Self {
+                $ty { $($field: self.$field.lerp(&other.$field, scalar)),* }
+            }
+        }
+
+        impl<T> Spatial for $ty<T> where T: Spatial {
+            type Scalar = T::Scalar;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self {
+                $ty { $($field: self.$field.add(&other.$field)),* }
+            }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self {
+                $ty { $($field: self.$field.sub(&other.$field)),* }
+            }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &T::Scalar) -> Self {
+                $ty { $($field: self.$field.scale(scalar)),* }
+            }
+        }
+    )
+}
+
+impl_for_mint!(Vector2; x, y);
+impl_for_mint!(Vector3; x, y, z);
+impl_for_mint!(Vector4; x, y, z, w);
+impl_for_mint!(Point2; x, y);
+impl_for_mint!(Point3; x, y, z);
+
+macro_rules! impl_slerp_for_quaternion {
+    ($float: ident) => (
+        impl Slerp for Quaternion<$float> {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn slerp(&self, other: &Self, scalar: &$float) -> Self {
+                let a: [$float; 4] = (*self).into();
+                let b: [$float; 4] = (*other).into();
+                a.slerp(&b, scalar).into()
+            }
+        }
+    )
+}
+
+impl_slerp_for_quaternion!(f32);
+impl_slerp_for_quaternion!(f64);
+
+#[test]
+fn mint_lerp() {
+    let a = Point2 { x: 0.0f32, y: 10.0 };
+    let b = Point2 { x: 10.0, y: 0.0 };
+    assert_eq!(a.lerp(&b, &0.5), Point2 { x: 5.0, y: 5.0 });
+
+    let a = Vector3 { x: 0u8, y: 0, z: 0 };
+    let b = Vector3 { x: 10, y: 20, z: 30 };
+    assert_eq!(a.lerp(&b, &0.5), Vector3 { x: 5, y: 10, z: 15 });
+
+    let identity = Quaternion { v: Vector3 { x: 0.0f64, y: 0.0, z: 0.0 }, s: 1.0 };
+    let half = Quaternion { v: Vector3 { x: 0.0, y: 0.0, z: 1.0 }, s: 0.0 };
+    let q = identity.slerp(&half, &0.5);
+    assert!((q.s - 0.5f64.sqrt()).abs() < 1e-12);
+}
@@ -0,0 +1,31 @@
+//! Implementations of the interpolation traits for types from other crates.
+//!
+//! Each integration is enabled by a feature with the name of the crate,
+//! except for `num-rational`, which is enabled by the `rational` feature.
+//! The `vecmath` types are plain arrays that need no feature,
+//! and are only checked by tests.
+
+#[cfg(feature = "cgmath")]
+mod cgmath;
+#[cfg(feature = "dasp")]
+pub mod dasp;
+#[cfg(feature = "euclid")]
+mod euclid;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "image")]
+pub mod image;
+#[cfg(feature = "kurbo")]
+mod kurbo;
+#[cfg(feature = "lyon_geom")]
+mod lyon_geom;
+#[cfg(feature = "mint")]
+mod mint;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "rational")]
+mod rational;
+#[cfg(feature = "ultraviolet")]
+mod ultraviolet;
+#[cfg(test)]
+mod vecmath;
@@ -0,0 +1,143 @@
+//! Interpolation of `nalgebra` vectors, points, rotations and isometries
+
+use nalgebra::{
+    Isometry2, Isometry3, Point2, Point3, RealField,
+    UnitComplex, UnitQuaternion, Vector2, Vector3, Vector4,
+};
+
+use lerp::Lerp;
+use slerp::Slerp;
+use spatial::Spatial;
+
+macro_rules! impl_for_vector {
+    ($vector: ident) => (
+        impl<T: RealField + Copy> Lerp for $vector<T> {
+            type Scalar = T;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &T) -> Self {
+                self + (other - self) * *scalar
+            }
+        }
+
+        impl<T: RealField + Copy> Spatial for $vector<T> {
+            type Scalar = T;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self { self + other }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self { self - other }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &T) -> Self { self * *scalar }
+        }
+    )
+}
+
+impl_for_vector!(Vector2);
+impl_for_vector!(Vector3);
+impl_for_vector!(Vector4);
+
+macro_rules! impl_for_point {
+    ($point: ident) => (
+        impl<T: RealField + Copy> Lerp for $point<T> {
+            type Scalar = T;
+
+            #[inline(always)]
+            fn lerp(&self, other: &Self, scalar: &T) -> Self {
+                self + (other - self) * *scalar
+            }
+        }
+
+        impl<T: RealField + Copy> Spatial for $point<T> {
+            type Scalar = T;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self { $point::from(self.coords + other.coords) }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self { $point::from(self.coords - other.coords) }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &T) -> Self { self * *scalar }
+        }
+    )
+}
+
+impl_for_point!(Point2);
+impl_for_point!(Point3);
+
+impl<T: RealField + Copy> Slerp for UnitQuaternion<T> {
+    type Scalar = T;
+
+    fn slerp(&self, other: &Self, scalar: &T) -> Self {
+        self.try_slerp(other, *scalar, T::default_epsilon())
+            .unwrap_or_else(|| self.nlerp(other, *scalar))
+    }
+}
+
+/// Normalized linear interpolation, which is cheaper than `Slerp`
+/// but does not rotate with constant angular velocity.
+impl<T: RealField + Copy> Lerp for UnitQuaternion<T> {
+    type Scalar = T;
+
+    fn lerp(&self, other: &Self, scalar: &T) -> Self {
+        self.nlerp(other, *scalar)
+    }
+}
+
+impl<T: RealField + Copy> Slerp for UnitComplex<T> {
+    type Scalar = T;
+
+    fn slerp(&self, other: &Self, scalar: &T) -> Self {
+        UnitComplex::slerp(self, other, *scalar)
+    }
+}
+
+/// Interpolates the translation linearly and the rotation spherically.
+impl<T: RealField + Copy> Lerp for Isometry2<T> {
+    type Scalar = T;
+
+    fn lerp(&self, other: &Self, scalar: &T) -> Self {
+        self.lerp_slerp(other, *scalar)
+    }
+}
+
+/// Interpolates the translation linearly and the rotation spherically.
+impl<T: RealField + Copy> Lerp for Isometry3<T> {
+    type Scalar = T;
+
+    fn lerp(&self, other: &Self, scalar: &T) -> Self {
+        let translation = Lerp::lerp(&self.translation.vector, &other.translation.vector, scalar);
+        let rotation = Slerp::slerp(&self.rotation, &other.rotation, scalar);
+        Isometry3::from_parts(translation.into(), rotation)
+    }
+}
+
+#[test]
+fn nalgebra_vector_bezier() {
+    use quad_bez;
+
+    let a = Vector2::new(0.0f32, 0.0);
+    let b = Vector2::new(1.0, 2.0);
+    let c = Vector2::new(2.0, 0.0);
+    assert_eq!(quad_bez(&a, &b, &c, &0.5), Vector2::new(1.0, 1.0));
+    assert_eq!(Spatial::scale(&b, &2.0), Vector2::new(2.0, 4.0));
+    assert_eq!(Lerp::lerp(&Point3::new(0.0, 0.0, 0.0), &Point3::new(2.0, 4.0, 6.0), &0.5),
+               Point3::new(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn nalgebra_rotation() {
+    let a = UnitQuaternion::identity();
+    let b = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), ::std::f64::consts::PI);
+    let q = Slerp::slerp(&a, &b, &0.5);
+    assert!((q.angle() - ::std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+    let a = Isometry3::translation(0.0f64, 0.0, 0.0);
+    let b = Isometry3::new(Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0));
+    let m = Lerp::lerp(&a, &b, &0.5);
+    assert_eq!(m.translation.vector, Vector3::new(1.0, 0.0, 0.0));
+    assert!((m.rotation.angle() - 0.5).abs() < 1e-12);
+}
@@ -0,0 +1,72 @@
+//! Exact interpolation of `num-rational` numbers
+//!
+//! With the `rational` feature, `Ratio` implements `Lerp`, `Spatial` and `InnerSpace`
+//! with itself as the scalar, so the Bézier functions of this crate, and the arrays
+//! of them, are evaluated without rounding. Tooling such as curve editors and tests
+//! can compute exact points and compare them against the float evaluation.
+//! `BigRational` never overflows; `Ratio` of a fixed size integer is faster,
+//! but overflows after a few levels of nesting with large denominators.
+
+use num_integer::Integer;
+use num_rational::Ratio;
+
+use lerp::Lerp;
+use spatial::{InnerSpace, Spatial};
+
+impl<T> Lerp for Ratio<T>
+    where T: Clone + Integer
+{
+    type Scalar = Ratio<T>;
+
+    #[inline(always)]
+    fn lerp(&self, other: &Ratio<T>, scalar: &Ratio<T>) -> Ratio<T> {
+        self + &((other - self) * scalar)
+    }
+}
+
+impl<T> Spatial for Ratio<T>
+    where T: Clone + Integer
+{
+    type Scalar = Ratio<T>;
+
+    #[inline(always)]
+    fn add(&self, other: &Ratio<T>) -> Ratio<T> { self + other }
+
+    #[inline(always)]
+    fn sub(&self, other: &Ratio<T>) -> Ratio<T> { self - other }
+
+    #[inline(always)]
+    fn scale(&self, scalar: &Ratio<T>) -> Ratio<T> { self * scalar }
+}
+
+impl<T> InnerSpace for Ratio<T>
+    where T: Clone + Integer
+{
+    #[inline(always)]
+    fn dot(&self, other: &Ratio<T>) -> Ratio<T> { self * other }
+}
+
+#[test]
+fn rational_cub_bez() {
+    use num_rational::BigRational;
+    use num_traits::{FromPrimitive, ToPrimitive};
+
+    let r = |n: i64, d: i64| Ratio::new(n, d);
+    let [a, b, c, d] = [[r(0, 1), r(0, 1)], [r(1, 3), r(1, 1)], [r(2, 3), r(-1, 1)], [r(1, 1), r(0, 1)]];
+    let p = ::cub_bez(&a, &b, &c, &d, &r(1, 3));
+    assert_eq!(p, [r(1, 3), r(2, 9)]);
+    assert_eq!([r(1, 3), r(1, 1)].dot(&[r(3, 1), r(1, 2)]), r(3, 2));
+
+    // The float evaluation agrees up to rounding.
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let exact = ::cub_bez(&a, &b, &c, &d, &Ratio::from_f64(t).unwrap());
+        let float = ::cub_bez(&[0.0, 0.0], &[1.0 / 3.0, 1.0], &[2.0 / 3.0, -1.0], &[1.0, 0.0], &t);
+        assert!((exact[1].to_f64().unwrap() - float[1]).abs() < 1e-15);
+    }
+
+    let big = |n: i64, d: i64| BigRational::new(n.into(), d.into());
+    let t = big(1, 1 << 40);
+    let x = ::quad_bez(&big(0, 1), &big(1, 1), &big(0, 1), &t);
+    assert_eq!(x, &t * &big(2, 1) * (big(1, 1) - &t));
+}
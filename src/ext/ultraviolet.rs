@@ -0,0 +1,125 @@
+//! Interpolation of `ultraviolet` vectors and rotors, including the wide types
+//!
+//! The wide types hold several lanes, which are interpolated at once
+//! with a wide scalar such as `f32x8`.
+
+use ultraviolet::{f32x4, f32x8, f64x2, f64x4};
+use ultraviolet::{Rotor2, Rotor2x4, Rotor2x8, Rotor3, Rotor3x4, Rotor3x8};
+use ultraviolet::{DRotor2, DRotor2x2, DRotor2x4, DRotor3, DRotor3x2, DRotor3x4};
+use ultraviolet::{Vec2, Vec2x4, Vec2x8, Vec3, Vec3x4, Vec3x8, Vec4, Vec4x4, Vec4x8};
+use ultraviolet::{DVec2, DVec2x2, DVec2x4, DVec3, DVec3x2, DVec3x4, DVec4, DVec4x2, DVec4x4};
+use ultraviolet::Lerp as UvLerp;
+use ultraviolet::Slerp as UvSlerp;
+
+use lerp::Lerp;
+use slerp::Slerp;
+use spatial::Spatial;
+
+/// Impls of `Lerp` and `Spatial` passing through to ultraviolet's operators
+macro_rules! impl_for_ultraviolet {
+    ($($scalar: ident => ($($ty: ident),+)),+) => {
+        $($(
+            impl Lerp for $ty {
+                type Scalar = $scalar;
+
+                #[inline(always)]
+                fn lerp(&self, other: &$ty, scalar: &$scalar) -> $ty {
+                    UvLerp::lerp(self, *other, *scalar)
+                }
+            }
+
+            impl Spatial for $ty {
+                type Scalar = $scalar;
+
+                #[inline(always)]
+                fn add(&self, other: &$ty) -> $ty { *self + *other }
+
+                #[inline(always)]
+                fn sub(&self, other: &$ty) -> $ty { *self - *other }
+
+                #[inline(always)]
+                fn scale(&self, scalar: &$scalar) -> $ty { *self * *scalar }
+            }
+        )+)+
+    }
+}
+
+impl_for_ultraviolet!(
+    f32 => (Vec2, Vec3, Vec4),
+    f32x4 => (f32x4, Vec2x4, Vec3x4, Vec4x4),
+    f32x8 => (f32x8, Vec2x8, Vec3x8, Vec4x8),
+    f64 => (DVec2, DVec3, DVec4),
+    f64x2 => (f64x2, DVec2x2, DVec3x2, DVec4x2),
+    f64x4 => (f64x4, DVec2x4, DVec3x4, DVec4x4)
+);
+
+/// Impls of `Lerp` as normalized linear interpolation for rotors
+macro_rules! impl_lerp_for_rotor {
+    ($($scalar: ident => ($($ty: ident),+)),+) => {
+        $($(
+            impl Lerp for $ty {
+                type Scalar = $scalar;
+
+                #[inline(always)]
+                fn lerp(&self, other: &$ty, scalar: &$scalar) -> $ty {
+                    UvLerp::lerp(self, *other, *scalar).normalized()
+                }
+            }
+        )+)+
+    }
+}
+
+impl_lerp_for_rotor!(
+    f32 => (Rotor2, Rotor3),
+    f32x4 => (Rotor2x4, Rotor3x4),
+    f32x8 => (Rotor2x8, Rotor3x8),
+    f64 => (DRotor2, DRotor3),
+    f64x2 => (DRotor2x2, DRotor3x2),
+    f64x4 => (DRotor2x4, DRotor3x4)
+);
+
+/// Impls of `Slerp` passing through to ultraviolet's slerp for 3D rotors
+macro_rules! impl_slerp_for_rotor {
+    ($($scalar: ident => ($($ty: ident),+)),+) => {
+        $($(
+            impl Slerp for $ty {
+                type Scalar = $scalar;
+
+                #[inline(always)]
+                fn slerp(&self, other: &$ty, scalar: &$scalar) -> $ty {
+                    UvSlerp::slerp(self, *other, *scalar)
+                }
+            }
+        )+)+
+    }
+}
+
+impl_slerp_for_rotor!(
+    f32 => (Rotor3),
+    f32x4 => (Rotor3x4),
+    f32x8 => (Rotor3x8),
+    f64 => (DRotor3),
+    f64x2 => (DRotor3x2),
+    f64x4 => (DRotor3x4)
+);
+
+#[test]
+fn ultraviolet_wide() {
+    let a = Vec3x8::from([Vec3::zero(); 8]);
+    let b = Vec3x8::from([Vec3::one(); 8]);
+    let t = f32x8::from([0.0, 0.125, 0.25, 0.375, 0.5, 0.625, 0.75, 1.0]);
+    let lanes: [Vec3; 8] = Lerp::lerp(&a, &b, &t).into();
+    assert_eq!(lanes[0], Vec3::zero());
+    assert_eq!(lanes[4], Vec3::broadcast(0.5));
+    assert_eq!(lanes[7], Vec3::one());
+}
+
+#[test]
+fn ultraviolet_rotor() {
+    let a = Rotor3::identity();
+    let b = Rotor3::from_rotation_xy(::std::f32::consts::FRAC_PI_2);
+    let r = Slerp::slerp(&a, &b, &0.5);
+    let expected = Rotor3::from_rotation_xy(::std::f32::consts::FRAC_PI_4);
+    assert!((r.dot(expected) - 1.0).abs() < 1e-5);
+    assert!((Lerp::lerp(&a, &b, &1.0).dot(b) - 1.0).abs() < 1e-5);
+}
@@ -0,0 +1,34 @@
+//! Interpolation of `vecmath` vectors and matrices
+//!
+//! The `vecmath` types are aliases of nested arrays,
+//! so they are covered by the array impls of `Lerp` and `Spatial`.
+//! The tests here make sure the aliases keep working with them.
+
+#[test]
+fn vecmath_vector() {
+    use vecmath::{vec3_add, Vector3};
+    use lerp::Lerp;
+    use spatial::Spatial;
+
+    let a: Vector3<f32> = [0.0, 2.0, 4.0];
+    let b: Vector3<f32> = [2.0, 4.0, 8.0];
+    assert_eq!(a.lerp(&b, &0.5), [1.0, 3.0, 6.0]);
+    assert_eq!(vec3_add(a, b), a.add(&b));
+}
+
+#[test]
+fn vecmath_matrix() {
+    use vecmath::{mat2x3_id, mat4_id, Matrix2x3, Matrix3x2, Matrix4};
+    use lerp::Lerp;
+    use spatial::Spatial;
+
+    let id: Matrix2x3<f64> = mat2x3_id();
+    let zero: Matrix2x3<f64> = [[0.0; 3]; 2];
+    assert_eq!(zero.lerp(&id, &0.5), [[0.5, 0.0, 0.0], [0.0, 0.5, 0.0]]);
+
+    let m: Matrix3x2<f64> = [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+    assert_eq!(m.scale(&2.0), [[2.0, 4.0], [6.0, 8.0], [10.0, 12.0]]);
+
+    let id: Matrix4<f32> = mat4_id();
+    assert_eq!(id.lerp(&id, &0.25), id);
+}
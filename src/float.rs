@@ -0,0 +1,123 @@
+//! Scalar arithmetic used by time driven types
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use num_traits;
+use num_traits::FloatConst;
+
+/// Describes a floating point scalar that can be used for time and progress.
+///
+/// This is a thin layer over `num_traits::Float`,
+/// with the operations used by the time driven types.
+pub trait Float: Copy + PartialOrd
+    + Add<Output = Self> + Sub<Output = Self>
+    + Mul<Output = Self> + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Returns zero.
+    fn zero() -> Self;
+
+    /// Returns one.
+    fn one() -> Self;
+
+    /// Converts from `f64`, rounding to the nearest representable value.
+    fn from_f64(v: f64) -> Self;
+
+    /// Converts to `f64`.
+    fn to_f64(self) -> f64;
+
+    /// Returns the ratio of a circle's circumference to its diameter.
+    fn pi() -> Self;
+
+    /// Returns the difference between one and the next larger number.
+    fn epsilon() -> Self {
+        Self::from_f64(f64::EPSILON)
+    }
+
+    /// Returns the largest integer less than or equal to the number.
+    fn floor(self) -> Self;
+
+    /// Computes the sine of the number in radians.
+    fn sin(self) -> Self;
+
+    /// Computes the cosine of the number in radians.
+    fn cos(self) -> Self {
+        Self::from_f64(num_traits::Float::cos(self.to_f64()))
+    }
+
+    /// Computes the angle in radians of the point `(other, self)`,
+    /// from `-pi` to `pi`.
+    fn atan2(self, other: Self) -> Self {
+        Self::from_f64(num_traits::Float::atan2(self.to_f64(), other.to_f64()))
+    }
+
+    /// Returns `e^(self)`.
+    fn exp(self) -> Self;
+
+    /// Returns the square root of the number.
+    fn sqrt(self) -> Self {
+        Self::from_f64(num_traits::Float::sqrt(self.to_f64()))
+    }
+
+    /// Raises the number to a floating point power.
+    fn powf(self, n: Self) -> Self {
+        Self::from_f64(num_traits::Float::powf(self.to_f64(), n.to_f64()))
+    }
+}
+
+/// Implemented for every `num_traits::Float` that also provides `FloatConst`,
+/// so third party scalar types can be used for time and progress.
+impl<T> Float for T
+    where T: num_traits::Float + FloatConst
+{
+    #[inline(always)]
+    fn zero() -> T { T::zero() }
+
+    #[inline(always)]
+    fn one() -> T { T::one() }
+
+    #[inline(always)]
+    fn from_f64(v: f64) -> T { T::from(v).unwrap_or_else(T::nan) }
+
+    #[inline(always)]
+    fn to_f64(self) -> f64 { num_traits::ToPrimitive::to_f64(&self).unwrap_or(f64::NAN) }
+
+    #[inline(always)]
+    fn pi() -> T { T::PI() }
+
+    #[inline(always)]
+    fn epsilon() -> T { num_traits::Float::epsilon() }
+
+    #[inline(always)]
+    fn floor(self) -> T { num_traits::Float::floor(self) }
+
+    #[inline(always)]
+    fn sin(self) -> T { num_traits::Float::sin(self) }
+
+    #[inline(always)]
+    fn cos(self) -> T { num_traits::Float::cos(self) }
+
+    #[inline(always)]
+    fn atan2(self, other: T) -> T { num_traits::Float::atan2(self, other) }
+
+    #[inline(always)]
+    fn exp(self) -> T { num_traits::Float::exp(self) }
+
+    #[inline(always)]
+    fn sqrt(self) -> T { num_traits::Float::sqrt(self) }
+
+    #[inline(always)]
+    fn powf(self, n: T) -> T { num_traits::Float::powf(self, n) }
+}
+
+/// Computes `a * b + c`.
+///
+/// With the `fma` feature, this is a fused multiply-add with a single rounding.
+/// Otherwise, the product is rounded before adding, as written.
+#[inline(always)]
+pub(crate) fn mul_add<T: num_traits::Float>(a: T, b: T, c: T) -> T {
+    #[cfg(feature = "fma")]
+    return a.mul_add(b, c);
+    #[cfg(not(feature = "fma"))]
+    return a * b + c;
+}
@@ -0,0 +1,223 @@
+//! Extension traits for reading easing and interpolation left to right
+//!
+//! These are not in the prelude, so they are only in scope where imported.
+//!
+//! ```
+//! use interpolation::EaseFunction;
+//! use interpolation::fluent::{ramp, ProgressExt, ProgressIterExt};
+//!
+//! let x = 0.5f32.eased(EaseFunction::QuadraticIn).tween(&20.0f32, &340.0);
+//! assert_eq!(x, 100.0);
+//!
+//! let fade: Vec<u8> = ramp::<f32>(5).ease(EaseFunction::QuadraticIn).tween(0, 160).collect();
+//! assert_eq!(fade, [0, 10, 40, 90, 160]);
+//! ```
+
+use core::marker::PhantomData;
+
+use ease::{Ease, Easing};
+use float::Float;
+use lerp::Lerp;
+
+/// Methods on progress values, usually from zero to one.
+pub trait ProgressExt: Sized {
+    /// Returns the progress eased with `easing`.
+    fn eased<E: Into<Easing>>(self, easing: E) -> Self;
+
+    /// Interpolates from `start` to `end` with the progress.
+    fn tween<T: Lerp<Scalar = Self>>(self, start: &T, end: &T) -> T;
+}
+
+impl<S> ProgressExt for S
+    where S: Ease + Float
+{
+    #[inline]
+    fn eased<E: Into<Easing>>(self, easing: E) -> S {
+        easing.into().calc(self)
+    }
+
+    #[inline]
+    fn tween<T: Lerp<Scalar = S>>(self, start: &T, end: &T) -> T {
+        start.lerp(end, &self)
+    }
+}
+
+/// Methods on iterators of progress values.
+pub trait ProgressIterExt: Iterator + Sized {
+    /// Eases every progress value with `easing`.
+    fn ease<E: Into<Easing>>(self, easing: E) -> Eased<Self> {
+        Eased { iter: self, easing: easing.into() }
+    }
+
+    /// Interpolates from `start` to `end` with every progress value.
+    fn tween<T>(self, start: T, end: T) -> Tweened<Self, T>
+        where T: Lerp<Scalar = Self::Item>
+    {
+        Tweened { iter: self, start, end }
+    }
+}
+
+impl<I> ProgressIterExt for I
+    where I: Iterator, I::Item: Ease + Float
+{}
+
+/// An iterator that eases progress values, created by `ProgressIterExt::ease`.
+#[derive(Debug, Clone)]
+pub struct Eased<I> {
+    iter: I,
+    easing: Easing,
+}
+
+impl<I> Iterator for Eased<I>
+    where I: Iterator, I::Item: Ease + Float
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next().map(|t| self.easing.calc(t))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for Eased<I>
+    where I: DoubleEndedIterator, I::Item: Ease + Float
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        self.iter.next_back().map(|t| self.easing.calc(t))
+    }
+}
+
+impl<I> ExactSizeIterator for Eased<I>
+    where I: ExactSizeIterator, I::Item: Ease + Float
+{}
+
+/// An iterator that interpolates between two values,
+/// created by `ProgressIterExt::tween`.
+#[derive(Debug, Clone)]
+pub struct Tweened<I, T> {
+    iter: I,
+    start: T,
+    end: T,
+}
+
+impl<I, T> Iterator for Tweened<I, T>
+    where I: Iterator, T: Lerp<Scalar = I::Item>
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|t| self.start.lerp(&self.end, &t))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, T> DoubleEndedIterator for Tweened<I, T>
+    where I: DoubleEndedIterator, T: Lerp<Scalar = I::Item>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|t| self.start.lerp(&self.end, &t))
+    }
+}
+
+impl<I, T> ExactSizeIterator for Tweened<I, T>
+    where I: ExactSizeIterator, T: Lerp<Scalar = I::Item>
+{}
+
+/// Returns `n` evenly spaced progress values from zero to one, inclusive.
+///
+/// A single value is zero.
+pub fn ramp<S: Float>(n: usize) -> Ramp<S> {
+    Ramp { front: 0, back: n, last: n.saturating_sub(1).max(1), marker: PhantomData }
+}
+
+/// An iterator of evenly spaced progress values, created by `ramp`.
+#[derive(Debug, Clone)]
+pub struct Ramp<S> {
+    front: usize,
+    back: usize,
+    last: usize,
+    marker: PhantomData<S>,
+}
+
+impl<S: Float> Ramp<S> {
+    fn at(&self, i: usize) -> S {
+        if i == self.last {
+            S::one()
+        } else {
+            S::from_f64(i as f64 / self.last as f64)
+        }
+    }
+}
+
+impl<S: Float> Iterator for Ramp<S> {
+    type Item = S;
+
+    #[inline]
+    fn next(&mut self) -> Option<S> {
+        if self.front < self.back {
+            self.front += 1;
+            Some(self.at(self.front - 1))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<S: Float> DoubleEndedIterator for Ramp<S> {
+    #[inline]
+    fn next_back(&mut self) -> Option<S> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.at(self.back))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S: Float> ExactSizeIterator for Ramp<S> {}
+
+#[test]
+fn fluent_progress() {
+    use bezier_ease::CubicBezierEase;
+    use ease::EaseFunction;
+
+    assert_eq!(0.25f64.eased(EaseFunction::QuadraticIn), 0.0625);
+    assert_eq!(0.5f32.tween(&[0.0f32, 2.0], &[2.0, 6.0]), [1.0, 4.0]);
+    assert_eq!(0.5f32.tween(&0u8, &10), 5);
+    let ease = CubicBezierEase::new(0.0, 0.0, 1.0, 1.0);
+    assert!((0.3f64.eased(ease).tween(&0.0f64, &10.0) - 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn fluent_iter() {
+    use ease::EaseFunction;
+
+    assert_eq!(ramp::<f64>(0).count(), 0);
+    assert_eq!(ramp::<f64>(1).collect::<Vec<_>>(), [0.0]);
+    assert_eq!(ramp::<f32>(3).rev().collect::<Vec<_>>(), [1.0, 0.5, 0.0]);
+    let eased: Vec<f64> = ramp(3).ease(EaseFunction::QuadraticIn).collect();
+    assert_eq!(eased, [0.0, 0.25, 1.0]);
+    let mut stagger = [0.0f32, 0.5, 1.0].iter().cloned().tween([0.0f32, 10.0], [1.0, 20.0]);
+    assert_eq!(stagger.len(), 3);
+    assert_eq!(stagger.next_back(), Some([1.0, 20.0]));
+    assert_eq!(stagger.next(), Some([0.0, 10.0]));
+}
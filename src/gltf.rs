@@ -0,0 +1,191 @@
+//! Keyframe evaluation with glTF animation sampler semantics
+
+use alloc::vec::Vec;
+
+use error::{self, check_increasing, check_len};
+use hermite::hermite;
+use lerp::Lerp;
+use slerp::Slerp;
+use spatial::Spatial;
+
+/// The interpolation of a glTF animation sampler.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GltfInterpolation {
+    /// Each keyframe value is held until the next keyframe.
+    Step,
+    /// Values are interpolated linearly, or spherically for rotations.
+    Linear,
+    /// Cubic Hermite spline with explicit in and out tangents.
+    CubicSpline,
+}
+
+/// Keyframes laid out like a glTF animation sampler.
+///
+/// For `CubicSpline`, every keyframe has three values:
+/// the in-tangent, the value and the out-tangent, in that order.
+/// Times outside the keyframe range hold the first or last value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfSampler<T> {
+    /// The interpolation between keyframes.
+    pub interpolation: GltfInterpolation,
+    /// The keyframe times in ascending order.
+    pub times: Vec<f32>,
+    /// The output values, one or three per keyframe.
+    pub values: Vec<T>,
+}
+
+impl<T> GltfSampler<T>
+    where T: Clone
+{
+    /// Creates a sampler from the input and output accessors of a glTF sampler.
+    pub fn new(interpolation: GltfInterpolation, times: Vec<f32>, values: Vec<T>) -> GltfSampler<T> {
+        GltfSampler { interpolation, times, values }
+    }
+
+    /// Creates a sampler, checking that there is at least one keyframe,
+    /// that the times are increasing, and that there are one or three values per time.
+    pub fn try_new(interpolation: GltfInterpolation, times: Vec<f32>, values: Vec<T>) -> error::Result<GltfSampler<T>> {
+        let sampler = GltfSampler::new(interpolation, times, values);
+        check_increasing(&sampler.times)?;
+        check_len(sampler.times.len() * sampler.stride(), sampler.values.len())?;
+        Ok(sampler)
+    }
+
+    fn stride(&self) -> usize {
+        match self.interpolation {
+            GltfInterpolation::CubicSpline => 3,
+            _ => 1,
+        }
+    }
+
+    /// Returns the number of keyframes with both a time and a value.
+    pub fn len(&self) -> usize {
+        self.times.len().min(self.values.len() / self.stride())
+    }
+
+    /// Returns `true` if there are no keyframes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of keyframe `i`, or `None` if there is no such keyframe.
+    pub fn keyframe(&self, i: usize) -> Option<&T> {
+        match self.interpolation {
+            GltfInterpolation::CubicSpline => self.values.get(3 * i + 1),
+            _ => self.values.get(i),
+        }
+    }
+
+    /// Returns the keyframe before `time`, the normalized time until the next
+    /// and the duration between them,
+    /// or the keyframe to hold outside the keyframe range.
+    fn segment(&self, time: f32) -> Result<(usize, f32, f32), usize> {
+        let n = self.len();
+        let i = self.times.iter().take(n).take_while(|&&t| t <= time).count();
+        if i == 0 {
+            return Err(0);
+        } else if i >= n {
+            return Err(n - 1);
+        }
+        match (self.times.get(i - 1), self.times.get(i)) {
+            (Some(&t0), Some(&t1)) => Ok((i - 1, (time - t0) / (t1 - t0), t1 - t0)),
+            _ => Err(0),
+        }
+    }
+
+    fn sample_with<F>(&self, time: f32, linear: F) -> Option<T>
+        where F: Fn(&T, &T, f32) -> T, T: Spatial<Scalar = f32>
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let (i, t, td) = match self.segment(time) {
+            Ok(segment) => segment,
+            Err(i) => return self.keyframe(i).cloned(),
+        };
+        match self.interpolation {
+            GltfInterpolation::Step => self.keyframe(i).cloned(),
+            GltfInterpolation::Linear => Some(linear(self.keyframe(i)?, self.keyframe(i + 1)?, t)),
+            GltfInterpolation::CubicSpline => match self.values.get(3 * i + 1..3 * i + 5)? {
+                [v0, b0, a1, v1] => {
+                    // Tangents are scaled by the time between the keyframes.
+                    Some(hermite(v0, &b0.scale(&td), v1, &a1.scale(&td), &t))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the value at `time`, interpolating translations, scales and weights.
+    pub fn sample(&self, time: f32) -> Option<T>
+        where T: Lerp<Scalar = f32> + Spatial<Scalar = f32>
+    {
+        self.sample_with(time, |a, b, t| a.lerp(b, &t))
+    }
+
+    /// Returns the value at `time`, interpolating rotations spherically.
+    ///
+    /// Cubic spline results are not normalized.
+    pub fn sample_rotation(&self, time: f32) -> Option<T>
+        where T: Slerp<Scalar = f32> + Spatial<Scalar = f32>
+    {
+        self.sample_with(time, |a, b, t| a.slerp(b, &t))
+    }
+}
+
+#[test]
+fn gltf_step_linear() {
+    let mut sampler = GltfSampler::new(GltfInterpolation::Linear, vec![1.0, 2.0, 4.0], vec![0.0f32, 2.0, 6.0]);
+    assert_eq!(sampler.sample(0.0), Some(0.0));
+    assert_eq!(sampler.sample(1.5), Some(1.0));
+    assert_eq!(sampler.sample(3.0), Some(4.0));
+    assert_eq!(sampler.sample(5.0), Some(6.0));
+
+    sampler.interpolation = GltfInterpolation::Step;
+    assert_eq!(sampler.sample(3.9), Some(2.0));
+    assert_eq!(GltfSampler::<f32>::new(GltfInterpolation::Step, vec![], vec![]).sample(0.0), None);
+}
+
+#[test]
+fn gltf_cubic_spline() {
+    // A tangent of 1 per second over two seconds gives a straight line.
+    let values = vec![0.0, 0.0f32, 1.0, 1.0, 2.0, 0.0];
+    let sampler = GltfSampler::new(GltfInterpolation::CubicSpline, vec![0.0, 2.0], values);
+    assert_eq!(sampler.len(), 2);
+    assert_eq!(sampler.sample(0.5), Some(0.5));
+    assert_eq!(sampler.sample(1.0), Some(1.0));
+    assert_eq!(sampler.sample(2.0), Some(2.0));
+}
+
+#[test]
+fn gltf_rotation() {
+    let h = 0.5f32.sqrt();
+    let sampler = GltfSampler::new(GltfInterpolation::Linear, vec![0.0, 1.0],
+                                   vec![[0.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 0.0]]);
+    let q = sampler.sample_rotation(0.5).unwrap();
+    assert!((q[2] - h).abs() < 1e-6 && (q[3] - h).abs() < 1e-6);
+}
+
+#[test]
+fn gltf_truncated_values() {
+    // The last keyframe is missing its out-tangent.
+    let values = vec![0.0f32, 0.0, 1.0, 1.0, 2.0];
+    let sampler = GltfSampler::new(GltfInterpolation::CubicSpline, vec![0.0, 2.0], values);
+    assert_eq!(sampler.len(), 1);
+    assert_eq!(sampler.sample(1.0), Some(0.0));
+    assert_eq!(sampler.keyframe(2), None);
+    assert_eq!(sampler.sample(f32::NAN), Some(0.0));
+}
+
+#[test]
+fn gltf_try_new() {
+    use error::InterpolationError::*;
+
+    let values = vec![0.0f32, 0.0, 1.0, 1.0, 2.0];
+    assert_eq!(GltfSampler::try_new(GltfInterpolation::CubicSpline, vec![0.0, 2.0], values).err(),
+               Some(MismatchedLengths { expected: 6, found: 5 }));
+    assert_eq!(GltfSampler::try_new(GltfInterpolation::Step, vec![0.0, 0.0], vec![1.0f32, 2.0]).err(),
+               Some(NotIncreasing { index: 1 }));
+    assert_eq!(GltfSampler::<f32>::try_new(GltfInterpolation::Step, vec![], vec![]).err(), Some(Empty));
+    assert!(GltfSampler::try_new(GltfInterpolation::Linear, vec![0.0, 1.0], vec![1.0f32, 2.0]).is_ok());
+}
@@ -0,0 +1,179 @@
+//! Import of Godot curve resources
+//!
+//! Godot saves `Curve` and `Curve2D` resources as text in `.tres` files,
+//! with the points packed into a `_data` property.
+//! Both the Godot 3 and the Godot 4 formats are read.
+
+use alloc::vec::Vec;
+
+use bake::BakedTrack;
+use curve::{AnimationCurve, CurveKey};
+use path::{Path, PathPoint};
+
+/// A Godot `Curve` resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GodotCurve {
+    /// The points, with their left and right tangents.
+    pub curve: AnimationCurve,
+    /// The number of values Godot bakes the curve into.
+    pub bake_resolution: usize,
+    /// The start of the domain, `0` before Godot 4.3.
+    pub min_domain: f32,
+    /// The end of the domain, `1` before Godot 4.3.
+    pub max_domain: f32,
+}
+
+impl GodotCurve {
+    /// Parses the text of a `.tres` file holding a `Curve`.
+    ///
+    /// Returns `None` if there is no `_data` property with whole points.
+    pub fn from_tres(text: &str) -> Option<GodotCurve> {
+        // Each point is a position followed by the left and right tangents and modes.
+        let data = numbers(enclosed(property(text, "_data")?, '[', ']')?)?;
+        if data.len() % 6 != 0 {
+            return None;
+        }
+        let keys = data.chunks_exact(6).filter_map(|p| match *p {
+            [x, y, left, right, _, _] => Some(CurveKey::new(x, y, left, right)),
+            _ => None,
+        }).collect();
+        let number = |name| property(text, name).and_then(|v| numbers(v.lines().next()?)?.first().cloned());
+        Some(GodotCurve {
+            curve: AnimationCurve::new(keys),
+            bake_resolution: number("bake_resolution").map_or(100, |v| v as usize),
+            min_domain: number("min_domain").unwrap_or(0.0),
+            max_domain: number("max_domain").unwrap_or(1.0),
+        })
+    }
+
+    /// Bakes the curve over its domain at the bake resolution, like Godot does.
+    pub fn bake(&self) -> BakedTrack<f32> {
+        let n = self.bake_resolution.max(2);
+        let step = (self.max_domain - self.min_domain) / (n - 1) as f32;
+        BakedTrack::bake(self.min_domain, step, n, |t| self.curve.sample(t).unwrap_or(0.0))
+    }
+}
+
+/// A Godot `Curve2D` resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GodotCurve2D {
+    /// The points, with the handles converted to absolute control points.
+    pub path: Path<[f32; 2]>,
+    /// The distance between baked points, in pixels.
+    pub bake_interval: f32,
+}
+
+impl GodotCurve2D {
+    /// Parses the text of a `.tres` file holding a `Curve2D`.
+    ///
+    /// Returns `None` if there is no `points` array with whole points.
+    pub fn from_tres(text: &str) -> Option<GodotCurve2D> {
+        let data = property(text, "_data")?;
+        let points = data.get(data.find("\"points\"")?..)?;
+        // Each point is the in handle, the out handle and the position.
+        let data = numbers(enclosed(points, '(', ')')?)?;
+        if data.len() % 6 != 0 {
+            return None;
+        }
+        let points = data.chunks_exact(6).filter_map(|p| match *p {
+            [in_x, in_y, out_x, out_y, x, y] => Some(PathPoint {
+                position: [x, y],
+                in_control: [x + in_x, y + in_y],
+                out_control: [x + out_x, y + out_y],
+            }),
+            _ => None,
+        }).collect();
+        let bake_interval = property(text, "bake_interval")
+            .and_then(|v| numbers(v.lines().next()?)?.first().cloned())
+            .unwrap_or(5.0);
+        Some(GodotCurve2D { path: Path::new(points), bake_interval })
+    }
+}
+
+/// Returns the text after `name =` at the start of a line, up to the end.
+fn property<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let rest = line.trim_start();
+        if let Some(rest) = rest.strip_prefix(name) {
+            let rest = rest.trim_start();
+            if let Some(value) = rest.strip_prefix('=') {
+                let start = offset + line.len() - value.len();
+                return text.get(start..);
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Returns the text between the first `open` and the matching `close`.
+fn enclosed(text: &str, open: char, close: char) -> Option<&str> {
+    let start = text.find(open)? + open.len_utf8();
+    let mut depth = 1;
+    let inner = text.get(start..)?;
+    for (i, c) in inner.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return inner.get(..i);
+            }
+        }
+    }
+    None
+}
+
+/// Parses the numbers in a list, skipping type names such as `Vector2`.
+fn numbers(text: &str) -> Option<Vec<f32>> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '.' || c == '-' || c == '+' || c == '_'))
+        .filter(|s| !s.is_empty() && !s.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+        .map(|s| s.parse().ok())
+        .collect()
+}
+
+#[test]
+fn godot_curve() {
+    let tres = r#"[gd_resource type="Curve" format=3 uid="uid://b1x2"]
+
+[resource]
+max_value = 2.0
+bake_resolution = 11
+_data = [Vector2(0, 0), 0.0, 1.0, 0, 0, Vector2(1, 1), 1.0, 0.0, 0, 0]
+point_count = 2
+"#;
+    let curve = GodotCurve::from_tres(tres).unwrap();
+    assert_eq!(curve.curve.len(), 2);
+    assert_eq!(curve.bake_resolution, 11);
+    assert_eq!(curve.curve.sample(0.5), Some(0.5));
+    let baked = curve.bake();
+    assert_eq!(baked.values.len(), 11);
+    assert_eq!(baked.end(), 1.0);
+
+    let godot3 = "[resource]\n_data = [ Vector2( 0, 1 ), 0.0, 0.0, 0, 0, Vector2( 1, 0 ), 0.0, 0.0, 0, 0 ]\n";
+    let curve = GodotCurve::from_tres(godot3).unwrap();
+    assert_eq!(curve.bake_resolution, 100);
+    assert_eq!(curve.curve.sample(0.0), Some(1.0));
+    assert_eq!(GodotCurve::from_tres("[resource]\n"), None);
+}
+
+#[test]
+fn godot_curve_2d() {
+    let tres = r#"[gd_resource type="Curve2D" format=3]
+
+[resource]
+bake_interval = 2.5
+_data = {
+"points": PackedVector2Array(0, 0, 0, 10, 0, 0, 0, -10, 0, 0, 100, 50),
+"tilts": PackedFloat32Array(0, 0)
+}
+point_count = 2
+"#;
+    let curve = GodotCurve2D::from_tres(tres).unwrap();
+    assert_eq!(curve.bake_interval, 2.5);
+    let points = curve.path.points();
+    assert_eq!(points[0].out_control, [0.0, 10.0]);
+    assert_eq!(points[1].in_control, [100.0, 40.0]);
+    assert_eq!(curve.path.sample(1.0), Some([100.0, 50.0]));
+}
@@ -0,0 +1,149 @@
+//! Starting animations when others have finished
+
+use alloc::vec::Vec;
+
+use animate::Animate;
+
+/// Refers to a node in a `Group`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone)]
+struct Node<A> {
+    animation: Option<A>,
+    after: Vec<usize>,
+    started: bool,
+}
+
+/// Animations that wait for other animations to finish before starting.
+///
+/// Each node waits for all the nodes it is added after,
+/// which makes it a join of those nodes.
+/// A barrier is a node without an animation,
+/// used to wait for a set of nodes in one place.
+///
+/// A node starts in the update where its last dependency finishes
+/// and receives the whole time step of that update.
+#[derive(Debug, Clone)]
+pub struct Group<A> {
+    nodes: Vec<Node<A>>,
+}
+
+impl<A> Default for Group<A> {
+    fn default() -> Group<A> {
+        Group { nodes: Vec::new() }
+    }
+}
+
+impl<A> Group<A>
+    where A: Animate, A::Time: Copy
+{
+    /// Creates an empty group.
+    pub fn new() -> Group<A> {
+        Group::default()
+    }
+
+    fn push(&mut self, animation: Option<A>, after: &[NodeId]) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            animation,
+            after: after.iter().map(|n| n.0).filter(|&i| i < id.0).collect(),
+            started: false,
+        });
+        id
+    }
+
+    /// Adds an animation that starts right away.
+    pub fn add(&mut self, animation: A) -> NodeId {
+        self.push(Some(animation), &[])
+    }
+
+    /// Adds an animation that starts when all of `after` have finished.
+    /// Nodes of another group in `after` are ignored.
+    pub fn add_after(&mut self, animation: A, after: &[NodeId]) -> NodeId {
+        self.push(Some(animation), after)
+    }
+
+    /// Adds a node that finishes when all of `after` have finished.
+    pub fn barrier(&mut self, after: &[NodeId]) -> NodeId {
+        self.push(None, after)
+    }
+
+    /// Returns the animation of a node, or `None` for a barrier or a node of another group.
+    pub fn get(&self, id: NodeId) -> Option<&A> {
+        self.nodes.get(id.0).and_then(|node| node.animation.as_ref())
+    }
+
+    /// Returns `true` if the node has started.
+    pub fn is_started(&self, id: NodeId) -> bool {
+        self.nodes.get(id.0).is_some_and(|node| node.started)
+    }
+
+    /// Returns `true` if the node has started and its animation has finished.
+    pub fn is_node_finished(&self, id: NodeId) -> bool {
+        self.nodes.get(id.0).is_some_and(|node| {
+            node.started && node.animation.as_ref().is_none_or(|a| a.is_finished())
+        })
+    }
+
+    /// Returns `true` when every node has finished.
+    pub fn is_finished(&self) -> bool {
+        (0..self.nodes.len()).all(|i| self.is_node_finished(NodeId(i)))
+    }
+
+    /// Starts the nodes that are ready and advances the started animations by `dt`.
+    pub fn update(&mut self, dt: A::Time) {
+        // Dependencies are always added before the nodes waiting for them,
+        // so one pass in order starts every node that became ready.
+        for i in 0..self.nodes.len() {
+            let ready = self.nodes.get(i).is_some_and(|node| {
+                node.started || node.after.iter().all(|&j| self.is_node_finished(NodeId(j)))
+            });
+            if let (true, Some(node)) = (ready, self.nodes.get_mut(i)) {
+                node.started = true;
+                if let Some(ref mut animation) = node.animation {
+                    animation.update(dt);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn group_join() {
+    use tween::Tween;
+
+    let mut group = Group::new();
+    let a = group.add(Tween::new(0.0f32, 1.0, 1.0));
+    let b = group.add(Tween::new(0.0f32, 1.0, 2.0));
+    let all = group.barrier(&[a, b]);
+    let c = group.add_after(Tween::new(0.0f32, 1.0, 1.0), &[all]);
+
+    group.update(1.0);
+    assert!(group.is_node_finished(a));
+    assert!(!group.is_started(c));
+    group.update(0.5);
+    assert!(!group.is_started(all));
+    group.update(0.5);
+    assert!(group.is_node_finished(all));
+    assert_eq!(group.get(c).map(|t| t.value()), Some(0.5));
+    assert!(!group.is_finished());
+    group.update(0.5);
+    assert!(group.is_finished());
+}
+
+#[test]
+fn group_foreign_node() {
+    use tween::Tween;
+
+    let mut other = Group::<Tween<f32>>::new();
+    other.add(Tween::new(0.0, 1.0, 1.0));
+    let foreign = other.add(Tween::new(0.0, 1.0, 1.0));
+
+    let mut group = Group::new();
+    assert!(group.get(foreign).is_none());
+    assert!(!group.is_started(foreign));
+    let a = group.add_after(Tween::new(0.0f32, 1.0, 1.0), &[foreign]);
+    group.update(1.0);
+    assert!(group.is_node_finished(a));
+}
@@ -0,0 +1,37 @@
+//! Cubic Hermite interpolation
+
+use float::Float;
+use spatial::Spatial;
+
+/// Performs cubic Hermite interpolation from `p0` to `p1`,
+/// with the tangents `m0` at the start and `m1` at the end.
+///
+/// The tangents are derivatives with respect to `t`, which goes from 0 to 1.
+/// Keyframes with tangents per second, such as glTF cubic splines,
+/// need their tangents scaled by the time between the keyframes.
+/// The same curve is a cubic Bézier curve with the control points
+/// `p0`, `p0 + m0 / 3`, `p1 - m1 / 3` and `p1`.
+#[inline(always)]
+pub fn hermite<T>(p0: &T, m0: &T, p1: &T, m1: &T, t: &T::Scalar) -> T
+    where T: Spatial, T::Scalar: Float
+{
+    let (two, three) = (T::Scalar::from_f64(2.0), T::Scalar::from_f64(3.0));
+    let t = *t;
+    let (t2, t3) = (t * t, t * t * t);
+    let v = m0.mul_add(&(t3 - two * t2 + t), &p0.scale(&(two * t3 - three * t2 + T::Scalar::one())));
+    let v = p1.mul_add(&(three * t2 - two * t3), &v);
+    m1.mul_add(&(t3 - t2), &v)
+}
+
+#[test]
+fn hermite_curve() {
+    assert_eq!(hermite(&0.0f32, &1.0, &1.0, &1.0, &0.25), 0.25);
+    assert_eq!(hermite(&[1.0f64, 2.0], &[0.0, 0.0], &[3.0, -2.0], &[0.0, 0.0], &0.5), [2.0, 0.0]);
+    assert_eq!(hermite(&[1.0f64, 2.0], &[5.0, 5.0], &[3.0, -2.0], &[7.0, 7.0], &1.0), [3.0, -2.0]);
+    let (p0, m0, p1, m1) = (0.5f64, 3.0, -1.0, 6.0);
+    for i in 0..=8 {
+        let t = i as f64 / 8.0;
+        let b = ::cub_bez(&p0, &(p0 + m0 / 3.0), &(p1 - m1 / 3.0), &p1, &t);
+        assert!((hermite(&p0, &m0, &p1, &m1, &t) - b).abs() < 1e-12);
+    }
+}
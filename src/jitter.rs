@@ -0,0 +1,150 @@
+//! Randomized variation of tweens
+
+use rand::distributions::uniform::SampleUniform;
+use rand::Rng;
+
+use animate::Animate;
+use ease::Ease;
+use float::Float;
+use lerp::Lerp;
+use tween::Tween;
+
+/// Describes how much to randomize each instance of a tween,
+/// so crowds of animated objects do not move in lockstep.
+///
+/// Pass a seeded random number generator to get the same variation every run.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Jitter<S> {
+    /// The largest change of the duration, as a fraction of the duration.
+    pub duration: S,
+    /// The largest extra delay.
+    pub delay: S,
+    /// The largest deviation of the eased progress halfway through.
+    pub noise: S,
+}
+
+impl<S> Jitter<S>
+    where S: Float + SampleUniform
+{
+    /// Creates a jitter that does not change anything.
+    pub fn new() -> Jitter<S> {
+        Jitter {
+            duration: S::zero(),
+            delay: S::zero(),
+            noise: S::zero(),
+        }
+    }
+
+    /// Returns a randomized copy of `tween`.
+    ///
+    /// Negative fields count by their size, and NaN or infinite ones as zero.
+    /// The duration does not become negative, even if it may change by more than all of it.
+    pub fn apply<T, R>(&self, tween: &Tween<T>, rng: &mut R) -> Jittered<T>
+        where T: Lerp<Scalar = S> + Clone, S: Ease, R: Rng + ?Sized
+    {
+        let (zero, one) = (S::zero(), S::one());
+        let mut tween = tween.clone();
+        let scale = one + rng.gen_range(-one..=one) * magnitude(self.duration);
+        tween.duration = tween.duration * if scale > zero { scale } else { zero };
+        tween.delay = tween.delay + rng.gen_range(zero..=one) * magnitude(self.delay);
+        Jittered {
+            tween,
+            noise: rng.gen_range(-one..=one) * magnitude(self.noise),
+        }
+    }
+}
+
+/// Returns the size of `x`, or zero if it is NaN or infinite.
+fn magnitude<S: Float>(x: S) -> S {
+    if !x.to_f64().is_finite() {
+        S::zero()
+    } else if x < S::zero() {
+        -x
+    } else {
+        x
+    }
+}
+
+impl<S> Default for Jitter<S>
+    where S: Float + SampleUniform
+{
+    fn default() -> Jitter<S> {
+        Jitter::new()
+    }
+}
+
+/// A tween with a random deviation of its eased progress.
+///
+/// The deviation is largest halfway and vanishes at the ends,
+/// so the start and end values are reached exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jittered<T: Lerp> {
+    /// The randomized tween.
+    pub tween: Tween<T>,
+    /// The deviation of the eased progress halfway through.
+    pub noise: T::Scalar,
+}
+
+impl<T> Animate for Jittered<T>
+    where T: Lerp, T::Scalar: Float + Ease
+{
+    type Value = T;
+    type Time = T::Scalar;
+
+    fn update(&mut self, dt: T::Scalar) {
+        self.tween.update(dt)
+    }
+
+    fn value(&self) -> T {
+        let one = T::Scalar::one();
+        let four = one + one + one + one;
+        let p = self.tween.easing.calc(self.tween.progress());
+        let p = p + self.noise * four * p * (one - p);
+        self.tween.from.lerp(&self.tween.to, &p)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.tween.is_finished()
+    }
+}
+
+#[test]
+fn jitter_deterministic() {
+    use rand::rngs::mock::StepRng;
+
+    let jitter = Jitter { duration: 0.5f64, delay: 1.0, noise: 0.25 };
+    let tween = Tween::new(0.0, 1.0, 2.0);
+    let a = jitter.apply(&tween, &mut StepRng::new(1 << 62, 1 << 61));
+    let b = jitter.apply(&tween, &mut StepRng::new(1 << 62, 1 << 61));
+    assert_eq!(a, b);
+    assert!(a.tween.duration >= 1.0 && a.tween.duration <= 3.0);
+    assert!(a.tween.delay >= 0.0 && a.tween.delay <= 1.0);
+
+    let mut a = a;
+    assert_eq!(a.value(), 0.0);
+    a.update(10.0);
+    assert_eq!(a.value(), 1.0);
+}
+
+#[test]
+fn jitter_out_of_range() {
+    use rand::rngs::mock::StepRng;
+
+    let tween = Tween::new(0.0f32, 1.0, 2.0);
+    let jitter = Jitter { duration: -0.5f32, delay: -1.0, noise: -0.25 };
+    let a = jitter.apply(&tween, &mut StepRng::new(1 << 62, 1 << 61));
+    assert!(a.tween.duration >= 1.0 && a.tween.duration <= 3.0);
+    assert!(a.tween.delay >= 0.0 && a.tween.delay <= 1.0);
+    assert!(a.noise.abs() <= 0.25);
+
+    let jitter = Jitter { duration: f32::NAN, delay: f32::INFINITY, noise: f32::NAN };
+    let a = jitter.apply(&tween, &mut StepRng::new(1 << 62, 1 << 61));
+    assert_eq!((a.tween.duration, a.tween.delay, a.noise), (2.0, 0.0, 0.0));
+
+    // A change of more than all of the duration stops at zero.
+    let jitter = Jitter { duration: 3.0f32, delay: f32::MAX, noise: 0.0 };
+    for seed in 0..16 {
+        let a = jitter.apply(&tween, &mut StepRng::new(seed << 60, 1 << 59));
+        assert!(a.tween.duration >= 0.0 && a.tween.delay >= 0.0);
+    }
+}
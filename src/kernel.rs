@@ -0,0 +1,132 @@
+//! Separable reconstruction kernels for resampling
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use spatial::Spatial;
+
+/// A reconstruction filter, applied separately along each axis when resampling.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum Kernel {
+    /// Linear interpolation between neighbours.
+    Triangle,
+    /// The Catmull-Rom spline, sharp and interpolating.
+    CatmullRom,
+    /// The Mitchell-Netravali filter with `B = C = 1/3`, a compromise
+    /// between blurring and ringing.
+    Mitchell,
+    /// The Lanczos filter with three lobes, sharp with some ringing.
+    Lanczos3,
+}
+
+impl Kernel {
+    /// Returns the radius outside which the weight is zero.
+    pub fn support(self) -> f32 {
+        match self {
+            Kernel::Triangle => 1.0,
+            Kernel::CatmullRom | Kernel::Mitchell => 2.0,
+            Kernel::Lanczos3 => 3.0,
+        }
+    }
+
+    /// Returns the weight at distance `x` from the sample.
+    pub fn weight(self, x: f32) -> f32 {
+        let x = x.abs();
+        if x >= self.support() {
+            return 0.0;
+        }
+        match self {
+            Kernel::Triangle => 1.0 - x,
+            Kernel::CatmullRom => cubic(0.0, 0.5, x),
+            Kernel::Mitchell => cubic(1.0 / 3.0, 1.0 / 3.0, x),
+            Kernel::Lanczos3 => sinc(x) * sinc(x / 3.0),
+        }
+    }
+
+    /// Returns the first source index and the normalized weights
+    /// of each destination sample, when resampling `src_len` samples to `dst_len`.
+    ///
+    /// Samples are centered in their cells,
+    /// and the kernel is widened when downsampling to avoid aliasing.
+    pub fn weights(self, src_len: usize, dst_len: usize) -> Vec<(usize, Vec<f32>)> {
+        let scale = src_len as f32 / dst_len as f32;
+        let filter_scale = scale.max(1.0);
+        let radius = self.support() * filter_scale;
+        (0..dst_len).map(|i| {
+            let center = (i as f32 + 0.5) * scale - 0.5;
+            let start = (Float::ceil(center - radius).max(0.0)) as usize;
+            let end = (Float::floor(center + radius) as usize).min(src_len.saturating_sub(1));
+            let mut weights: Vec<f32> = (start..=end)
+                .map(|j| self.weight((j as f32 - center) / filter_scale))
+                .collect();
+            let sum: f32 = weights.iter().sum();
+            if sum != 0.0 {
+                for w in &mut weights {
+                    *w /= sum;
+                }
+            }
+            (start, weights)
+        }).collect()
+    }
+
+    /// Resamples a sequence of values to `len` values.
+    pub fn resample<T>(self, src: &[T], len: usize) -> Vec<T>
+        where T: Spatial<Scalar = f32> + Clone
+    {
+        if src.is_empty() {
+            return vec![];
+        }
+        self.weights(src.len(), len).into_iter().filter_map(|(start, weights)| {
+            let mut terms = src.iter().skip(start).zip(&weights).map(|(v, w)| v.scale(w));
+            let first = terms.next()?;
+            Some(terms.fold(first, |sum, v| sum.add(&v)))
+        }).collect()
+    }
+}
+
+/// The Mitchell-Netravali family of cubic filters.
+fn cubic(b: f32, c: f32, x: f32) -> f32 {
+    let (x2, x3) = (x * x, x * x * x);
+    let w = if x < 1.0 {
+        (12.0 - 9.0 * b - 6.0 * c) * x3 + (-18.0 + 12.0 * b + 6.0 * c) * x2 + (6.0 - 2.0 * b)
+    } else {
+        (-b - 6.0 * c) * x3 + (6.0 * b + 30.0 * c) * x2
+            + (-12.0 * b - 48.0 * c) * x + (8.0 * b + 24.0 * c)
+    };
+    w / 6.0
+}
+
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let x = x * ::core::f32::consts::PI;
+        Float::sin(x) / x
+    }
+}
+
+#[test]
+fn kernel_weights() {
+    for &kernel in &[Kernel::Triangle, Kernel::CatmullRom, Kernel::Mitchell, Kernel::Lanczos3] {
+        assert!(kernel.weight(kernel.support()) == 0.0);
+        for (_, weights) in kernel.weights(10, 23) {
+            assert!((weights.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        }
+    }
+    assert_eq!(Kernel::CatmullRom.weight(0.0), 1.0);
+    assert!(Kernel::CatmullRom.weight(1.0).abs() < 1e-6);
+    assert!(Kernel::Lanczos3.weight(2.0).abs() < 1e-6);
+}
+
+#[test]
+fn kernel_resample() {
+    let src = [1.0f32, 1.0, 1.0, 1.0];
+    assert!(Kernel::Lanczos3.resample(&src, 7).iter().all(|v| (v - 1.0).abs() < 1e-6));
+    assert_eq!(Kernel::Triangle.resample(&[0.0f32, 4.0], 4), vec![0.0, 1.0, 3.0, 4.0]);
+    let down = Kernel::Mitchell.resample(&[[0.0f32, 2.0], [2.0, 0.0]], 1);
+    assert!((down[0][0] - 1.0).abs() < 1e-6 && (down[0][1] - 1.0).abs() < 1e-6);
+}
@@ -1,5 +1,7 @@
 //! Linear interpolation
 
+use rounding::{Rounding, RoundingMode};
+
 /// Performs linear interpolation.
 /// A linear interpolation consists of two states 'a' and 'b'.
 /// The 't' variable is a factor between 0 and 1 that
@@ -38,49 +40,83 @@ macro_rules! impl_lerp_for_float {
 impl_lerp_for_float!(f32);
 impl_lerp_for_float!(f64);
 
-/// Implementation of `Lerp` for signed integers.
+/// Describes a type that can linearly interpolate with a choice of rounding.
+pub trait LerpRound: Lerp {
+    /// Like `lerp`, rounding the result with `rounding`.
+    fn lerp_round<R>(&self, other: &Self, scalar: &Self::Scalar, rounding: &mut R) -> Self
+        where R: Rounding<Self::Scalar>;
+}
+
+/// Implementation of `Lerp` for integers.
 /// This will cast the int to the Scalar before multiplying and rounding to the nearest value.
+///
+/// The difference is computed in a wider integer type, so it does not overflow
+/// for endpoints far apart, such as `MIN` and `MAX`.
+/// `lerp` rounds to the nearest integer, with halfway cases away from `self`,
+/// so the midpoint of `0` and `1` is `1` and the midpoint of `1` and `0` is `0`.
+/// Other rounding is available with `lerp_round`.
+///
+/// The distance cast to the Scalar can round past the endpoint for large values,
+/// so between zero and one the result is limited to the endpoints,
+/// and is exactly `other` when `scalar` is one.
+/// This keeps the results monotonic in `scalar`.
+/// Above one, the result saturates at the bounds of the type.
+/// Below zero, signed results saturate too, while unsigned ones are `self`.
 macro_rules! impl_lerp_for_int {
-    ($int: ident, $scalar: ident) => (
+    (@below signed, $a: ident, $d: ident, $min: ident, $max: ident) => (
+        if $d < 0 { ($a, $max) } else { ($min, $a) }
+    );
+    (@below unsigned, $a: ident, $d: ident, $min: ident, $max: ident) => (
+        ($a, $a)
+    );
+    ($int: ident, $wide: ident, $scalar: ident, $sign: ident) => (
         impl Lerp for $int {
             type Scalar = $scalar;
 
             #[inline(always)]
             fn lerp(&self, other: &$int, scalar: &$scalar) -> $int {
-                self + ((other - self) as $scalar * scalar).round() as $int
+                self.lerp_round(other, scalar, &mut RoundingMode::Nearest)
             }
         }
-    )
-}
-
-impl_lerp_for_int!(i8,  f32);
-impl_lerp_for_int!(i16, f32);
-impl_lerp_for_int!(i32, f32);
-impl_lerp_for_int!(i64, f64);
-
-/// Implementation of `Lerp` for unsigned integers.
-/// Will cast the uint to the Scalar before multiplying and rounding to the nearest value.
-macro_rules! impl_lerp_for_uint {
-    ($uint: ident, $scalar: ident) => (
-        impl Lerp for $uint {
-            type Scalar = $scalar;
 
+        impl LerpRound for $int {
             #[inline(always)]
-            fn lerp(&self, other: &$uint, scalar: &$scalar) -> $uint {
-                if self <= other {
-                    self + ((other - self) as $scalar * scalar).round() as $uint
+            fn lerp_round<R>(&self, other: &$int, scalar: &$scalar, rounding: &mut R) -> $int
+                where R: Rounding<$scalar>
+            {
+                let a = *self as $wide;
+                let b = *other as $wide;
+                let d = b - a;
+                let y = d as $scalar * scalar;
+                let floor = ::num_traits::Float::floor(y);
+                let frac = y - floor;
+                let lo = a.saturating_add(floor as $wide);
+                let x = if frac > 0.0 && rounding.round_up(frac, lo & 1 != 0, y > 0.0) {
+                    lo.saturating_add(1)
                 } else {
-                    self - ((self - other) as $scalar * scalar).round() as $uint
-                }
+                    lo
+                };
+                let (min, max) = ($int::MIN as $wide, $int::MAX as $wide);
+                let (lo, hi) = match () {
+                    _ if *scalar == 1.0 => return *other,
+                    _ if *scalar > 1.0 => if d < 0 { (min, b) } else { (b, max) },
+                    _ if *scalar < 0.0 => impl_lerp_for_int!(@below $sign, a, d, min, max),
+                    _ => if d < 0 { (b, a) } else { (a, b) },
+                };
+                x.max(lo).min(hi) as $int
             }
         }
     )
 }
 
-impl_lerp_for_uint!(u8,  f32);
-impl_lerp_for_uint!(u16, f32);
-impl_lerp_for_uint!(u32, f32);
-impl_lerp_for_uint!(u64, f64);
+impl_lerp_for_int!(i8,  i16,  f32, signed);
+impl_lerp_for_int!(i16, i32,  f32, signed);
+impl_lerp_for_int!(i32, i64,  f32, signed);
+impl_lerp_for_int!(i64, i128, f64, signed);
+impl_lerp_for_int!(u8,  i16,  f32, unsigned);
+impl_lerp_for_int!(u16, i32,  f32, unsigned);
+impl_lerp_for_int!(u32, i64,  f32, unsigned);
+impl_lerp_for_int!(u64, i128, f64, unsigned);
 
 /// Transitive impl of `Lerp` for arrays, given a length and index list
 macro_rules! impl_lerp_for_array {
@@ -98,11 +134,32 @@ macro_rules! impl_lerp_for_array {
     }
 }
 
+/// Transitive impl of `LerpRound` for arrays, given a length and index list
+macro_rules! impl_lerp_round_for_array {
+    ($len:expr; $($i:expr),*) => {
+        impl<T> LerpRound for [T; $len] where T: LerpRound {
+            #[inline(always)]
+            fn lerp_round<R>(&self, other: &Self, scalar: &Self::Scalar, rounding: &mut R) -> Self
+                where R: Rounding<Self::Scalar>
+            {
+                [
+                    $(self[$i].lerp_round(&other[$i], scalar, rounding)),*
+                ]
+            }
+        }
+    }
+}
+
 impl_lerp_for_array!(1; 0);
 impl_lerp_for_array!(2; 0, 1);
 impl_lerp_for_array!(3; 0, 1, 2);
 impl_lerp_for_array!(4; 0, 1, 2, 3);
 impl_lerp_for_array!(5; 0, 1, 2, 3, 4);
+impl_lerp_round_for_array!(1; 0);
+impl_lerp_round_for_array!(2; 0, 1);
+impl_lerp_round_for_array!(3; 0, 1, 2);
+impl_lerp_round_for_array!(4; 0, 1, 2, 3);
+impl_lerp_round_for_array!(5; 0, 1, 2, 3, 4);
 
 #[test]
 fn lerp_f32() {
@@ -242,6 +299,55 @@ fn lerp_i64() {
     }
 }
 
+#[test]
+fn lerp_i8_exhaustive_endpoints() {
+    for a in i8::MIN ..= i8::MAX {
+        for b in i8::MIN ..= i8::MAX {
+            assert_eq!(lerp(&a, &b, &0.0), a);
+            assert_eq!(lerp(&a, &b, &1.0), b);
+        }
+    }
+}
+
+#[test]
+fn lerp_int_extremes() {
+    macro_rules! check {
+        ($int: ident, $scalar: ident) => {{
+            let edges = [$int::MIN, $int::MIN + 1, -1, 0, 1, $int::MAX - 1, $int::MAX];
+            for &a in &edges {
+                for &b in &edges {
+                    assert_eq!(lerp(&a, &b, &0.0), a);
+                    assert_eq!(lerp(&a, &b, &1.0), b);
+                    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+                    let mut prev = lerp(&a, &b, &-2.0);
+                    for i in -1000 ..= 2000 {
+                        let x = lerp(&a, &b, &(i as $scalar / 1000.0));
+                        if (0 ..= 1000).contains(&i) {
+                            assert!((lo ..= hi).contains(&x));
+                        }
+                        assert!(if a <= b { x >= prev } else { x <= prev });
+                        prev = x;
+                    }
+                }
+            }
+            assert_eq!(lerp(&$int::MIN, &$int::MAX, &2.0), $int::MAX);
+            assert_eq!(lerp(&$int::MIN, &$int::MAX, &-1.0), $int::MIN);
+        }}
+    }
+
+    check!(i8, f32);
+    check!(i16, f32);
+    check!(i32, f32);
+    check!(i64, f64);
+    assert_eq!(lerp(&i8::MIN, &i8::MAX, &0.5), 0);
+    assert_eq!(lerp(&i32::MIN, &i32::MAX, &0.5), 0);
+    assert_eq!(lerp(&i64::MIN, &i64::MAX, &0.5), 0);
+    assert_eq!(lerp(&i64::MAX, &i64::MIN, &0.5), -1);
+    assert_eq!(lerp(&0i32, &16_777_217, &1.0), 16_777_217);
+    assert_eq!(lerp(&0i32, &1, &0.5), 1);
+    assert_eq!(lerp(&1i32, &0, &0.5), 0);
+}
+
 #[test]
 fn lerp_u8() {
     for x in 0 ..= 10 {
@@ -294,6 +400,83 @@ fn lerp_u64() {
     }
 }
 
+#[test]
+fn lerp_u8_exhaustive_endpoints() {
+    for a in 0 ..= u8::MAX {
+        for b in 0 ..= u8::MAX {
+            assert_eq!(lerp(&a, &b, &0.0), a);
+            assert_eq!(lerp(&a, &b, &1.0), b);
+        }
+    }
+}
+
+#[test]
+fn lerp_uint_extremes() {
+    macro_rules! check {
+        ($uint: ident, $scalar: ident) => {{
+            let edges = [0, 1, 2, $uint::MAX / 2, $uint::MAX / 2 + 1,
+                         $uint::MAX - 2, $uint::MAX - 1, $uint::MAX];
+            for &a in &edges {
+                for &b in &edges {
+                    assert_eq!(lerp(&a, &b, &0.0), a);
+                    assert_eq!(lerp(&a, &b, &1.0), b);
+                    assert_eq!(lerp(&a, &b, &-1.0), a);
+                    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+                    let mut prev = a;
+                    for i in 0 ..= 1000 {
+                        let x = lerp(&a, &b, &(i as $scalar / 1000.0));
+                        assert!((lo ..= hi).contains(&x));
+                        assert!(if a <= b { x >= prev } else { x <= prev });
+                        prev = x;
+                    }
+                    assert!(if a <= b { lerp(&a, &b, &2.0) >= b } else { lerp(&a, &b, &2.0) <= b });
+                }
+            }
+        }}
+    }
+
+    check!(u8, f32);
+    check!(u16, f32);
+    check!(u32, f32);
+    check!(u64, f64);
+    assert_eq!(lerp(&u32::MAX, &0, &0.0), u32::MAX);
+    assert_eq!(lerp(&1u32, &u32::MAX, &1.0), u32::MAX);
+    assert_eq!(lerp(&1u64, &u64::MAX, &1.0), u64::MAX);
+    assert_eq!(lerp(&0u32, &u32::MAX, &2.0), u32::MAX);
+    assert_eq!(lerp(&0u32, &16_777_217, &1.0), 16_777_217);
+    assert_eq!(lerp(&2u8, &3, &-1.0), 2);
+    assert_eq!(lerp(&1u8, &3, &-0.4), 1);
+    assert_eq!(lerp(&16_777_217u32, &0, &1.0), 0);
+}
+
+#[test]
+fn lerp_round_modes() {
+    let mut nearest = RoundingMode::Nearest;
+    let mut even = RoundingMode::NearestEven;
+    let mut floor = RoundingMode::Floor;
+    let mut ceil = RoundingMode::Ceil;
+
+    for &(a, b) in &[(0i32, 5), (5, 0), (-5, 0), (0, -5), (-3, 4)] {
+        for i in 0 ..= 10 {
+            let t = i as f32 / 10.0;
+            let x = a as f32 + (b - a) as f32 * t;
+            assert_eq!(a.lerp_round(&b, &t, &mut nearest), a.lerp(&b, &t));
+            assert_eq!(a.lerp_round(&b, &t, &mut floor), x.floor() as i32);
+            assert_eq!(a.lerp_round(&b, &t, &mut ceil), x.ceil() as i32);
+            assert_eq!(a.lerp_round(&b, &t, &mut even), x.round_ties_even() as i32);
+        }
+    }
+    assert_eq!(0u8.lerp_round(&5, &0.3, &mut even), 2);
+    assert_eq!(0u8.lerp_round(&5, &0.5, &mut even), 2);
+    assert_eq!(1u8.lerp_round(&0, &0.5, &mut even), 0);
+    assert_eq!(0u8.lerp_round(&255, &0.999, &mut floor), 254);
+    assert_eq!(0u8.lerp_round(&255, &1.0, &mut floor), 255);
+    assert_eq!(u64::MAX.lerp_round(&0, &0.0, &mut ceil), u64::MAX);
+    assert_eq!(5u8.lerp_round(&9, &-0.5, &mut floor), 5);
+    assert_eq!(5i8.lerp_round(&9, &-0.5, &mut floor), 3);
+    assert_eq!([0u8, 10].lerp_round(&[255, 20], &0.5, &mut floor), [127, 15]);
+}
+
 #[test]
 fn lerp_array_2() {
     for x in 0 ..= 10 {
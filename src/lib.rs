@@ -1,4 +1,7 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
+#![deny(clippy::unwrap_used, clippy::expect_used, clippy::panic, clippy::indexing_slicing)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 //! Interpolation algorithms.
 //!
@@ -10,18 +13,281 @@
 //! controlling the mixture of states.
 //! The choice of interpolation algorithm depends often
 //! on the circumstances where it used.
+//!
+//! The common traits and types can be imported with `use interpolation::prelude::*;`.
+//!
+//! Without the default `std` feature, the crate is `no_std` and never allocates.
+//! The core of easing, lerp, tweens, Bézier curves and transitions stays available,
+//! with `libm` providing the math functions.
+//! Keyframe tracks, timelines, paths, baked tables and importers store their keys
+//! in a `Vec`, and need the `alloc` feature. Recording and plotting need `std`.
+//!
+//! The ease functions use the math functions of `num_traits::Float`.
+//! These come from `std` by default, or from `libm` with the `libm` feature.
+//! Other backends, such as platform intrinsics or fast approximations,
+//! can be plugged in with a scalar type that implements `num_traits::Float`.
+//! With the `deterministic` feature, the easings use their own `sin`, `cos`
+//! and powers of two instead, built from correctly rounded arithmetic,
+//! so the results are bit-identical on every platform,
+//! for example for lockstep multiplayer simulations.
+//! With the `fma` feature, polynomial easings, cubic Bézier easings and Hermite curves
+//! use fused multiply-add, which rounds once instead of twice.
+//! This changes the results in the last bits, and is only fast on targets
+//! with a hardware instruction for it, such as `x86_64` with `target-cpu=native`.
+//!
+//! Evaluation never panics, so it is safe on real-time threads such as audio callbacks.
+//! Out of range indices, empty inputs and NaN give `None`, an empty result
+//! or a held value instead. This is enforced with clippy lints,
+//! which only allow `unwrap`, `expect` and indexing in tests.
+//! The only exception is the `check-finite` feature, which makes debug builds
+//! assert that the progress passed to easings is not NaN or infinite.
+//! Constructors named `try_new` check their input up front,
+//! and return an `InterpolationError` describing what is wrong.
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled");
 
-pub use ease::{ Ease, EaseFunction };
-pub use lerp::{lerp, Lerp};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "serde")]
+extern crate base64;
+#[cfg(feature = "bincode")]
+extern crate bincode;
+#[cfg(feature = "cgmath")]
+extern crate cgmath;
+#[cfg(any(feature = "std", test))]
+extern crate core;
+#[cfg(feature = "dasp")]
+extern crate dasp;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "euclid")]
+extern crate euclid;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "kurbo")]
+extern crate kurbo;
+#[cfg(all(test, loom))]
+extern crate loom;
+#[cfg(feature = "lyon_geom")]
+extern crate lyon_geom;
+#[cfg(feature = "mint")]
+extern crate mint;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "rational")]
+extern crate num_integer;
+#[cfg(feature = "rational")]
+extern crate num_rational;
+extern crate num_traits;
+#[cfg(feature = "plotters")]
+extern crate plotters;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+#[cfg(feature = "ultraviolet")]
+extern crate ultraviolet;
+#[cfg(test)]
+extern crate vecmath;
+#[cfg(feature = "wasm-bindgen")]
+extern crate wasm_bindgen;
 
+pub use animate::{Animate, Tagged};
+pub use arc_length::{ParametricCurve, PreparedCurve};
+#[cfg(feature = "alloc")]
+pub use arena::{Arena, Handle};
+#[cfg(feature = "alloc")]
+pub use bake::{BakedTrack, EaseTable};
+#[cfg(feature = "rkyv")]
+pub use bake::{ArchivedBakedTrack, ArchivedEaseTable};
+pub use batch::{ease_slice, lerp_slice, lerp_soa, lerp_soa_channels, smoothstep_slice};
+pub use bezier::{bezier, bezier_in_place, CubicBezier, QuadraticBezier};
+#[cfg(feature = "alloc")]
+pub use bezier::elevate_degree;
+pub use bezier_ease::CubicBezierEase;
+pub use blender::TransitionBlender;
+#[cfg(feature = "alloc")]
+pub use bspline::BSpline;
+pub use camera::CameraBlend;
+pub use catmull_rom::{catmull_rom, catmull_rom_bezier};
+pub use changes::{ChangeFilter, Changes};
+pub use clothoid::Clothoid;
+pub use conic::RationalQuadraticBezier;
+#[cfg(feature = "alloc")]
+pub use curve::{AnimationCurve, CurveKey};
+pub use ease::{ Ease, EaseFunction, Easing, StepPosition };
+pub use elliptical_arc::EllipticalArc;
+pub use error::InterpolationError;
+#[cfg(feature = "dasp")]
+pub use ext::dasp::{CubicInterpolator, EasedInterpolator};
+#[cfg(feature = "image")]
+pub use ext::image::resize_image;
+pub use float::Float;
+#[cfg(feature = "alloc")]
+pub use gltf::{GltfInterpolation, GltfSampler};
+#[cfg(feature = "alloc")]
+pub use godot::{GodotCurve, GodotCurve2D};
+#[cfg(feature = "alloc")]
+pub use group::{Group, NodeId};
+pub use hermite::hermite;
+#[cfg(feature = "rand")]
+pub use jitter::{Jitter, Jittered};
+#[cfg(feature = "alloc")]
+pub use kernel::Kernel;
+pub use lerp::{lerp, Lerp, LerpRound};
+#[cfg(feature = "alloc")]
+pub use lottie::{lottie_timeline, LottieHandle, LottieKeyframe};
+#[cfg(feature = "serde")]
+pub use lut_serde::{LutSample, Readable};
+#[cfg(feature = "alloc")]
+pub use morph::{lerp_weights, MorphWeights};
+#[cfg(feature = "alloc")]
+pub use nurbs::Nurbs;
+pub use oscillator::{Oscillator, Waveform};
+#[cfg(feature = "rayon")]
+pub use par::{par_bake, par_record};
+pub use patch::{BezierPatch, CoonsPatch};
+#[cfg(feature = "alloc")]
+pub use path::{Path, PathPoint};
+pub use poly::{cub_bez_poly, quad_bez_poly, CubicPoly, QuadraticPoly};
+#[cfg(feature = "std")]
+pub use record::{export_csv, export_tsv, record, trace, Columns};
+pub use rounding::{Rounding, RoundingMode};
+#[cfg(feature = "rand")]
+pub use rounding::Stochastic;
+#[cfg(feature = "alloc")]
+pub use sharded::{ShardedArena, ShardedHandle};
+#[cfg(target_has_atomic = "32")]
+pub use shared::{AtomicTime, SharedTween};
+pub use slerp::{slerp, Slerp};
+pub use spatial::{InnerSpace, Planar, Spatial};
+#[cfg(feature = "alloc")]
+pub use step_track::StepTrack;
+#[cfg(feature = "alloc")]
+pub use tcb::{TcbKey, TcbSpline};
+#[cfg(feature = "alloc")]
+pub use threshold::{Crossing, Crossings, Thresholds};
+#[cfg(feature = "alloc")]
+pub use timeline::{Repeat, Timeline};
+pub use transform::{Transform, TransformTween};
+pub use transition::Transition;
+pub use tween::Tween;
+#[cfg(feature = "alloc")]
+pub use unity::{unity_curve, UnityCurve, UnityKeyframe};
+
+#[macro_use]
+mod macros;
+
+mod animate;
+mod arc_length;
+#[cfg(feature = "alloc")]
+mod arena;
+#[cfg(feature = "alloc")]
+mod bake;
+mod batch;
+mod bezier;
+mod bezier_ease;
+mod blender;
+#[cfg(feature = "alloc")]
+mod bspline;
+mod camera;
+mod catmull_rom;
+mod changes;
+mod clothoid;
+mod conic;
+mod css;
+#[cfg(feature = "alloc")]
+mod curve;
+mod deterministic;
+mod elliptical_arc;
+mod ext;
 mod ease;
+mod float;
+#[cfg(feature = "alloc")]
+mod gltf;
+#[cfg(feature = "alloc")]
+mod godot;
+#[cfg(feature = "alloc")]
+mod group;
+mod hermite;
+#[cfg(feature = "rand")]
+mod jitter;
+#[cfg(feature = "alloc")]
+mod kernel;
 mod lerp;
+#[cfg(feature = "alloc")]
+mod lottie;
+#[cfg(feature = "serde")]
+mod lut_serde;
+#[cfg(feature = "alloc")]
+mod morph;
+#[cfg(feature = "alloc")]
+mod nurbs;
+mod oscillator;
+#[cfg(feature = "rayon")]
+mod par;
+mod patch;
+#[cfg(feature = "alloc")]
+mod path;
+mod poly;
+#[cfg(feature = "std")]
+mod record;
+mod rounding;
+#[cfg(feature = "alloc")]
+mod sharded;
+#[cfg(target_has_atomic = "32")]
+mod shared;
+mod slerp;
+mod spatial;
+#[cfg(feature = "alloc")]
+mod step_track;
+#[cfg(feature = "alloc")]
+mod tcb;
+#[cfg(feature = "alloc")]
+mod threshold;
+#[cfg(feature = "alloc")]
+mod timeline;
+mod transform;
+mod transition;
+mod tween;
+#[cfg(feature = "alloc")]
+mod unity;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "alloc")]
+pub mod codegen;
+pub mod const_ease;
+#[cfg(feature = "plotters")]
+pub mod debug_plot;
+pub mod error;
+pub mod fluent;
+pub mod prelude;
+pub mod presets;
+pub mod progress;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
 
 /// Performs quadratic beziér interpolation.
 /// This is done by nesting linear interpolations.
 /// For more information, see:
 ///
 /// [Beziér Curve at Wikipedia](http://en.wikipedia.org/wiki/B%C3%A9zier_curve)
+///
+/// For `Spatial` types, `quad_bez_poly` evaluates the same curve with fewer operations.
 #[inline(always)]
 pub fn quad_bez<T: Lerp>(
     x0: &T,
@@ -39,6 +305,9 @@ pub fn quad_bez<T: Lerp>(
 /// For more information, see:
 ///
 /// [Beziér Curve at Wikipedia](http://en.wikipedia.org/wiki/B%C3%A9zier_curve)
+///
+/// For `Spatial` types, `cub_bez_poly` evaluates the same curve with fewer operations.
+/// Curves with more points can be evaluated with `bezier`.
 #[inline(always)]
 pub fn cub_bez<T: Lerp>(
     x0: &T,
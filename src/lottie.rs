@@ -0,0 +1,166 @@
+//! Import of Lottie and After Effects keyframe easing
+//!
+//! A Lottie keyframe holds the easing of the segment that starts at it:
+//! the out handle `o` of the keyframe and the in handle `i` of the next,
+//! which are the control points of a `CubicBezierEase`.
+//! With the `serde` feature, keyframes can be read from Lottie JSON directly.
+
+use bezier_ease::CubicBezierEase;
+use ease::Easing;
+use lerp::Lerp;
+use timeline::Timeline;
+use tween::Tween;
+
+/// A Bézier easing handle, with coordinates normalized to the segment.
+///
+/// Lottie stores one coordinate per dimension for separate easing.
+/// Only the first is used, since all dimensions share one segment here.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+pub struct LottieHandle {
+    /// The normalized time of the handle.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::first"))]
+    pub x: f32,
+    /// The normalized value of the handle.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::first"))]
+    pub y: f32,
+}
+
+/// A keyframe of an animated Lottie property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+pub struct LottieKeyframe<V> {
+    /// The frame of the keyframe.
+    #[cfg_attr(feature = "serde", serde(rename = "t"))]
+    pub time: f32,
+    /// The value at the keyframe.
+    #[cfg_attr(feature = "serde", serde(rename = "s", default))]
+    pub start: Option<V>,
+    /// The value at the end of the segment, used by older exports
+    /// instead of the start value of the next keyframe.
+    #[cfg_attr(feature = "serde", serde(rename = "e", default))]
+    pub end: Option<V>,
+    /// The out handle of this keyframe.
+    #[cfg_attr(feature = "serde", serde(rename = "o", default))]
+    pub out_handle: Option<LottieHandle>,
+    /// The in handle of the next keyframe.
+    #[cfg_attr(feature = "serde", serde(rename = "i", default))]
+    pub in_handle: Option<LottieHandle>,
+    /// Holds the value until the next keyframe.
+    #[cfg_attr(feature = "serde", serde(rename = "h", default, deserialize_with = "de::flag"))]
+    pub hold: bool,
+}
+
+impl<V> LottieKeyframe<V> {
+    /// Returns the easing of the segment starting at this keyframe.
+    /// Keyframes without handles are linear.
+    pub fn easing(&self) -> Easing {
+        match (self.out_handle, self.in_handle) {
+            (Some(o), Some(i)) => {
+                CubicBezierEase::new(o.x as f64, o.y as f64, i.x as f64, i.y as f64).into()
+            }
+            _ => Easing::Linear,
+        }
+    }
+}
+
+/// Converts keyframes to a timeline with one tween per segment,
+/// with times in seconds at `frame_rate` frames per second.
+///
+/// Keyframes without a value, such as the closing keyframe of older exports,
+/// take the end value of the previous keyframe.
+pub fn lottie_timeline<V>(keyframes: &[LottieKeyframe<V>], frame_rate: f32) -> Timeline<V>
+    where V: Lerp<Scalar = f32> + Clone
+{
+    let mut timeline = Timeline::new();
+    for (i, pair) in keyframes.windows(2).enumerate() {
+        let (a, b) = match *pair {
+            [ref a, ref b] => (a, b),
+            _ => continue,
+        };
+        let previous = i.checked_sub(1).and_then(|j| keyframes.get(j));
+        let from = match a.start.clone().or_else(|| previous.and_then(|k| k.end.clone())) {
+            Some(from) => from,
+            None => continue,
+        };
+        let to = if a.hold {
+            from.clone()
+        } else {
+            match b.start.clone().or_else(|| a.end.clone()) {
+                Some(to) => to,
+                None => continue,
+            }
+        };
+        let duration = (b.time - a.time) / frame_rate;
+        timeline.insert(a.time / frame_rate, Tween::new(from, to, duration).easing(a.easing()));
+    }
+    timeline
+}
+
+#[cfg(feature = "serde")]
+mod de {
+    use alloc::vec::Vec;
+
+    use serde::{Deserialize, Deserializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(f32),
+        Many(Vec<f32>),
+    }
+
+    /// Reads a number, or the first number of an array.
+    pub fn first<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::One(v) => Ok(v),
+            OneOrMany::Many(v) => v.first().cloned()
+                .ok_or_else(|| ::serde::de::Error::custom("empty handle coordinates")),
+        }
+    }
+
+    /// Reads a flag stored as `0` or `1`.
+    pub fn flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+        Ok(u8::deserialize(deserializer)? != 0)
+    }
+}
+
+#[test]
+fn lottie_segments() {
+    let handle = |x, y| Some(LottieHandle { x, y });
+    let keyframes = vec![
+        LottieKeyframe {
+            time: 0.0, start: Some([0.0f32]), end: None,
+            out_handle: handle(0.42, 0.0), in_handle: handle(0.58, 1.0), hold: false,
+        },
+        LottieKeyframe {
+            time: 30.0, start: Some([100.0]), end: None,
+            out_handle: None, in_handle: None, hold: true,
+        },
+        LottieKeyframe {
+            time: 60.0, start: Some([50.0]), end: None,
+            out_handle: None, in_handle: None, hold: false,
+        },
+    ];
+    let timeline = lottie_timeline(&keyframes, 30.0);
+    assert_eq!(timeline.clips().len(), 2);
+    assert_eq!(timeline.duration(), 2.0);
+    assert_eq!(timeline.value_at(0.5), Some([50.0]));
+    assert_eq!(timeline.value_at(1.5), Some([100.0]));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn lottie_json() {
+    let json = r#"[
+        {"t": 0, "s": [0, 0], "o": {"x": [0.33], "y": [0]}, "i": {"x": [0.67], "y": [1]}},
+        {"t": 24, "s": [10, 20], "h": 1},
+        {"t": 48}
+    ]"#;
+    let keyframes: Vec<LottieKeyframe<[f32; 2]>> = serde_json::from_str(json).unwrap();
+    assert_eq!(keyframes[0].out_handle, Some(LottieHandle { x: 0.33, y: 0.0 }));
+    assert!(keyframes[1].hold);
+    let timeline = lottie_timeline(&keyframes, 24.0);
+    assert_eq!(timeline.value_at(0.0), Some([0.0, 0.0]));
+    assert_eq!(timeline.value_at(1.5), Some([10.0, 20.0]));
+}
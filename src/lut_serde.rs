@@ -0,0 +1,207 @@
+//! Compact serde representation of baked lookup tables
+//!
+//! Samples are stored as little-endian bytes: a byte blob in binary formats
+//! and a base64 string in human-readable formats such as JSON.
+//! Wrap a table in `Readable` to write plain number arrays instead.
+//! Both representations are accepted when deserializing.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use bake::{BakedTrack, EaseTable};
+
+/// A value that can be stored in a lookup table as little-endian bytes.
+pub trait LutSample: Copy {
+    /// The number of bytes of a value.
+    const SIZE: usize;
+
+    /// Appends the bytes of the value.
+    fn write_le(&self, out: &mut Vec<u8>);
+
+    /// Reads a value from `SIZE` bytes. Missing bytes are read as zero.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_lut_sample_for_float {
+    ($float: ident, $size: expr) => (
+        impl LutSample for $float {
+            const SIZE: usize = $size;
+
+            fn write_le(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> $float {
+                let mut buf = [0; $size];
+                for (b, &v) in buf.iter_mut().zip(bytes) {
+                    *b = v;
+                }
+                $float::from_le_bytes(buf)
+            }
+        }
+    )
+}
+
+impl_lut_sample_for_float!(f32, 4);
+impl_lut_sample_for_float!(f64, 8);
+
+impl<T: LutSample, const N: usize> LutSample for [T; N] {
+    const SIZE: usize = T::SIZE * N;
+
+    fn write_le(&self, out: &mut Vec<u8>) {
+        for v in self {
+            v.write_le(out);
+        }
+    }
+
+    fn read_le(bytes: &[u8]) -> [T; N] {
+        let mut chunks = bytes.chunks_exact(T::SIZE);
+        [(); N].map(|_| T::read_le(chunks.next().unwrap_or(&[])))
+    }
+}
+
+/// Serializes a lookup table with plain number arrays,
+/// for inspecting or editing by hand.
+#[derive(Debug, Copy, Clone)]
+pub struct Readable<'a, T: 'a>(pub &'a T);
+
+struct Samples<'a, V: 'a> {
+    values: &'a [V],
+    plain: bool,
+}
+
+impl<'a, V> Serialize for Samples<'a, V>
+    where V: LutSample + Serialize
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.plain {
+            return self.values.serialize(serializer);
+        }
+        let mut bytes = Vec::with_capacity(self.values.len() * V::SIZE);
+        for v in self.values {
+            v.write_le(&mut bytes);
+        }
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&BASE64.encode(&bytes))
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+struct SamplesVisitor<V>(PhantomData<V>);
+
+impl<V: LutSample> SamplesVisitor<V> {
+    fn from_bytes<E: de::Error>(bytes: &[u8]) -> Result<Vec<V>, E> {
+        if V::SIZE == 0 || !bytes.len().is_multiple_of(V::SIZE) {
+            return Err(E::invalid_length(bytes.len(), &"a multiple of the sample size"));
+        }
+        Ok(bytes.chunks_exact(V::SIZE).map(V::read_le).collect())
+    }
+}
+
+impl<'de, V> Visitor<'de> for SamplesVisitor<V>
+    where V: LutSample + Deserialize<'de>
+{
+    type Value = Vec<V>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("samples as bytes, a base64 string or a sequence")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Vec<V>, E> {
+        SamplesVisitor::from_bytes(bytes)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Vec<V>, E> {
+        let bytes = BASE64.decode(s).map_err(E::custom)?;
+        SamplesVisitor::from_bytes(&bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<V>, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(v) = seq.next_element()? {
+            values.push(v);
+        }
+        Ok(values)
+    }
+}
+
+pub fn deserialize_samples<'de, D, V>(deserializer: D) -> Result<Vec<V>, D::Error>
+    where D: Deserializer<'de>, V: LutSample + Deserialize<'de>
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(SamplesVisitor(PhantomData))
+    } else {
+        deserializer.deserialize_byte_buf(SamplesVisitor(PhantomData))
+    }
+}
+
+fn serialize_ease_table<S: Serializer>(table: &EaseTable, plain: bool, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_struct("EaseTable", 1)?;
+    s.serialize_field("samples", &Samples { values: table.samples(), plain })?;
+    s.end()
+}
+
+fn serialize_baked_track<S, V>(track: &BakedTrack<V>, plain: bool, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer, V: LutSample + Serialize
+{
+    let mut s = serializer.serialize_struct("BakedTrack", 3)?;
+    s.serialize_field("start", &track.start)?;
+    s.serialize_field("step", &track.step)?;
+    s.serialize_field("values", &Samples { values: &track.values, plain })?;
+    s.end()
+}
+
+impl Serialize for EaseTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_ease_table(self, false, serializer)
+    }
+}
+
+impl<'a> Serialize for Readable<'a, EaseTable> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_ease_table(self.0, true, serializer)
+    }
+}
+
+impl<V> Serialize for BakedTrack<V>
+    where V: LutSample + Serialize
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_baked_track(self, false, serializer)
+    }
+}
+
+impl<'a, V> Serialize for Readable<'a, BakedTrack<V>>
+    where V: LutSample + Serialize
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_baked_track(self.0, true, serializer)
+    }
+}
+
+#[test]
+fn lut_serde_json() {
+    use ease::EaseFunction;
+
+    let table = EaseTable::new(EaseFunction::QuadraticIn, 3);
+    let json = serde_json::to_string(&table).unwrap();
+    assert_eq!(json, r#"{"samples":"AAAAAAAAgD4AAIA/"}"#);
+    assert_eq!(serde_json::from_str::<EaseTable>(&json).unwrap(), table);
+
+    let plain = serde_json::to_string(&Readable(&table)).unwrap();
+    assert_eq!(plain, r#"{"samples":[0.0,0.25,1.0]}"#);
+    assert_eq!(serde_json::from_str::<EaseTable>(&plain).unwrap(), table);
+
+    let track = BakedTrack::bake(0.0, 0.5, 3, |t| [t, 2.0 * t]);
+    let json = serde_json::to_string(&track).unwrap();
+    assert_eq!(serde_json::from_str::<BakedTrack<[f32; 2]>>(&json).unwrap(), track);
+    assert!(serde_json::from_str::<EaseTable>(r#"{"samples":"AAA="}"#).is_err());
+}
@@ -0,0 +1,149 @@
+//! Declarative construction of tweens and timelines
+
+/// Creates a tween from `from => to, duration` and an optional ease function.
+///
+/// ```
+/// #[macro_use]
+/// extern crate interpolation;
+///
+/// # fn main() {
+/// let fade = tween!(0.0f32 => 1.0, 0.25, QuadraticOut);
+/// assert_eq!(fade.duration, 0.25);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! tween {
+    ($from:expr => $to:expr, $duration:expr) => {
+        $crate::Tween::new($from, $to, $duration)
+    };
+    ($from:expr => $to:expr, $duration:expr, $ease:ident) => {
+        $crate::Tween::new($from, $to, $duration).easing($crate::EaseFunction::$ease)
+    };
+}
+
+/// Creates a timeline from a list of steps separated by `;`.
+///
+/// - `from => to, duration` or `from => to, duration, Ease` appends a tween
+/// - `delay duration` leaves a gap before the next tween
+/// - `mark "label"` places a marker at the current time
+///
+/// Timelines need the `alloc` feature.
+///
+/// ```
+/// #[macro_use]
+/// extern crate interpolation;
+///
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// let timeline = timeline! {
+///     0.0f32 => 1.0, 0.25, CubicOut;
+///     mark "shown";
+///     delay 1.0;
+///     1.0 => 0.0, 0.25, CubicIn;
+/// };
+/// assert_eq!(timeline.duration(), 1.5);
+/// assert_eq!(timeline.marker("shown"), Some(0.25));
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! timeline {
+    (@step $timeline:ident, $cursor:ident;) => {};
+    (@step $timeline:ident, $cursor:ident; delay $delay:expr; $($rest:tt)*) => {
+        let $cursor = $cursor + $delay;
+        $crate::timeline!(@step $timeline, $cursor; $($rest)*);
+    };
+    (@step $timeline:ident, $cursor:ident; mark $label:expr; $($rest:tt)*) => {
+        $timeline.mark($label, $cursor);
+        $crate::timeline!(@step $timeline, $cursor; $($rest)*);
+    };
+    (@step $timeline:ident, $cursor:ident;
+     $from:expr => $to:expr, $duration:expr $(, $ease:ident)*; $($rest:tt)*) => {
+        let tween = $crate::tween!($from => $to, $duration $(, $ease)*);
+        let duration = tween.total_duration();
+        $timeline.insert($cursor, tween);
+        let $cursor = $cursor + duration;
+        $crate::timeline!(@step $timeline, $cursor; $($rest)*);
+    };
+    ($($steps:tt)*) => {{
+        let mut timeline = $crate::Timeline::new();
+        let _cursor = $crate::Float::zero();
+        $crate::timeline!(@step timeline, _cursor; $($steps)*);
+        timeline
+    }};
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn timeline_macro() {
+    let timeline = timeline! {
+        mark "start";
+        0.0f64 => 10.0, 1.0;
+        delay 0.5;
+        mark "back";
+        10.0 => 0.0, 1.0, QuadraticIn;
+    };
+    assert_eq!(timeline.duration(), 2.5);
+    assert_eq!(timeline.marker("start"), Some(0.0));
+    assert_eq!(timeline.marker("back"), Some(1.5));
+    assert_eq!(timeline.value_at(1.25), Some(10.0));
+    assert_eq!(timeline.value_at(2.0), Some(7.5));
+}
+
+/// Bakes a lookup table of `n` evenly spaced samples at compile time,
+/// including both ends.
+///
+/// Takes an ease function, like `const_ease::bake_lut`,
+/// or the body of a custom curve of progress `p` as an `f64`,
+/// written with operations that can be evaluated in a `const`.
+///
+/// Other curves, such as ones calling `sin` or `powf`, are written after `lazy`,
+/// and give a `const_ease::LazyLut` that is baked on first use, with the `std` feature.
+///
+/// ```
+/// #[macro_use]
+/// extern crate interpolation;
+///
+/// # fn main() {
+/// static BOUNCE: [f32; 64] = bake_lut!(64, BounceOut);
+/// static SMOOTHER: [f32; 64] = bake_lut!(64, |p| p * p * p * (p * (6.0 * p - 15.0) + 10.0));
+/// assert_eq!(SMOOTHER[63], 1.0);
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use]
+/// extern crate interpolation;
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// static GAMMA: interpolation::const_ease::LazyLut<64> = bake_lut!(lazy 64, |p| p.powf(2.2));
+/// assert_eq!(GAMMA[63], 1.0);
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! bake_lut {
+    (lazy $n:expr, |$p:ident| $body:expr) => {
+        $crate::const_ease::LazyLut::<{ $n }>::new(|$p: f64| $body as f64)
+    };
+    ($n:expr, |$p:ident| $body:expr) => {{
+        const LUT: [f32; $n] = {
+            let mut lut = [0.0f32; $n];
+            let mut i = 0;
+            while i < $n {
+                let $p: f64 = if $n > 1 { i as f64 / ($n - 1) as f64 } else { 0.0 };
+                lut[i] = $body as f32;
+                i += 1;
+            }
+            lut
+        };
+        LUT
+    }};
+    ($n:expr, $ease:ident) => {{
+        const LUT: [f32; $n] = $crate::const_ease::bake_lut($crate::EaseFunction::$ease);
+        LUT
+    }};
+}
@@ -0,0 +1,141 @@
+//! Morph target weight interpolation
+
+use alloc::vec::Vec;
+
+use error::{self, check_increasing, check_len, InterpolationError};
+
+/// Linearly interpolates two weight vectors into `out`.
+///
+/// Only the common prefix of `a`, `b` and `out` is written.
+pub fn lerp_weights(a: &[f32], b: &[f32], t: f32, out: &mut [f32]) {
+    for ((o, a), b) in out.iter_mut().zip(a).zip(b) {
+        *o = a + (b - a) * t;
+    }
+}
+
+/// Keyframed morph target weights, laid out like a glTF weights channel.
+///
+/// The weights of all keyframes are stored in one flat list,
+/// with `targets` weights per keyframe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MorphWeights {
+    /// The number of morph targets.
+    pub targets: usize,
+    /// The keyframe times in ascending order.
+    pub times: Vec<f32>,
+    /// The weights, `targets` per keyframe.
+    pub weights: Vec<f32>,
+    /// Clamps every weight to `[min, max]` after interpolation.
+    pub clamp: Option<[f32; 2]>,
+    /// Scales the weights to sum to one after interpolation.
+    pub normalize: bool,
+}
+
+impl MorphWeights {
+    /// Creates keyframed weights without clamping or normalization.
+    pub fn new(targets: usize, times: Vec<f32>, weights: Vec<f32>) -> MorphWeights {
+        MorphWeights {
+            targets,
+            times,
+            weights,
+            clamp: None,
+            normalize: false,
+        }
+    }
+
+    /// Creates keyframed weights, checking that there is at least one target
+    /// and one keyframe, that the times are increasing,
+    /// and that there are `targets` weights per keyframe.
+    pub fn try_new(targets: usize, times: Vec<f32>, weights: Vec<f32>) -> error::Result<MorphWeights> {
+        if targets == 0 {
+            return Err(InterpolationError::InvalidParameter { name: "targets" });
+        }
+        check_increasing(&times)?;
+        check_len(times.len() * targets, weights.len())?;
+        Ok(MorphWeights::new(targets, times, weights))
+    }
+
+    /// Returns the weights of keyframe `i`, or an empty slice if there is no such keyframe.
+    pub fn keyframe(&self, i: usize) -> &[f32] {
+        self.weights.get(i * self.targets .. (i + 1) * self.targets).unwrap_or(&[])
+    }
+
+    /// Writes the weights at `time` into `out`.
+    ///
+    /// Times outside the keyframe range hold the first or last keyframe.
+    pub fn sample(&self, time: f32, out: &mut [f32]) {
+        let n = self.times.len().min(self.weights.len() / self.targets.max(1));
+        if n == 0 {
+            return;
+        }
+
+        let i = self.times.iter().take(n).take_while(|&&t| t <= time).count();
+        let segment = match (i.checked_sub(1).and_then(|j| self.times.get(j)), self.times.get(i)) {
+            (Some(&t0), Some(&t1)) if i < n => Some((t0, t1)),
+            _ => None,
+        };
+        match segment {
+            Some((t0, t1)) => {
+                let t = (time - t0) / (t1 - t0);
+                lerp_weights(self.keyframe(i - 1), self.keyframe(i), t, out);
+            }
+            None => {
+                let key = self.keyframe(if i == 0 { 0 } else { n - 1 });
+                for (o, w) in out.iter_mut().zip(key) {
+                    *o = *w;
+                }
+            }
+        }
+
+        let len = self.targets.min(out.len());
+        let out = match out.get_mut(..len) {
+            Some(out) => out,
+            None => return,
+        };
+        if let Some([min, max]) = self.clamp {
+            for w in out.iter_mut() {
+                *w = w.max(min).min(max);
+            }
+        }
+        if self.normalize {
+            let sum: f32 = out.iter().sum();
+            if sum > 0.0 {
+                for w in out.iter_mut() {
+                    *w /= sum;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn morph_weights_sample() {
+    let mut morph = MorphWeights::new(2, vec![0.0, 1.0], vec![
+        0.0, 1.0,
+        1.0, 3.0,
+    ]);
+    let mut out = [0.0; 2];
+    morph.sample(0.5, &mut out);
+    assert_eq!(out, [0.5, 2.0]);
+    morph.sample(2.0, &mut out);
+    assert_eq!(out, [1.0, 3.0]);
+
+    morph.clamp = Some([0.0, 1.0]);
+    morph.sample(0.5, &mut out);
+    assert_eq!(out, [0.5, 1.0]);
+
+    morph.normalize = true;
+    morph.sample(0.5, &mut out);
+    assert_eq!(out, [1.0 / 3.0, 2.0 / 3.0]);
+}
+
+#[test]
+fn morph_weights_try_new() {
+    use error::InterpolationError::*;
+
+    assert_eq!(MorphWeights::try_new(0, vec![0.0], vec![]).err(), Some(InvalidParameter { name: "targets" }));
+    assert_eq!(MorphWeights::try_new(2, vec![0.0, 1.0], vec![0.0; 3]).err(),
+               Some(MismatchedLengths { expected: 4, found: 3 }));
+    assert_eq!(MorphWeights::try_new(2, vec![1.0, 0.0], vec![0.0; 4]).err(), Some(NotIncreasing { index: 1 }));
+    assert!(MorphWeights::try_new(2, vec![0.0, 1.0], vec![0.0; 4]).is_ok());
+}
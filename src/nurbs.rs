@@ -0,0 +1,143 @@
+//! Rational B-splines, with a weight per control point
+
+use alloc::vec::Vec;
+
+use bspline::BSpline;
+use error::{self, check_len, InterpolationError};
+use float::Float;
+use lerp::Lerp;
+use spatial::Spatial;
+
+/// A non-uniform rational B-spline curve.
+///
+/// Each control point has a weight that pulls the curve towards it.
+/// With equal weights, this is a `BSpline`. Other weights represent
+/// circles, ellipses and other conics exactly, which polynomial curves can not.
+/// The curve is evaluated in homogeneous coordinates, where each point
+/// is multiplied by its weight, and divided by the interpolated weight at the end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nurbs<T: Lerp> {
+    spline: BSpline<T>,
+    weights: Vec<T::Scalar>,
+}
+
+impl<T> Nurbs<T>
+    where T: Lerp + Spatial<Scalar = <T as Lerp>::Scalar> + Clone, <T as Lerp>::Scalar: Float
+{
+    /// Creates a curve from its degree, control points, weights and knots.
+    ///
+    /// If the number of weights or knots does not match, the curve evaluates to `None`.
+    pub fn new(degree: usize, points: Vec<T>, weights: Vec<<T as Lerp>::Scalar>,
+               knots: Vec<<T as Lerp>::Scalar>) -> Nurbs<T> {
+        Nurbs { spline: BSpline::new(degree, points, knots), weights }
+    }
+
+    /// Creates a curve, checking the knots like `BSpline::try_new`,
+    /// and that there is a positive weight per control point.
+    pub fn try_new(degree: usize, points: Vec<T>, weights: Vec<<T as Lerp>::Scalar>,
+                   knots: Vec<<T as Lerp>::Scalar>) -> error::Result<Nurbs<T>> {
+        check_len(points.len(), weights.len())?;
+        if !weights.iter().all(|&w| w > <T as Lerp>::Scalar::zero()) {
+            return Err(InterpolationError::InvalidParameter { name: "weights" });
+        }
+        Ok(Nurbs { spline: BSpline::try_new(degree, points, knots)?, weights })
+    }
+
+    /// Returns the degree.
+    pub fn degree(&self) -> usize {
+        self.spline.degree()
+    }
+
+    /// Returns the control points.
+    pub fn points(&self) -> &[T] {
+        self.spline.points()
+    }
+
+    /// Returns the weights of the control points.
+    pub fn weights(&self) -> &[<T as Lerp>::Scalar] {
+        &self.weights
+    }
+
+    /// Returns the knots.
+    pub fn knots(&self) -> &[<T as Lerp>::Scalar] {
+        self.spline.knots()
+    }
+
+    /// Returns the first and last parameter of the curve,
+    /// or `None` if the number of knots does not match.
+    pub fn domain(&self) -> Option<(<T as Lerp>::Scalar, <T as Lerp>::Scalar)> {
+        self.spline.domain()
+    }
+
+    /// Returns the point at `t`.
+    ///
+    /// `t` is clamped to the domain, and NaN gives the start.
+    /// Returns `None` if the interpolated weight is zero.
+    pub fn sample(&self, t: <T as Lerp>::Scalar) -> Option<T> {
+        let (k, t) = self.spline.span(t)?;
+        let first = k - self.degree();
+        let points = self.points().get(first..=k)?;
+        let weights = self.weights.get(first..=k)?;
+        let mut d: Vec<_> = points.iter().zip(weights).map(|(p, &w)| (p.scale(&w), w)).collect();
+        let (p, w) = self.spline.de_boor(k, t, &mut d, |a, b, alpha| {
+            (b.0.sub(&a.0).mul_add(&alpha, &a.0), a.1 + (b.1 - a.1) * alpha)
+        })?;
+        if w == <T as Lerp>::Scalar::zero() {
+            return None;
+        }
+        Some(p.scale(&(<T as Lerp>::Scalar::one() / w)))
+    }
+}
+
+impl<T> From<BSpline<T>> for Nurbs<T>
+    where T: Lerp + Clone, T::Scalar: Float
+{
+    /// Gives every control point a weight of one.
+    fn from(spline: BSpline<T>) -> Nurbs<T> {
+        let weights = spline.points().iter().map(|_| T::Scalar::one()).collect();
+        Nurbs { spline, weights }
+    }
+}
+
+#[test]
+fn nurbs_circle() {
+    // A full circle from four quarter arcs.
+    let h = 0.5f64.sqrt();
+    let points = vec![[1.0f64, 0.0], [1.0, 1.0], [0.0, 1.0], [-1.0, 1.0], [-1.0, 0.0],
+                      [-1.0, -1.0], [0.0, -1.0], [1.0, -1.0], [1.0, 0.0]];
+    let weights = vec![1.0, h, 1.0, h, 1.0, h, 1.0, h, 1.0];
+    let knots = vec![0.0, 0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1.0, 1.0, 1.0];
+    let circle = Nurbs::try_new(2, points, weights, knots).unwrap();
+    for i in 0..=64 {
+        let p = circle.sample(i as f64 / 64.0).unwrap();
+        assert!((p[0] * p[0] + p[1] * p[1] - 1.0).abs() < 1e-12);
+    }
+    let p = circle.sample(0.125).unwrap();
+    assert!((p[0] - h).abs() < 1e-12 && (p[1] - h).abs() < 1e-12);
+    assert_eq!(circle.sample(0.0), Some([1.0, 0.0]));
+}
+
+#[test]
+fn nurbs_bspline() {
+    let spline = BSpline::clamped(2, vec![0.0f32, 4.0, 1.0, 3.0]);
+    let nurbs = Nurbs::from(spline.clone());
+    for i in 0..=8 {
+        let t = i as f32 / 8.0;
+        assert!((nurbs.sample(t).unwrap() - spline.sample(t).unwrap()).abs() < 1e-6);
+    }
+    assert_eq!(Nurbs::new(1, vec![0.0f32, 1.0], vec![1.0], vec![0.0, 0.0, 1.0, 1.0]).sample(0.5), None);
+}
+
+#[test]
+fn nurbs_try_new() {
+    use error::InterpolationError::*;
+
+    let knots = vec![0.0f32, 0.0, 1.0, 1.0];
+    assert_eq!(Nurbs::try_new(1, vec![0.0f32, 1.0], vec![1.0], knots.clone()).err(),
+               Some(MismatchedLengths { expected: 2, found: 1 }));
+    assert_eq!(Nurbs::try_new(1, vec![0.0f32, 1.0], vec![1.0, 0.0], knots.clone()).err(),
+               Some(InvalidParameter { name: "weights" }));
+    assert_eq!(Nurbs::try_new(1, vec![0.0f32, 1.0], vec![1.0, f32::NAN], knots.clone()).err(),
+               Some(InvalidParameter { name: "weights" }));
+    assert_eq!(Nurbs::try_new(1, vec![0.0f32, 1.0], vec![1.0, 3.0], knots).unwrap().sample(0.5), Some(0.75));
+}
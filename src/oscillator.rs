@@ -0,0 +1,145 @@
+//! Looping value generators
+
+use animate::Animate;
+use float::Float;
+use lerp::Lerp;
+
+/// The shape of an oscillator cycle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum Waveform {
+    /// Starts in the middle, rises to the maximum and falls to the minimum.
+    Sine,
+    /// Rises linearly from the minimum to the maximum and back.
+    Triangle,
+    /// Holds the maximum for the first half, then the minimum.
+    Square,
+    /// Rises linearly from the minimum to the maximum, then jumps back.
+    Saw,
+}
+
+impl Waveform {
+    /// Returns the normalized output between zero and one
+    /// at a position `p` within a cycle between zero and one.
+    pub fn calc<S: Float>(self, p: S) -> S {
+        let one = S::one();
+        let two = one + one;
+        let half = one / two;
+        match self {
+            Waveform::Sine => half + half * (two * S::pi() * p).sin(),
+            Waveform::Triangle => if p < half { two * p } else { two - two * p },
+            Waveform::Square => if p < half { one } else { S::zero() },
+            Waveform::Saw => p,
+        }
+    }
+}
+
+/// Oscillates a value between a minimum and a maximum at a frequency.
+///
+/// Oscillators never finish, and can be used anywhere a tween is,
+/// for idle bobbing, pulsing highlights or parameter modulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Oscillator<T: Lerp> {
+    /// The shape of the cycle.
+    pub waveform: Waveform,
+    /// The value at the bottom of the cycle.
+    pub min: T,
+    /// The value at the top of the cycle.
+    pub max: T,
+    /// The number of cycles per unit of time.
+    pub frequency: T::Scalar,
+    /// The offset into the cycle, between zero and one.
+    pub phase: T::Scalar,
+    cycle: T::Scalar,
+}
+
+impl<T> Oscillator<T>
+    where T: Lerp, T::Scalar: Float
+{
+    /// Creates a new oscillator with zero phase.
+    pub fn new(waveform: Waveform, min: T, max: T, frequency: T::Scalar) -> Oscillator<T> {
+        Oscillator {
+            waveform,
+            min,
+            max,
+            frequency,
+            phase: T::Scalar::zero(),
+            cycle: T::Scalar::zero(),
+        }
+    }
+
+    /// Sets the phase offset.
+    pub fn phase(mut self, phase: T::Scalar) -> Oscillator<T> {
+        self.phase = phase;
+        self
+    }
+
+    /// Advances the oscillator by `dt`.
+    pub fn update(&mut self, dt: T::Scalar) {
+        let cycle = self.cycle + dt * self.frequency;
+        self.cycle = cycle - cycle.floor();
+    }
+
+    /// Returns the position within the current cycle, between zero and one.
+    pub fn position(&self) -> T::Scalar {
+        let p = self.cycle + self.phase;
+        p - p.floor()
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> T {
+        self.min.lerp(&self.max, &self.waveform.calc(self.position()))
+    }
+}
+
+impl<T> Animate for Oscillator<T>
+    where T: Lerp, T::Scalar: Float
+{
+    type Value = T;
+    type Time = T::Scalar;
+
+    fn update(&mut self, dt: T::Scalar) {
+        Oscillator::update(self, dt)
+    }
+
+    fn value(&self) -> T {
+        Oscillator::value(self)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> ::arbitrary::Arbitrary<'a> for Oscillator<T>
+    where T: Lerp + ::arbitrary::Arbitrary<'a>, T::Scalar: Float + ::arbitrary::Arbitrary<'a>
+{
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Oscillator<T>> {
+        let mut oscillator = Oscillator::new(u.arbitrary()?, u.arbitrary()?, u.arbitrary()?, u.arbitrary()?)
+            .phase(u.arbitrary()?);
+        oscillator.update(u.arbitrary()?);
+        Ok(oscillator)
+    }
+}
+
+#[test]
+fn oscillator_waveforms() {
+    let mut saw = Oscillator::new(Waveform::Saw, 0.0f64, 10.0, 2.0);
+    saw.update(0.125);
+    assert_eq!(saw.value(), 2.5);
+    saw.update(0.5);
+    assert_eq!(saw.value(), 2.5);
+
+    let mut square = Oscillator::new(Waveform::Square, -1.0f32, 1.0, 1.0).phase(0.5);
+    assert_eq!(square.value(), -1.0);
+    square.update(0.5);
+    assert_eq!(square.value(), 1.0);
+
+    let mut triangle = Oscillator::new(Waveform::Triangle, 0.0f32, 1.0, 1.0);
+    triangle.update(0.75);
+    assert_eq!(triangle.value(), 0.5);
+
+    let mut sine = Oscillator::new(Waveform::Sine, 0.0f64, 2.0, 1.0);
+    assert_eq!(sine.value(), 1.0);
+    sine.update(0.25);
+    assert_eq!(sine.value(), 2.0);
+    assert!(!Animate::is_finished(&sine));
+}
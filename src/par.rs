@@ -0,0 +1,81 @@
+//! Parallel batch evaluation, for offline baking of many samples
+
+use rayon::prelude::*;
+
+use animate::Animate;
+use ease::{Ease, Easing};
+use float::Float;
+use sharded::ShardedArena;
+
+impl Easing {
+    /// Replaces every value in `values` with its eased value, in parallel.
+    pub fn par_calc_slice<T: Ease + Float + Send>(self, values: &mut [T]) {
+        values.par_iter_mut().for_each(|v| *v = self.calc(*v));
+    }
+}
+
+impl<A> ShardedArena<A>
+    where A: Animate + Send, A::Time: Copy + Send + Sync
+{
+    /// Advances every stored animation by `dt`, updating the shards in parallel.
+    pub fn par_update(&mut self, dt: A::Time) {
+        self.shards_mut().par_iter_mut().for_each(|arena| arena.update(dt));
+    }
+}
+
+/// Samples `f` at `n` evenly spaced points from zero to one, inclusive, in parallel.
+///
+/// This is the parallel version of `record`,
+/// used to bake tracks and lookup tables with many samples.
+pub fn par_record<S, V, F>(n: usize, f: F) -> Vec<(S, V)>
+    where S: Float + Send, V: Send, F: Fn(S) -> V + Sync
+{
+    match n {
+        0 => vec![],
+        1 => vec![(S::zero(), f(S::zero()))],
+        _ => (0..n).into_par_iter().map(|i| {
+            let t = S::from_f64(i as f64 / (n - 1) as f64);
+            (t, f(t))
+        }).collect(),
+    }
+}
+
+/// Evaluates `f` for every sample in `samples`, in parallel,
+/// writing the results to `out`.
+///
+/// Only the samples that have a place in `out` are evaluated.
+pub fn par_bake<S, V, F>(samples: &[S], out: &mut [V], f: F)
+    where S: Sync, V: Send, F: Fn(&S) -> V + Sync
+{
+    out.par_iter_mut().zip(samples.par_iter()).for_each(|(o, s)| *o = f(s));
+}
+
+#[test]
+fn par_matches_sequential() {
+    use ease::EaseFunction;
+    use record::record;
+
+    let easing = Easing::from(EaseFunction::CubicInOut);
+    let mut a: Vec<f64> = (0..1000).map(|i| i as f64 / 999.0).collect();
+    let mut b = a.clone();
+    easing.calc_slice(&mut a);
+    easing.par_calc_slice(&mut b);
+    assert_eq!(a, b);
+
+    let f = |t: f32| t.calc(EaseFunction::BounceOut);
+    assert_eq!(par_record(257, f), record(257, f));
+
+    let mut out = vec![0.0; 3];
+    par_bake(&[1.0f32, 2.0, 3.0], &mut out, |s| s * 2.0);
+    assert_eq!(out, vec![2.0, 4.0, 6.0]);
+}
+
+#[test]
+fn par_update_sharded() {
+    use tween::Tween;
+
+    let mut arena = ShardedArena::new(3);
+    let handles: Vec<_> = (0..30).map(|_| arena.insert(Tween::new(0.0f32, 4.0, 2.0))).collect();
+    arena.par_update(1.0);
+    assert!(handles.iter().all(|&h| arena.get(h).map(|t| t.value()) == Some(2.0)));
+}
@@ -0,0 +1,189 @@
+//! Surface patches, from control points or from boundary curves
+//!
+//! A tensor product patch is a cubic Bézier curve of cubic Bézier curves:
+//! each row of control points is evaluated along `u`, and the four results
+//! are the control points of a curve along `v`.
+//! The patch passes through its corner points, and its edges are the outer rows and columns.
+//!
+//! A Coons patch is given by its four edges instead. It blends linearly between
+//! opposite edges in both directions, and subtracts the bilinear blend of the corners,
+//! which both blends count. With straight edges, this is bilinear interpolation.
+
+use arc_length::ParametricCurve;
+use bezier::CubicBezier;
+use float::Float;
+use lerp::Lerp;
+use spatial::{InnerSpace, Spatial};
+
+/// A bicubic Bézier patch with 4 by 4 control points.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct BezierPatch<T> {
+    /// The control points, in rows along `u`, from `v = 0` to `v = 1`.
+    pub points: [[T; 4]; 4],
+}
+
+impl<T> BezierPatch<T> {
+    /// Creates a patch from rows of control points along `u`.
+    pub fn new(points: [[T; 4]; 4]) -> BezierPatch<T> {
+        BezierPatch { points }
+    }
+
+    /// Evaluates every row at `u` with `f`, and the resulting curve along `v` with `g`.
+    fn eval<F, G, R>(&self, f: F, g: G) -> R
+        where T: Clone, F: Fn(CubicBezier<T>) -> T, G: FnOnce(CubicBezier<T>) -> R
+    {
+        let [ref a, ref b, ref c, ref d] = self.points;
+        let row = |points: &[T; 4]| f(CubicBezier { points: points.clone() });
+        g(CubicBezier::new(row(a), row(b), row(c), row(d)))
+    }
+
+    /// Returns the point at `(u, v)`.
+    pub fn sample(&self, u: &T::Scalar, v: &T::Scalar) -> T
+        where T: Lerp + Clone, T::Scalar: Float
+    {
+        self.eval(|row| row.sample(u), |column| column.sample(v))
+    }
+}
+
+impl<T> BezierPatch<T>
+    where T: Spatial + Clone, T::Scalar: Float
+{
+    /// Returns the partial derivative along `u` at `(u, v)`.
+    pub fn derivative_u(&self, u: &T::Scalar, v: &T::Scalar) -> T {
+        self.eval(|row| row.derivative(u), |column| column.to_poly().eval(v))
+    }
+
+    /// Returns the partial derivative along `v` at `(u, v)`.
+    pub fn derivative_v(&self, u: &T::Scalar, v: &T::Scalar) -> T {
+        self.eval(|row| row.to_poly().eval(u), |column| column.derivative(v))
+    }
+}
+
+impl<S> BezierPatch<[S; 3]>
+    where S: Float + Spatial<Scalar = S> + InnerSpace
+{
+    /// Returns the unit normal at `(u, v)`, the cross product of the derivatives along `u` and `v`,
+    /// or `None` where they are parallel or zero, such as at a corner where an edge collapses.
+    pub fn normal(&self, u: &S, v: &S) -> Option<[S; 3]> {
+        let ([ax, ay, az], [bx, by, bz]) = (self.derivative_u(u, v), self.derivative_v(u, v));
+        let n = [ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx];
+        let length = n.length();
+        if length > S::zero() {
+            Some(n.scale(&(S::one() / length)))
+        } else {
+            None
+        }
+    }
+}
+
+/// A Coons patch, which fills the area between four boundary curves.
+///
+/// The curves run from `u = 0` to `u = 1` at the bottom and top,
+/// and from `v = 0` to `v = 1` at the left and right.
+/// They should meet at the corners, which are taken from the bottom and top.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct CoonsPatch<C> {
+    /// The edge at `v = 0`.
+    pub bottom: C,
+    /// The edge at `v = 1`.
+    pub top: C,
+    /// The edge at `u = 0`.
+    pub left: C,
+    /// The edge at `u = 1`.
+    pub right: C,
+}
+
+impl<C> CoonsPatch<C>
+    where C: ParametricCurve, C::Point: Spatial<Scalar = C::Scalar>
+{
+    /// Creates a patch from its boundary curves.
+    pub fn new(bottom: C, top: C, left: C, right: C) -> CoonsPatch<C> {
+        CoonsPatch { bottom, top, left, right }
+    }
+
+    /// Returns the point at `(u, v)`.
+    pub fn sample(&self, u: &C::Scalar, v: &C::Scalar) -> C::Point {
+        let (zero, one) = (C::Scalar::zero(), C::Scalar::one());
+        let (u, v) = (*u, *v);
+        let (mu, mv) = (one - u, one - v);
+        let along_v = self.bottom.point_at(u).scale(&mv).add(&self.top.point_at(u).scale(&v));
+        let along_u = self.left.point_at(v).scale(&mu).add(&self.right.point_at(v).scale(&u));
+        let corners = self.bottom.point_at(zero).scale(&(mu * mv))
+            .add(&self.bottom.point_at(one).scale(&(u * mv)))
+            .add(&self.top.point_at(zero).scale(&(mu * v)))
+            .add(&self.top.point_at(one).scale(&(u * v)));
+        along_v.add(&along_u).sub(&corners)
+    }
+}
+
+#[cfg(test)]
+fn grid(height: impl Fn(usize, usize) -> f64) -> BezierPatch<[f64; 3]> {
+    let row = |j: usize| [0, 1, 2, 3].map(|i| [i as f64, j as f64, height(i, j)]);
+    BezierPatch::new([row(0), row(1), row(2), row(3)])
+}
+
+#[test]
+fn patch_flat() {
+    let patch = grid(|_, _| 0.0);
+    assert_eq!(patch.sample(&0.0, &0.0), [0.0, 0.0, 0.0]);
+    assert_eq!(patch.sample(&1.0, &1.0), [3.0, 3.0, 0.0]);
+    assert_eq!(patch.sample(&0.25, &0.5), [0.75, 1.5, 0.0]);
+    assert_eq!(patch.derivative_u(&0.3, &0.6), [3.0, 0.0, 0.0]);
+    assert_eq!(patch.derivative_v(&0.3, &0.6), [0.0, 3.0, 0.0]);
+    assert_eq!(patch.normal(&0.3, &0.6), Some([0.0, 0.0, 1.0]));
+    // A patch of scalars, such as a height map.
+    let heights = BezierPatch::new([[0.0f32, 1.0, 2.0, 3.0]; 4]);
+    assert_eq!(heights.sample(&0.5, &0.7), 1.5);
+    assert_eq!(heights.derivative_v(&0.5, &0.7), 0.0);
+}
+
+#[test]
+fn patch_curved() {
+    let patch = grid(|i, j| ((i * 3 + j * 5) % 4) as f64 - 1.5);
+    let h = 1e-6;
+    for &(u, v) in &[(0.2, 0.3), (0.5, 0.5), (0.9, 0.1), (0.0, 1.0)] {
+        let (du, dv) = (patch.derivative_u(&u, &v), patch.derivative_v(&u, &v));
+        for k in 0..3 {
+            let fu = (patch.sample(&(u + h), &v)[k] - patch.sample(&(u - h), &v)[k]) / (2.0 * h);
+            let fv = (patch.sample(&u, &(v + h))[k] - patch.sample(&u, &(v - h))[k]) / (2.0 * h);
+            assert!((fu - du[k]).abs() < 1e-6 && (fv - dv[k]).abs() < 1e-6);
+        }
+        let n = patch.normal(&u, &v).unwrap();
+        assert!((n.dot(&du)).abs() < 1e-12 && (n.dot(&dv)).abs() < 1e-12);
+        assert!((n.length() - 1.0).abs() < 1e-12);
+    }
+    // The edges are the curves of the outer rows and columns.
+    let edge = CubicBezier { points: patch.points[3] };
+    let (p, q) = (patch.sample(&0.4, &1.0), edge.sample(&0.4));
+    assert!((0..3).all(|k| (p[k] - q[k]).abs() < 1e-12));
+    // A collapsed edge has no normal at its corners.
+    let mut cone = patch;
+    cone.points[0] = [[0.0, 0.0, 0.0]; 4];
+    assert_eq!(cone.normal(&0.5, &0.0), None);
+}
+
+#[test]
+fn coons_patch() {
+    let line = |a: [f64; 2], b: [f64; 2]| CubicBezier::new(a, a.lerp(&b, &(1.0 / 3.0)), a.lerp(&b, &(2.0 / 3.0)), b);
+    let (p00, p10, p01, p11) = ([0.0, 0.0], [4.0, 1.0], [1.0, 3.0], [5.0, 5.0]);
+    // Straight edges give bilinear interpolation.
+    let patch = CoonsPatch::new(line(p00, p10), line(p01, p11), line(p00, p01), line(p10, p11));
+    for &(u, v) in &[(0.0, 0.0), (0.3, 0.6), (1.0, 0.5), (0.8, 1.0)] {
+        let p = patch.sample(&u, &v);
+        let q = p00.lerp(&p10, &u).lerp(&p01.lerp(&p11, &u), &v);
+        assert!((p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12);
+    }
+    // Curved edges are reproduced at the boundary.
+    let bottom = CubicBezier::new(p00, [1.0, -2.0], [3.0, 2.0], p10);
+    let right = CubicBezier::new(p10, [6.0, 2.0], [3.0, 4.0], p11);
+    let patch = CoonsPatch::new(bottom, line(p01, p11), line(p00, p01), right);
+    for i in 0..=8 {
+        let t = i as f64 / 8.0;
+        let (p, q) = (patch.sample(&t, &0.0), bottom.sample(&t));
+        assert!((p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12);
+        let (p, q) = (patch.sample(&1.0, &t), right.sample(&t));
+        assert!((p[0] - q[0]).abs() < 1e-12 && (p[1] - q[1]).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,305 @@
+//! Paths of cubic Bézier segments through points
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use catmull_rom::catmull_rom_bezier;
+use error::{self, InterpolationError};
+use float::Float;
+use lerp::Lerp;
+use spatial::InnerSpace;
+
+/// A point of a `Path`, with the control points of its handles.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PathPoint<T> {
+    /// The position of the point.
+    pub position: T,
+    /// The control point of the segment arriving at the point.
+    pub in_control: T,
+    /// The control point of the segment leaving the point.
+    pub out_control: T,
+}
+
+impl<T: Clone> PathPoint<T> {
+    /// Creates a point without handles, so its segments are straight.
+    pub fn corner(position: T) -> PathPoint<T> {
+        PathPoint { in_control: position.clone(), out_control: position.clone(), position }
+    }
+}
+
+/// A curve through points, with a cubic Bézier segment between each pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<T> {
+    points: Vec<PathPoint<T>>,
+}
+
+impl<T> Path<T>
+    where T: Lerp, T::Scalar: Float
+{
+    /// Creates a path through points.
+    pub fn new(points: Vec<PathPoint<T>>) -> Path<T> {
+        Path { points }
+    }
+
+    /// Creates a path through points, checking that there is at least one point.
+    pub fn try_new(points: Vec<PathPoint<T>>) -> error::Result<Path<T>> {
+        if points.is_empty() {
+            return Err(InterpolationError::Empty);
+        }
+        Ok(Path { points })
+    }
+
+    /// Returns the points.
+    pub fn points(&self) -> &[PathPoint<T>] {
+        &self.points
+    }
+
+    /// Returns the number of segments.
+    pub fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+
+    /// Returns the position at `t`, where the integer part selects the segment
+    /// and the fractional part the position on it.
+    /// `t` is clamped to the ends of the path.
+    pub fn sample(&self, t: T::Scalar) -> Option<T> where T: Clone {
+        let n = self.segment_count();
+        if n == 0 {
+            return self.points.first().map(|p| p.position.clone());
+        }
+        let zero = T::Scalar::zero();
+        let t = if t < zero { zero } else { t };
+        let i = (t.floor().to_f64() as usize).min(n - 1);
+        let t = t - T::Scalar::from_f64(i as f64);
+        let t = if t > T::Scalar::one() { T::Scalar::one() } else { t };
+        let (a, b) = (self.points.get(i)?, self.points.get(i + 1)?);
+        Some(::cub_bez(&a.position, &a.out_control, &b.in_control, &b.position, &t))
+    }
+}
+
+impl<T> Path<T>
+    where T: Lerp + InnerSpace<Scalar = <T as Lerp>::Scalar> + Clone, <T as Lerp>::Scalar: Float
+{
+    /// Creates a path through `positions` along a Catmull-Rom spline.
+    ///
+    /// `alpha` is `0.0` for the uniform spline, `0.5` for the centripetal spline
+    /// and `1.0` for the chordal spline; see `catmull_rom`.
+    /// The path starts and ends with straight handles.
+    pub fn catmull_rom(positions: &[T], alpha: <T as Lerp>::Scalar) -> Path<T> {
+        let mut points: Vec<_> = positions.iter().cloned().map(PathPoint::corner).collect();
+        for (i, w) in positions.windows(2).enumerate() {
+            let (p1, p2) = match *w {
+                [ref p1, ref p2] => (p1, p2),
+                _ => continue,
+            };
+            let p0 = i.checked_sub(1).and_then(|i| positions.get(i)).unwrap_or(p1);
+            let p3 = positions.get(i + 2).unwrap_or(p2);
+            let [_, b1, b2, _] = catmull_rom_bezier(p0, p1, p2, p3, &alpha);
+            if let Some(point) = points.get_mut(i) {
+                point.out_control = b1;
+            }
+            if let Some(point) = points.get_mut(i + 1) {
+                point.in_control = b2;
+            }
+        }
+        Path { points }
+    }
+
+    /// Measures the path for evaluation by arc length,
+    /// with `samples` chords per segment, and at least one.
+    ///
+    /// Building the table costs a few evaluations per sample, so keep the
+    /// returned `PreparedPath` and evaluate it every frame instead of the path.
+    pub fn prepared(self, samples: usize) -> PreparedPath<T> {
+        let samples = samples.max(1);
+        let zero = <T as Lerp>::Scalar::zero();
+        let n = self.segment_count();
+        let mut prepared = PreparedPath {
+            path: self,
+            samples,
+            lengths: vec![zero; n * (samples + 1)],
+            ends: vec![zero; n],
+        };
+        for i in 0..n {
+            prepared.measure_segment(i);
+        }
+        prepared.sum_ends();
+        prepared
+    }
+}
+
+/// A `Path` with a table of arc lengths, for evaluation at a distance along it.
+///
+/// The table is refit when points are changed through `set_point`,
+/// only for the segments next to the changed point.
+/// The path itself can not be changed otherwise, so the table never goes stale.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedPath<T: Lerp> {
+    path: Path<T>,
+    samples: usize,
+    /// The length from the start of each segment at every sample, segment after segment.
+    lengths: Vec<T::Scalar>,
+    /// The length from the start of the path to the end of each segment.
+    ends: Vec<T::Scalar>,
+}
+
+impl<T> PreparedPath<T>
+    where T: Lerp + InnerSpace<Scalar = <T as Lerp>::Scalar> + Clone, <T as Lerp>::Scalar: Float
+{
+    /// Returns the path.
+    pub fn path(&self) -> &Path<T> {
+        &self.path
+    }
+
+    /// Returns the path, dropping the table.
+    pub fn into_path(self) -> Path<T> {
+        self.path
+    }
+
+    /// Replaces a point and refits the table of the segments next to it.
+    ///
+    /// Returns `false`, and changes nothing, if there is no point at `index`.
+    pub fn set_point(&mut self, index: usize, point: PathPoint<T>) -> bool {
+        match self.path.points.get_mut(index) {
+            Some(p) => *p = point,
+            None => return false,
+        }
+        if index > 0 {
+            self.measure_segment(index - 1);
+        }
+        if index < self.path.segment_count() {
+            self.measure_segment(index);
+        }
+        self.sum_ends();
+        true
+    }
+
+    /// Returns the approximate length of the path.
+    pub fn length(&self) -> <T as Lerp>::Scalar {
+        self.ends.last().cloned().unwrap_or_else(<T as Lerp>::Scalar::zero)
+    }
+
+    /// Returns the parameter of `Path::sample` at `distance` along the path.
+    /// `distance` is clamped to the ends of the path.
+    pub fn t_at_length(&self, distance: <T as Lerp>::Scalar) -> <T as Lerp>::Scalar {
+        let zero = <T as Lerp>::Scalar::zero();
+        let distance = if distance > zero { distance } else { zero };
+        let last = self.ends.len().saturating_sub(1);
+        let i = self.ends.partition_point(|&end| end < distance).min(last);
+        let start = match i {
+            0 => zero,
+            _ => self.ends.get(i - 1).cloned().unwrap_or(zero),
+        };
+        let m = self.samples + 1;
+        let table = match self.lengths.get(i * m..(i + 1) * m) {
+            Some(table) => table,
+            None => return zero,
+        };
+        let local = distance - start;
+        let j = table.partition_point(|&l| l < local).clamp(1, self.samples);
+        let frac = match (table.get(j - 1), table.get(j)) {
+            (Some(&a), Some(&b)) if b > a => {
+                let f = (local - a) / (b - a);
+                if f > <T as Lerp>::Scalar::one() { <T as Lerp>::Scalar::one() } else { f }
+            }
+            _ => <T as Lerp>::Scalar::one(),
+        };
+        <T as Lerp>::Scalar::from_f64(i as f64)
+            + (<T as Lerp>::Scalar::from_f64((j - 1) as f64) + frac)
+                / <T as Lerp>::Scalar::from_f64(self.samples as f64)
+    }
+
+    /// Returns the position at `distance` along the path,
+    /// so evenly spaced distances give evenly spaced points.
+    pub fn eval_at_length(&self, distance: <T as Lerp>::Scalar) -> Option<T> {
+        self.path.sample(self.t_at_length(distance))
+    }
+
+    /// Fills the table of one segment with chord lengths.
+    fn measure_segment(&mut self, i: usize) {
+        let m = self.samples + 1;
+        let (a, b) = match (self.path.points.get(i), self.path.points.get(i + 1)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return,
+        };
+        let table = match self.lengths.get_mut(i * m..(i + 1) * m) {
+            Some(table) => table,
+            None => return,
+        };
+        let mut prev = a.position.clone();
+        let mut length = <T as Lerp>::Scalar::zero();
+        for (k, l) in table.iter_mut().enumerate() {
+            let t = <T as Lerp>::Scalar::from_f64(k as f64 / self.samples as f64);
+            let p = ::cub_bez(&a.position, &a.out_control, &b.in_control, &b.position, &t);
+            length = length + p.distance(&prev);
+            *l = length;
+            prev = p;
+        }
+    }
+
+    /// Recomputes the length at the end of every segment.
+    fn sum_ends(&mut self) {
+        let m = self.samples + 1;
+        let zero = <T as Lerp>::Scalar::zero();
+        let mut total = zero;
+        for (end, table) in self.ends.iter_mut().zip(self.lengths.chunks(m)) {
+            total = total + table.last().cloned().unwrap_or(zero);
+            *end = total;
+        }
+    }
+}
+
+#[test]
+fn path_sample() {
+    let path = Path::new(vec![
+        PathPoint::corner([0.0f32, 0.0]),
+        PathPoint { position: [2.0, 0.0], in_control: [2.0, 0.0], out_control: [2.0, 1.0] },
+        PathPoint::corner([2.0, 2.0]),
+    ]);
+    assert_eq!(path.segment_count(), 2);
+    assert_eq!(path.sample(-1.0), Some([0.0, 0.0]));
+    assert_eq!(path.sample(0.5), Some([1.0, 0.0]));
+    assert_eq!(path.sample(1.0), Some([2.0, 0.0]));
+    assert_eq!(path.sample(9.0), Some([2.0, 2.0]));
+    assert_eq!(Path::<f32>::new(vec![]).sample(0.0), None);
+    assert_eq!(Path::<f32>::try_new(vec![]), Err(::error::InterpolationError::Empty));
+}
+
+#[test]
+fn path_catmull_rom() {
+    let positions = [[0.0f64, 0.0], [10.0, 0.0], [10.5, 0.0], [20.5, 0.0]];
+    let path = Path::catmull_rom(&positions, 0.5);
+    assert_eq!(path.segment_count(), 3);
+    for (i, p) in positions.iter().enumerate() {
+        assert_eq!(path.sample(i as f64), Some(*p));
+    }
+    let points = path.points();
+    assert!((points[0].out_control[0] - 10.0 / 3.0).abs() < 1e-12);
+    assert_eq!(points[1].out_control, ::catmull_rom_bezier(&positions[0], &positions[1], &positions[2], &positions[3], &0.5)[1]);
+    assert_eq!(Path::<f32>::catmull_rom(&[], 0.5).segment_count(), 0);
+    assert_eq!(Path::catmull_rom(&[1.0f32], 0.5).points(), &[PathPoint::corner(1.0)]);
+}
+
+#[test]
+fn prepared_path_length() {
+    let path = Path::new(vec![
+        PathPoint::corner([0.0f64, 0.0]),
+        PathPoint::corner([3.0, 0.0]),
+        PathPoint::corner([3.0, 1.0]),
+    ]);
+    let mut prepared = path.clone().prepared(8);
+    assert!((prepared.length() - 4.0).abs() < 1e-12);
+    assert_eq!(prepared.eval_at_length(-1.0), Some([0.0, 0.0]));
+    assert_eq!(prepared.eval_at_length(9.0), Some([3.0, 1.0]));
+    let p = prepared.eval_at_length(3.5).unwrap();
+    assert!((p[0] - 3.0).abs() < 1e-12 && (p[1] - 0.5).abs() < 0.05);
+
+    assert!(prepared.set_point(2, PathPoint::corner([3.0, 4.0])));
+    assert!(!prepared.set_point(3, PathPoint::corner([0.0, 0.0])));
+    assert!((prepared.length() - 7.0).abs() < 1e-12);
+    let mut fresh = prepared.clone().into_path();
+    fresh.points[2] = PathPoint::corner([3.0, 4.0]);
+    assert_eq!(prepared, fresh.prepared(8));
+    assert_eq!(path.prepared(0).t_at_length(1.5), 0.5);
+    assert_eq!(Path::<f32>::new(vec![]).prepared(4).eval_at_length(1.0), None);
+}
@@ -0,0 +1,113 @@
+//! Bézier curves in polynomial form
+//!
+//! `quad_bez` and `cub_bez` evaluate a curve with nested linear interpolations,
+//! which needs 3 and 6 lerps per point and works for any `Lerp` type.
+//! For types that implement `Spatial`, a curve can instead be expanded once
+//! into the coefficients of its polynomial, and then evaluated with Horner's method
+//! in 2 and 3 multiply-adds per point, which are fused with the `fma` feature.
+//! The results agree with the nested form up to rounding.
+
+use float::Float;
+use spatial::Spatial;
+
+/// A quadratic Bézier curve expanded to `a t^2 + b t + c`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QuadraticPoly<T> {
+    /// The coefficients from the highest degree, `[a, b, c]`.
+    pub coefficients: [T; 3],
+}
+
+impl<T> QuadraticPoly<T>
+    where T: Spatial + Clone, T::Scalar: Float
+{
+    /// Expands a quadratic Bézier curve with control points `x0`, `x1` and `x2`.
+    pub fn from_bezier(x0: &T, x1: &T, x2: &T) -> QuadraticPoly<T> {
+        let two = T::Scalar::from_f64(2.0);
+        let d = x1.sub(x0);
+        QuadraticPoly {
+            coefficients: [x2.sub(x1).sub(&d), d.scale(&two), x0.clone()],
+        }
+    }
+
+    /// Evaluates the curve at `t`.
+    #[inline(always)]
+    pub fn eval(&self, t: &T::Scalar) -> T {
+        let [ref a, ref b, ref c] = self.coefficients;
+        a.mul_add(t, b).mul_add(t, c)
+    }
+}
+
+/// A cubic Bézier curve expanded to `a t^3 + b t^2 + c t + d`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CubicPoly<T> {
+    /// The coefficients from the highest degree, `[a, b, c, d]`.
+    pub coefficients: [T; 4],
+}
+
+impl<T> CubicPoly<T>
+    where T: Spatial + Clone, T::Scalar: Float
+{
+    /// Expands a cubic Bézier curve with control points `x0` to `x3`.
+    pub fn from_bezier(x0: &T, x1: &T, x2: &T, x3: &T) -> CubicPoly<T> {
+        let three = T::Scalar::from_f64(3.0);
+        let d01 = x1.sub(x0);
+        let d12 = x2.sub(x1);
+        let d23 = x3.sub(x2);
+        let dd = d12.sub(&d01);
+        CubicPoly {
+            coefficients: [
+                d23.sub(&d12).sub(&dd),
+                dd.scale(&three),
+                d01.scale(&three),
+                x0.clone(),
+            ],
+        }
+    }
+
+    /// Evaluates the curve at `t`.
+    #[inline(always)]
+    pub fn eval(&self, t: &T::Scalar) -> T {
+        let [ref a, ref b, ref c, ref d] = self.coefficients;
+        a.mul_add(t, b).mul_add(t, c).mul_add(t, d)
+    }
+}
+
+/// Performs quadratic Bézier interpolation in polynomial form.
+///
+/// This gives the same curve as `quad_bez`, with fewer operations for `Spatial` types.
+/// To evaluate the same curve many times, keep a `QuadraticPoly` instead.
+#[inline(always)]
+pub fn quad_bez_poly<T>(x0: &T, x1: &T, x2: &T, t: &T::Scalar) -> T
+    where T: Spatial + Clone, T::Scalar: Float
+{
+    QuadraticPoly::from_bezier(x0, x1, x2).eval(t)
+}
+
+/// Performs cubic Bézier interpolation in polynomial form.
+///
+/// This gives the same curve as `cub_bez`, with fewer operations for `Spatial` types.
+/// To evaluate the same curve many times, keep a `CubicPoly` instead.
+#[inline(always)]
+pub fn cub_bez_poly<T>(x0: &T, x1: &T, x2: &T, x3: &T, t: &T::Scalar) -> T
+    where T: Spatial + Clone, T::Scalar: Float
+{
+    CubicPoly::from_bezier(x0, x1, x2, x3).eval(t)
+}
+
+#[test]
+fn poly_matches_nested() {
+    let (x0, x1, x2, x3) = ([0.0f64, 1.0], [2.0, -3.0], [5.0, 4.0], [-1.0, 0.5]);
+    for i in 0..=16 {
+        let t = i as f64 / 16.0;
+        let a = quad_bez_poly(&x0, &x1, &x2, &t);
+        let b = ::quad_bez(&x0, &x1, &x2, &t);
+        assert!((a[0] - b[0]).abs() < 1e-12 && (a[1] - b[1]).abs() < 1e-12);
+        let a = cub_bez_poly(&x0, &x1, &x2, &x3, &t);
+        let b = ::cub_bez(&x0, &x1, &x2, &x3, &t);
+        assert!((a[0] - b[0]).abs() < 1e-12 && (a[1] - b[1]).abs() < 1e-12);
+    }
+    let poly = CubicPoly::from_bezier(&0.0f32, &1.0, &2.0, &3.0);
+    assert_eq!(poly.coefficients, [0.0, 0.0, 3.0, 0.0]);
+    assert_eq!(poly.eval(&0.0), 0.0);
+    assert_eq!(poly.eval(&1.0), 3.0);
+}
@@ -0,0 +1,26 @@
+//! The traits and types used in most code, for importing with a glob
+//!
+//! ```
+//! use interpolation::prelude::*;
+//!
+//! let tween = Tween::new(20.0f32, 340.0, 1.0).easing(EaseFunction::CubicOut);
+//! assert_eq!(tween.value_at(1.0), 340.0);
+//! assert_eq!(lerp(&[0.0f32, 2.0], &[2.0, 4.0], &0.5), [1.0, 3.0]);
+//! ```
+//!
+//! The prelude contains the traits whose methods are called on values,
+//! and the few types and functions that nearly every user of the crate needs.
+//! Items are only added in minor releases when their names are unlikely to clash
+//! with the standard library or common dependencies, so for example `Float`,
+//! `Path` and `Handle` are left out. Items are only removed in breaking releases.
+
+pub use animate::Animate;
+pub use ease::{Ease, EaseFunction, Easing};
+pub use lerp::{lerp, Lerp, LerpRound};
+pub use progress::ProgressMode;
+pub use slerp::{slerp, Slerp};
+pub use spatial::{InnerSpace, Planar, Spatial};
+#[cfg(feature = "alloc")]
+pub use timeline::Timeline;
+pub use tween::Tween;
+pub use {cub_bez, quad_bez};
@@ -0,0 +1,117 @@
+//! Preconfigured tweens for common user interface transitions
+//!
+//! Entering elements decelerate and take a bit longer,
+//! exiting elements accelerate and leave a bit faster,
+//! following the durations used by common design systems.
+//! The returned tweens and timelines are ordinary values,
+//! so durations and easings can be changed afterwards.
+
+use ease::EaseFunction;
+#[cfg(feature = "alloc")]
+use timeline::Timeline;
+use tween::Tween;
+
+/// The duration of enter transitions in seconds.
+pub const ENTER_DURATION: f32 = 0.225;
+/// The duration of exit transitions in seconds.
+pub const EXIT_DURATION: f32 = 0.195;
+/// The easing of enter transitions.
+pub const ENTER_EASE: EaseFunction = EaseFunction::CubicOut;
+/// The easing of exit transitions.
+pub const EXIT_EASE: EaseFunction = EaseFunction::CubicIn;
+
+/// Fades opacity from zero to one.
+pub fn fade_in() -> Tween<f32> {
+    Tween::new(0.0, 1.0, ENTER_DURATION).easing(ENTER_EASE)
+}
+
+/// Fades opacity from one to zero.
+pub fn fade_out() -> Tween<f32> {
+    Tween::new(1.0, 0.0, EXIT_DURATION).easing(EXIT_EASE)
+}
+
+/// Slides an offset from `from` to rest at zero.
+pub fn slide_in(from: [f32; 2]) -> Tween<[f32; 2]> {
+    Tween::new(from, [0.0, 0.0], ENTER_DURATION).easing(ENTER_EASE)
+}
+
+/// Slides an offset from rest at zero to `to`.
+pub fn slide_out(to: [f32; 2]) -> Tween<[f32; 2]> {
+    Tween::new([0.0, 0.0], to, EXIT_DURATION).easing(EXIT_EASE)
+}
+
+/// Scales from slightly smaller up to full size.
+pub fn scale_in() -> Tween<f32> {
+    Tween::new(0.8, 1.0, ENTER_DURATION).easing(ENTER_EASE)
+}
+
+/// Scales from full size down to slightly smaller.
+pub fn scale_out() -> Tween<f32> {
+    Tween::new(1.0, 0.8, EXIT_DURATION).easing(EXIT_EASE)
+}
+
+/// Scales from zero past full size, then settles back.
+#[cfg(feature = "alloc")]
+pub fn pop_in() -> Timeline<f32> {
+    let mut timeline = Timeline::new();
+    timeline.push(Tween::new(0.0, 1.1, 0.15).easing(EaseFunction::QuadraticOut));
+    timeline.push(Tween::new(1.1, 1.0, 0.1).easing(EaseFunction::QuadraticInOut));
+    timeline
+}
+
+/// Swells slightly past full size, then scales down to zero.
+#[cfg(feature = "alloc")]
+pub fn pop_out() -> Timeline<f32> {
+    let mut timeline = Timeline::new();
+    timeline.push(Tween::new(1.0, 1.1, 0.075).easing(EaseFunction::QuadraticOut));
+    timeline.push(Tween::new(1.1, 0.0, 0.12).easing(EaseFunction::QuadraticIn));
+    timeline
+}
+
+#[test]
+fn presets_endpoints() {
+    for &(ref tween, from, to) in &[(fade_in(), 0.0, 1.0), (fade_out(), 1.0, 0.0),
+                                (scale_in(), 0.8, 1.0), (scale_out(), 1.0, 0.8)] {
+        assert_eq!(tween.value_at(0.0), from);
+        assert_eq!(tween.value_at(tween.total_duration()), to);
+    }
+    assert_eq!(slide_in([10.0, -4.0]).value_at(0.0), [10.0, -4.0]);
+    assert_eq!(slide_in([10.0, -4.0]).value_at(ENTER_DURATION), [0.0, 0.0]);
+    assert_eq!(slide_out([10.0, -4.0]).value_at(0.0), [0.0, 0.0]);
+    assert_eq!(slide_out([10.0, -4.0]).value_at(EXIT_DURATION), [10.0, -4.0]);
+}
+
+#[test]
+fn presets_monotonic() {
+    let steps = 64;
+    for tween in &[fade_in(), fade_out(), scale_in(), scale_out()] {
+        let rising = tween.value_at(tween.total_duration()) > tween.value_at(0.0);
+        let mut previous = tween.value_at(0.0);
+        for i in 1..=steps {
+            let value = tween.value_at(tween.total_duration() * i as f32 / steps as f32);
+            assert!(if rising { previous <= value } else { previous >= value });
+            previous = value;
+        }
+    }
+    // Enter transitions decelerate, exit transitions accelerate.
+    assert!(fade_in().value_at(ENTER_DURATION * 0.5) > 0.5);
+    assert!(fade_out().value_at(EXIT_DURATION * 0.5) > 0.5);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn presets_pop() {
+    let pop = pop_in();
+    assert_eq!(pop.value_at(0.0), Some(0.0));
+    assert_eq!(pop.value_at(0.15), Some(1.1));
+    assert_eq!(pop.value_at(pop.duration()), Some(1.0));
+    let pop = pop_out();
+    assert_eq!(pop.value_at(0.0), Some(1.0));
+    assert!(pop.value_at(pop.duration()).unwrap().abs() < 1e-6);
+    // Each phase moves in one direction.
+    let values: Vec<f32> = (0..=64).filter_map(|i| pop.value_at(pop.duration() * i as f32 / 64.0)).collect();
+    let peak = (0..values.len()).fold(0, |peak, i| if values[i] > values[peak] { i } else { peak });
+    assert!(values[peak] > 1.0);
+    assert!(values[..=peak].windows(2).all(|w| w[0] <= w[1]));
+    assert!(values[peak..].windows(2).all(|w| w[0] >= w[1]));
+}
@@ -0,0 +1,68 @@
+//! Mapping of unbounded progress into the range from zero to one
+//!
+//! Easings and lookup tables are defined from zero to one.
+//! Progress outside that range is clamped by default,
+//! or wrapped or mirrored for looping content.
+
+use float::Float;
+
+/// How progress outside the range from zero to one is mapped into it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum ProgressMode {
+    /// Holds zero before the range and one after it.
+    #[default]
+    Clamp,
+    /// Starts over from zero, like a loop.
+    Wrap,
+    /// Goes back and forth between zero and one, like a ping-pong loop.
+    Mirror,
+}
+
+impl ProgressMode {
+    /// Maps `t` into the range from zero to one.
+    pub fn apply<T: Float>(self, t: T) -> T {
+        match self {
+            ProgressMode::Clamp => clamp01(t),
+            ProgressMode::Wrap => wrap01(t),
+            ProgressMode::Mirror => mirror01(t),
+        }
+    }
+}
+
+/// Clamps `t` to the range from zero to one. NaN is passed through.
+pub fn clamp01<T: Float>(t: T) -> T {
+    if t < T::zero() {
+        T::zero()
+    } else if t > T::one() {
+        T::one()
+    } else {
+        t
+    }
+}
+
+/// Returns the fractional part of `t`, so `1.25` and `-0.75` both give `0.25`.
+pub fn wrap01<T: Float>(t: T) -> T {
+    t - t.floor()
+}
+
+/// Folds `t` back and forth, so `1.25` gives `0.75` and `2.25` gives `0.25`.
+pub fn mirror01<T: Float>(t: T) -> T {
+    let two = T::one() + T::one();
+    let t = t - (t / two).floor() * two;
+    if t > T::one() { two - t } else { t }
+}
+
+#[test]
+fn progress_modes() {
+    assert_eq!(clamp01(-0.5f32), 0.0);
+    assert_eq!(clamp01(1.5f32), 1.0);
+    assert_eq!(wrap01(1.25f32), 0.25);
+    assert_eq!(wrap01(-0.75f64), 0.25);
+    assert_eq!(mirror01(1.25f32), 0.75);
+    assert_eq!(mirror01(2.25f32), 0.25);
+    assert_eq!(mirror01(-0.25f64), 0.25);
+    assert_eq!(ProgressMode::Wrap.apply(3.5f32), 0.5);
+    assert_eq!(ProgressMode::default(), ProgressMode::Clamp);
+}
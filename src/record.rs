@@ -0,0 +1,163 @@
+//! Sampling curves and animations for debugging and testing
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use animate::Animate;
+use float::Float;
+
+/// Samples `f` at `n` evenly spaced points from zero to one, inclusive.
+///
+/// This works with easings, curves or anything else
+/// that can be evaluated at a normalized parameter.
+pub fn record<S, V, F>(n: usize, mut f: F) -> Vec<(S, V)>
+    where S: Float, F: FnMut(S) -> V
+{
+    match n {
+        0 => vec![],
+        1 => vec![(S::zero(), f(S::zero()))],
+        _ => (0..n).map(|i| {
+            let t = S::from_f64(i as f64 / (n - 1) as f64);
+            (t, f(t))
+        }).collect(),
+    }
+}
+
+/// Runs an animation for `steps` updates of `dt`,
+/// recording the time and value before the first and after every update.
+pub fn trace<A>(animation: &mut A, dt: A::Time, steps: usize) -> Vec<(A::Time, A::Value)>
+    where A: Animate, A::Time: Float
+{
+    let mut time = A::Time::zero();
+    let mut samples = Vec::with_capacity(steps + 1);
+    samples.push((time, animation.value()));
+    for _ in 0..steps {
+        animation.update(dt);
+        time = time + dt;
+        samples.push((time, animation.value()));
+    }
+    samples
+}
+
+/// Values that are written as one or more columns by `export_csv` and `export_tsv`.
+///
+/// Numbers are written as one `value` column, and arrays, such as
+/// points of a 2D or 3D track, as one column per component,
+/// from `value0` to `value1` and so on.
+pub trait Columns {
+    /// Writes the names of the columns, each after `delimiter`.
+    fn write_header<W: Write>(w: &mut W, delimiter: char) -> io::Result<()>;
+
+    /// Writes the values of the columns, each after `delimiter`.
+    fn write_values<W: Write>(&self, w: &mut W, delimiter: char) -> io::Result<()>;
+}
+
+macro_rules! impl_columns_for_number {
+    ($($t:ty),*) => {$(
+        impl Columns for $t {
+            fn write_header<W: Write>(w: &mut W, delimiter: char) -> io::Result<()> {
+                write!(w, "{}value", delimiter)
+            }
+
+            fn write_values<W: Write>(&self, w: &mut W, delimiter: char) -> io::Result<()> {
+                write!(w, "{}{}", delimiter, self)
+            }
+        }
+    )*};
+}
+
+impl_columns_for_number!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: Display, const N: usize> Columns for [T; N] {
+    fn write_header<W: Write>(w: &mut W, delimiter: char) -> io::Result<()> {
+        for i in 0..N {
+            write!(w, "{}value{}", delimiter, i)?;
+        }
+        Ok(())
+    }
+
+    fn write_values<W: Write>(&self, w: &mut W, delimiter: char) -> io::Result<()> {
+        for x in self {
+            write!(w, "{}{}", delimiter, x)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes samples as comma separated values, with a `t,value` header,
+/// for inspecting in spreadsheets or external plotting tools.
+///
+/// Arrays get one column per component, with a `t,value0,value1` header for 2D points.
+pub fn export_csv<W, S, V>(w: &mut W, samples: &[(S, V)]) -> io::Result<()>
+    where W: Write, S: Display, V: Columns
+{
+    export_delimited(w, samples, ',')
+}
+
+/// Writes samples as tab separated values, with a `t\tvalue` header.
+///
+/// Arrays get one column per component, like in `export_csv`.
+pub fn export_tsv<W, S, V>(w: &mut W, samples: &[(S, V)]) -> io::Result<()>
+    where W: Write, S: Display, V: Columns
+{
+    export_delimited(w, samples, '\t')
+}
+
+fn export_delimited<W, S, V>(w: &mut W, samples: &[(S, V)], delimiter: char) -> io::Result<()>
+    where W: Write, S: Display, V: Columns
+{
+    write!(w, "t")?;
+    V::write_header(w, delimiter)?;
+    writeln!(w)?;
+    for (t, v) in samples {
+        write!(w, "{}", t)?;
+        v.write_values(w, delimiter)?;
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn record_ease() {
+    use ease::{Ease, EaseFunction};
+
+    let samples = record(3, |t: f64| t.calc(EaseFunction::QuadraticIn));
+    assert_eq!(samples, vec![(0.0, 0.0), (0.5, 0.25), (1.0, 1.0)]);
+    assert_eq!(record(0, |t: f32| t).len(), 0);
+}
+
+#[test]
+fn trace_tween() {
+    use tween::Tween;
+
+    let mut tween = Tween::new(0.0f32, 4.0, 1.0);
+    let samples = trace(&mut tween, 0.5, 3);
+    assert_eq!(samples, vec![(0.0, 0.0), (0.5, 2.0), (1.0, 4.0), (1.5, 4.0)]);
+}
+
+#[test]
+fn export_samples() {
+    let samples = record(3, |t: f32| t * 2.0);
+    let mut csv = vec![];
+    export_csv(&mut csv, &samples).unwrap();
+    assert_eq!(String::from_utf8(csv).unwrap(), "t,value\n0,0\n0.5,1\n1,2\n");
+
+    let mut tsv = vec![];
+    export_tsv(&mut tsv, &samples[..1]).unwrap();
+    assert_eq!(String::from_utf8(tsv).unwrap(), "t\tvalue\n0\t0\n");
+}
+
+#[test]
+fn export_track_2d() {
+    use tween::Tween;
+
+    let mut tween = Tween::new([0.0f32, 4.0], [2.0, 0.0], 1.0);
+    let samples = trace(&mut tween, 0.5, 2);
+    let mut csv = vec![];
+    export_csv(&mut csv, &samples).unwrap();
+    assert_eq!(String::from_utf8(csv).unwrap(), "t,value0,value1\n0,0,4\n0.5,1,2\n1,2,0\n");
+
+    let mut tsv = vec![];
+    export_tsv(&mut tsv, &record(2, |t: f64| [t, 2.0 * t, 3.0])).unwrap();
+    assert_eq!(String::from_utf8(tsv).unwrap(), "t\tvalue0\tvalue1\tvalue2\n0\t0\t0\t3\n1\t1\t2\t3\n");
+}
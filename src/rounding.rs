@@ -0,0 +1,76 @@
+//! Rounding of interpolated integers
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use float::Float;
+
+/// Describes how an interpolated value between two integers is rounded.
+///
+/// Used by `LerpRound`, for example to snap pixels with `Floor`,
+/// or to avoid the bias of rounding in dithered animations.
+pub trait Rounding<S> {
+    /// Returns `true` to round up, given the fractional part `frac`
+    /// of the interpolated value, which is greater than zero and less than one.
+    ///
+    /// `odd` is `true` when the integer below the value is odd,
+    /// and `above_start` is `true` when the value is above the start of the interpolation.
+    fn round_up(&mut self, frac: S, odd: bool, above_start: bool) -> bool;
+}
+
+/// Deterministic rounding policies.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum RoundingMode {
+    /// Rounds to the nearest integer, with halfway cases away from the start.
+    /// This is what `Lerp` uses for integers.
+    #[default]
+    Nearest,
+    /// Rounds to the nearest integer, with halfway cases to the even integer.
+    NearestEven,
+    /// Rounds down.
+    Floor,
+    /// Rounds up.
+    Ceil,
+}
+
+impl<S: Float> Rounding<S> for RoundingMode {
+    #[inline(always)]
+    fn round_up(&mut self, frac: S, odd: bool, above_start: bool) -> bool {
+        let half = S::from_f64(0.5);
+        match *self {
+            RoundingMode::Nearest => frac > half || (frac == half && above_start),
+            RoundingMode::NearestEven => frac > half || (frac == half && odd),
+            RoundingMode::Floor => false,
+            RoundingMode::Ceil => true,
+        }
+    }
+}
+
+/// Rounds up with a probability equal to the fractional part,
+/// so the rounded values are right on average.
+///
+/// Pass a seeded random number generator to get the same values every run.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone)]
+pub struct Stochastic<R>(pub R);
+
+#[cfg(feature = "rand")]
+impl<S: Float, R: Rng> Rounding<S> for Stochastic<R> {
+    fn round_up(&mut self, frac: S, _odd: bool, _above_start: bool) -> bool {
+        self.0.gen::<f64>() < frac.to_f64()
+    }
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn rounding_stochastic() {
+    use lerp::LerpRound;
+    use rand::rngs::mock::StepRng;
+
+    let mut rounding = Stochastic(StepRng::new(0, u64::MAX / 1000 + 1));
+    let sum: u32 = (0..1000).map(|_| 0u32.lerp_round(&4, &0.3125, &mut rounding)).sum();
+    assert_eq!(sum, 1250);
+    assert_eq!(0u32.lerp_round(&4, &0.25, &mut rounding), 1);
+}
@@ -0,0 +1,161 @@
+//! Animation storage split into shards that are updated independently
+
+use alloc::vec::Vec;
+
+use animate::Animate;
+use arena::{Arena, Handle};
+
+/// A stable reference to a value stored in a `ShardedArena`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShardedHandle {
+    shard: u32,
+    handle: Handle,
+}
+
+impl ShardedHandle {
+    /// Returns the index of the shard holding the value.
+    pub fn shard(&self) -> usize {
+        self.shard as usize
+    }
+
+    /// Returns the handle of the value within its shard.
+    pub fn handle(&self) -> Handle {
+        self.handle
+    }
+}
+
+/// Stores animations in several arenas, so they can be updated in parallel.
+///
+/// Each shard is an ordinary `Arena`. The shards are disjoint,
+/// so a job system can take `shards_mut` apart and update every shard
+/// on a different thread, without locking and without `unsafe`.
+/// New values go to the shard with the fewest values.
+#[derive(Debug, Clone)]
+pub struct ShardedArena<T> {
+    shards: Vec<Arena<T>>,
+}
+
+impl<T> ShardedArena<T> {
+    /// Creates an arena with `shards` empty shards, and at least one.
+    pub fn new(shards: usize) -> ShardedArena<T> {
+        ShardedArena { shards: (0..shards.max(1)).map(|_| Arena::new()).collect() }
+    }
+
+    /// Inserts a value into the least occupied shard, returning its handle.
+    pub fn insert(&mut self, value: T) -> ShardedHandle {
+        match self.shards.iter_mut().enumerate().min_by_key(|(_, arena)| arena.len()) {
+            Some((shard, arena)) => ShardedHandle { shard: shard as u32, handle: arena.insert(value) },
+            None => {
+                self.shards.push(Arena::new());
+                self.insert(value)
+            }
+        }
+    }
+
+    /// Inserts a value into a given shard, or gives it back if there is no such shard.
+    pub fn insert_into(&mut self, shard: usize, value: T) -> Result<ShardedHandle, T> {
+        match self.shards.get_mut(shard) {
+            Some(arena) => Ok(ShardedHandle { shard: shard as u32, handle: arena.insert(value) }),
+            None => Err(value),
+        }
+    }
+
+    /// Removes a value, returning it if the handle was valid.
+    pub fn remove(&mut self, handle: ShardedHandle) -> Option<T> {
+        self.shards.get_mut(handle.shard())?.remove(handle.handle)
+    }
+
+    /// Returns the value of a handle.
+    pub fn get(&self, handle: ShardedHandle) -> Option<&T> {
+        self.shards.get(handle.shard())?.get(handle.handle)
+    }
+
+    /// Returns the value of a handle mutably.
+    pub fn get_mut(&mut self, handle: ShardedHandle) -> Option<&mut T> {
+        self.shards.get_mut(handle.shard())?.get_mut(handle.handle)
+    }
+
+    /// Returns the number of stored values in all shards.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(Arena::len).sum()
+    }
+
+    /// Returns `true` if there are no stored values.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(Arena::is_empty)
+    }
+
+    /// Returns the shards.
+    pub fn shards(&self) -> &[Arena<T>] {
+        &self.shards
+    }
+
+    /// Returns the shards mutably, to hand them out to different threads.
+    pub fn shards_mut(&mut self) -> &mut [Arena<T>] {
+        &mut self.shards
+    }
+}
+
+impl<A> ShardedArena<A>
+    where A: Animate, A::Time: Copy
+{
+    /// Advances every stored animation by `dt`, one shard after another.
+    pub fn update(&mut self, dt: A::Time) {
+        for arena in self.shards.iter_mut() {
+            arena.update(dt);
+        }
+    }
+
+    /// Removes finished animations, passing each of them to `f`.
+    pub fn remove_finished<F>(&mut self, mut f: F)
+        where F: FnMut(ShardedHandle, A)
+    {
+        for (shard, arena) in self.shards.iter_mut().enumerate() {
+            arena.remove_finished(|handle, animation| {
+                f(ShardedHandle { shard: shard as u32, handle }, animation)
+            });
+        }
+    }
+}
+
+#[test]
+fn sharded_threads() {
+    use tween::Tween;
+
+    let mut arena = ShardedArena::new(4);
+    let handles: Vec<_> = (0..64).map(|i| arena.insert(Tween::new(0.0f32, 1.0, 1.0 + i as f32))).collect();
+    assert_eq!(arena.len(), 64);
+    assert!(arena.shards().iter().all(|s| s.len() == 16));
+
+    ::std::thread::scope(|s| {
+        for shard in arena.shards_mut() {
+            s.spawn(move || shard.update(2.0));
+        }
+    });
+    let mut finished = vec![];
+    arena.remove_finished(|handle, tween| finished.push((handle, tween.value())));
+    assert_eq!(finished.len(), 2);
+    assert!(finished.contains(&(handles[0], 1.0)));
+    assert_eq!(arena.get(handles[3]).map(|t| t.value()), Some(0.5));
+    assert_eq!(arena.remove(handles[3]).map(|t| t.duration), Some(4.0));
+    assert_eq!(arena.get(handles[3]), None);
+    assert_eq!(arena.insert_into(9, Tween::new(0.0, 1.0, 1.0)).map_err(|t| t.duration), Err(1.0));
+}
+
+#[test]
+fn stateful_send_sync() {
+    use bake::{BakedTrack, EaseTable};
+    use curve::AnimationCurve;
+    use path::Path;
+    use timeline::Timeline;
+    use tween::Tween;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<BakedTrack<f32>>();
+    assert_send_sync::<EaseTable>();
+    assert_send_sync::<AnimationCurve>();
+    assert_send_sync::<Path<[f32; 2]>>();
+    assert_send_sync::<Timeline<f32>>();
+    assert_send_sync::<ShardedArena<Tween<f32>>>();
+}
@@ -0,0 +1,170 @@
+//! Tweens that can be driven and read from several threads
+//!
+//! The stateless types of the crate, such as easings, curves and the parameters
+//! of tweens, are plain data and are `Send` and `Sync`.
+//! Only the elapsed time changes while an animation plays, so `SharedTween`
+//! keeps it in an atomic, and can be updated through a shared reference.
+//! This needs compare-and-swap on 32 bits, so it is left out on targets without it.
+
+#[cfg(loom)]
+use loom::sync::atomic::{AtomicU32, Ordering};
+#[cfg(not(loom))]
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use lerp::Lerp;
+use tween::Tween;
+
+/// A time in seconds that can be read and advanced atomically.
+///
+/// The bits of the `f32` are stored in an `AtomicU32`.
+#[derive(Debug)]
+pub struct AtomicTime {
+    bits: AtomicU32,
+}
+
+impl AtomicTime {
+    /// Creates a time.
+    pub fn new(time: f32) -> AtomicTime {
+        AtomicTime { bits: AtomicU32::new(time.to_bits()) }
+    }
+
+    /// Returns the time.
+    pub fn load(&self) -> f32 {
+        f32::from_bits(self.bits.load(Ordering::Acquire))
+    }
+
+    /// Replaces the time.
+    pub fn store(&self, time: f32) {
+        self.bits.store(time.to_bits(), Ordering::Release)
+    }
+
+    /// Adds `dt` to the time, stopping at `max`, and returns the new time.
+    ///
+    /// Concurrent calls are all applied, as if they happened one after another.
+    pub fn advance(&self, dt: f32, max: f32) -> f32 {
+        let mut current = self.bits.load(Ordering::Acquire);
+        loop {
+            let time = f32::from_bits(current) + dt;
+            let time = if time > max { max } else { time };
+            match self.bits.compare_exchange(current, time.to_bits(), Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return time,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+/// A tween whose elapsed time is atomic, so it can be updated through `&self`.
+///
+/// A job system can advance it on one thread while others read its value,
+/// without locking.
+#[derive(Debug)]
+pub struct SharedTween<T: Lerp<Scalar = f32>> {
+    tween: Tween<T>,
+    elapsed: AtomicTime,
+}
+
+impl<T> SharedTween<T>
+    where T: Lerp<Scalar = f32>
+{
+    /// Shares a tween, starting at its elapsed time.
+    pub fn new(tween: Tween<T>) -> SharedTween<T> {
+        let elapsed = AtomicTime::new(tween.elapsed());
+        SharedTween { tween, elapsed }
+    }
+
+    /// Returns the parameters of the tween.
+    pub fn tween(&self) -> &Tween<T> {
+        &self.tween
+    }
+
+    /// Returns a tween with the current elapsed time.
+    pub fn into_tween(self) -> Tween<T> {
+        let mut tween = self.tween;
+        tween.seek(self.elapsed.load());
+        tween
+    }
+
+    /// Advances the tween by `dt`.
+    pub fn update(&self, dt: f32) {
+        self.elapsed.advance(dt, self.tween.total_duration());
+    }
+
+    /// Moves to `time` since the start, clamped to the total duration.
+    pub fn seek(&self, time: f32) {
+        let total = self.tween.total_duration();
+        self.elapsed.store(if time < 0.0 { 0.0 } else if time > total { total } else { time });
+    }
+
+    /// Returns the time elapsed since the start, including the delay.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed.load()
+    }
+
+    /// Returns `true` when the end has been reached.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed() >= self.tween.total_duration()
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> T {
+        self.tween.value_at(self.elapsed())
+    }
+}
+
+#[test]
+fn stateless_send_sync() {
+    use bezier_ease::CubicBezierEase;
+    use ease::{EaseFunction, Easing};
+    use oscillator::Oscillator;
+    use progress::ProgressMode;
+    use transform::TransformTween;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<EaseFunction>();
+    assert_send_sync::<Easing>();
+    assert_send_sync::<CubicBezierEase>();
+    assert_send_sync::<ProgressMode>();
+    assert_send_sync::<Oscillator<f32>>();
+    assert_send_sync::<Tween<[f64; 3]>>();
+    assert_send_sync::<TransformTween<[f32; 3], [f32; 4]>>();
+    assert_send_sync::<SharedTween<[f32; 2]>>();
+}
+
+#[cfg(not(loom))]
+#[test]
+fn shared_tween_threads() {
+    let tween = SharedTween::new(Tween::new(0.0f32, 100.0, 1.0));
+    ::std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| for _ in 0..64 { tween.update(1.0 / 1024.0) });
+        }
+        s.spawn(|| for _ in 0..64 { assert!((0.0..=100.0).contains(&tween.value())) });
+    });
+    assert_eq!(tween.elapsed(), 0.25);
+    assert_eq!(tween.value(), 25.0);
+    tween.seek(2.0);
+    assert!(tween.is_finished());
+    assert_eq!(tween.into_tween().value(), 100.0);
+}
+
+#[cfg(loom)]
+#[test]
+fn atomic_time_loom() {
+    use loom::sync::Arc;
+    use loom::thread;
+
+    loom::model(|| {
+        let time = Arc::new(AtomicTime::new(0.0));
+        let threads: Vec<_> = (0..2).map(|_| {
+            let time = time.clone();
+            thread::spawn(move || { time.advance(0.25, 10.0); })
+        }).collect();
+        time.advance(0.5, 10.0);
+        for t in threads {
+            t.join().unwrap();
+        }
+        assert_eq!(time.load(), 1.0);
+    });
+}
@@ -0,0 +1,91 @@
+//! Spherical linear interpolation
+
+use num_traits::Float;
+
+/// Performs spherical linear interpolation.
+/// Rotations are interpolated along the shortest arc
+/// with constant angular velocity.
+/// When 't' is zero then 'a' has full weight.
+/// When 't' is one then 'b' has full weight.
+#[inline(always)]
+pub fn slerp<T: Slerp>(a: &T, b: &T, t: &T::Scalar) -> T {
+    a.slerp(b, t)
+}
+
+/// Describes a rotation that can be interpolated along the shortest arc.
+pub trait Slerp {
+    /// The scaling type for spherical interpolation.
+    type Scalar;
+
+    /// Given `self` and another rotation `other`, return the rotation that is
+    /// `scalar` fraction of the angle between the two.
+    fn slerp(&self, other: &Self, scalar: &Self::Scalar) -> Self;
+}
+
+/// Implementation of `Slerp` for unit quaternions stored as `[x, y, z, w]`.
+/// Falls back to normalized linear interpolation when the rotations are nearly parallel.
+macro_rules! impl_slerp_for_quaternion {
+    ($float: ident) => (
+        impl Slerp for [$float; 4] {
+            type Scalar = $float;
+
+            fn slerp(&self, other: &[$float; 4], scalar: &$float) -> [$float; 4] {
+                let a = self;
+                let mut b = *other;
+                let mut dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3];
+                if dot < 0.0 {
+                    dot = -dot;
+                    b = [-b[0], -b[1], -b[2], -b[3]];
+                }
+
+                let t = *scalar;
+                let (wa, wb) = if dot > 0.9995 {
+                    (1.0 - t, t)
+                } else {
+                    let theta = Float::acos(dot);
+                    let sin_theta = Float::sin(theta);
+                    (Float::sin((1.0 - t) * theta) / sin_theta, Float::sin(t * theta) / sin_theta)
+                };
+
+                let q = [
+                    a[0] * wa + b[0] * wb,
+                    a[1] * wa + b[1] * wb,
+                    a[2] * wa + b[2] * wb,
+                    a[3] * wa + b[3] * wb,
+                ];
+                let len = Float::sqrt(q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]);
+                [q[0] / len, q[1] / len, q[2] / len, q[3] / len]
+            }
+        }
+    )
+}
+
+impl_slerp_for_quaternion!(f32);
+impl_slerp_for_quaternion!(f64);
+
+#[test]
+fn slerp_endpoints() {
+    let a = [0.0, 0.0, 0.0, 1.0];
+    let b = [0.0, 0.0, 1.0, 0.0];
+    assert_eq!(slerp(&a, &b, &0.0f64), a);
+    assert_eq!(slerp(&a, &b, &1.0f64), b);
+}
+
+#[test]
+fn slerp_half_angle() {
+    // 180 degrees around z, halfway is 90 degrees.
+    let a = [0.0, 0.0, 0.0, 1.0];
+    let b = [0.0, 0.0, 1.0, 0.0];
+    let q = slerp(&a, &b, &0.5f64);
+    let h = (0.5f64).sqrt();
+    assert!((q[2] - h).abs() < 1e-12);
+    assert!((q[3] - h).abs() < 1e-12);
+}
+
+#[test]
+fn slerp_shortest_arc() {
+    let a = [0.0, 0.0, 0.0, 1.0f32];
+    let b = [0.0, 0.0, 0.0, -1.0f32];
+    let q = slerp(&a, &b, &0.5);
+    assert_eq!(q, a);
+}
@@ -0,0 +1,202 @@
+//! Vector space operations
+
+use core::ops::Add;
+
+use float::{mul_add, Float};
+
+/// Describes a type that can be added, subtracted and scaled.
+///
+/// This is used by interpolation algorithms that need weighted sums
+/// of points instead of nested linear interpolations.
+pub trait Spatial {
+    /// The scaling type.
+    type Scalar;
+
+    /// Adds `other` to `self`.
+    fn add(&self, other: &Self) -> Self;
+
+    /// Subtracts `other` from `self`.
+    fn sub(&self, other: &Self) -> Self;
+
+    /// Multiplies `self` by `scalar`.
+    fn scale(&self, scalar: &Self::Scalar) -> Self;
+
+    /// Multiplies `self` by `scalar` and adds `other`.
+    ///
+    /// For floats and arrays of them, this is fused with the `fma` feature.
+    #[inline(always)]
+    fn mul_add(&self, scalar: &Self::Scalar, other: &Self) -> Self
+        where Self: Sized
+    {
+        self.scale(scalar).add(other)
+    }
+}
+
+/// Implementation of `Spatial` for floats.
+macro_rules! impl_spatial_for_float {
+    ($float: ident) => (
+        impl Spatial for $float {
+            type Scalar = $float;
+
+            #[inline(always)]
+            fn add(&self, other: &$float) -> $float { self + other }
+
+            #[inline(always)]
+            fn sub(&self, other: &$float) -> $float { self - other }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &$float) -> $float { self * scalar }
+
+            #[inline(always)]
+            fn mul_add(&self, scalar: &$float, other: &$float) -> $float {
+                mul_add(*self, *scalar, *other)
+            }
+        }
+    )
+}
+
+impl_spatial_for_float!(f32);
+impl_spatial_for_float!(f64);
+
+/// Transitive impl of `Spatial` for arrays, given a length and index list
+macro_rules! impl_spatial_for_array {
+    ($len:expr; $($i:expr),*) => {
+        impl<T> Spatial for [T; $len] where T: Spatial {
+            type Scalar = T::Scalar;
+
+            #[inline(always)]
+            fn add(&self, other: &Self) -> Self {
+                [$(self[$i].add(&other[$i])),*]
+            }
+
+            #[inline(always)]
+            fn sub(&self, other: &Self) -> Self {
+                [$(self[$i].sub(&other[$i])),*]
+            }
+
+            #[inline(always)]
+            fn scale(&self, scalar: &Self::Scalar) -> Self {
+                [$(self[$i].scale(scalar)),*]
+            }
+
+            #[inline(always)]
+            fn mul_add(&self, scalar: &Self::Scalar, other: &Self) -> Self {
+                [$(self[$i].mul_add(scalar, &other[$i])),*]
+            }
+        }
+    }
+}
+
+impl_spatial_for_array!(1; 0);
+impl_spatial_for_array!(2; 0, 1);
+impl_spatial_for_array!(3; 0, 1, 2);
+impl_spatial_for_array!(4; 0, 1, 2, 3);
+impl_spatial_for_array!(5; 0, 1, 2, 3, 4);
+
+/// Describes a `Spatial` type with a dot product, so lengths can be measured.
+///
+/// This is used for arc length and other geometric queries on curves.
+pub trait InnerSpace: Spatial {
+    /// Returns the dot product of `self` and `other`.
+    fn dot(&self, other: &Self) -> Self::Scalar;
+
+    /// Returns the length of `self`.
+    #[inline(always)]
+    fn length(&self) -> Self::Scalar where Self::Scalar: Float {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the distance between `self` and `other`.
+    #[inline(always)]
+    fn distance(&self, other: &Self) -> Self::Scalar where Self: Sized, Self::Scalar: Float {
+        self.sub(other).length()
+    }
+}
+
+impl InnerSpace for f32 {
+    #[inline(always)]
+    fn dot(&self, other: &f32) -> f32 { self * other }
+}
+
+impl InnerSpace for f64 {
+    #[inline(always)]
+    fn dot(&self, other: &f64) -> f64 { self * other }
+}
+
+/// Transitive impl of `InnerSpace` for arrays, given a length and index list
+macro_rules! impl_inner_space_for_array {
+    ($len:expr; $i0:expr $(, $i:expr)*) => {
+        impl<T> InnerSpace for [T; $len]
+            where T: InnerSpace, T::Scalar: Add<Output = T::Scalar>
+        {
+            #[inline(always)]
+            fn dot(&self, other: &Self) -> T::Scalar {
+                self[$i0].dot(&other[$i0])$( + self[$i].dot(&other[$i]))*
+            }
+        }
+    }
+}
+
+impl_inner_space_for_array!(1; 0);
+impl_inner_space_for_array!(2; 0, 1);
+impl_inner_space_for_array!(3; 0, 1, 2);
+impl_inner_space_for_array!(4; 0, 1, 2, 3);
+impl_inner_space_for_array!(5; 0, 1, 2, 3, 4);
+
+/// Describes a two-dimensional `InnerSpace`, with a perpendicular direction.
+///
+/// This is used for normals, curvature and other queries on planar curves.
+pub trait Planar: InnerSpace {
+    /// Returns `self` rotated by a quarter turn, from the first axis towards the second.
+    fn perp(&self) -> Self;
+
+    /// Returns the cross product of `self` and `other`, which is positive
+    /// when `other` points to the side that `perp` turns to.
+    #[inline(always)]
+    fn cross(&self, other: &Self) -> Self::Scalar where Self: Sized {
+        self.perp().dot(other)
+    }
+}
+
+/// Implementation of `Planar` for pairs of floats.
+macro_rules! impl_planar_for_float_pair {
+    ($float: ident) => (
+        impl Planar for [$float; 2] {
+            #[inline(always)]
+            fn perp(&self) -> [$float; 2] {
+                let [x, y] = *self;
+                [-y, x]
+            }
+        }
+    )
+}
+
+impl_planar_for_float_pair!(f32);
+impl_planar_for_float_pair!(f64);
+
+#[test]
+fn spatial_array() {
+    let a = [1.0f32, 2.0];
+    let b = [3.0, 5.0];
+    assert_eq!(a.add(&b), [4.0, 7.0]);
+    assert_eq!(b.sub(&a), [2.0, 3.0]);
+    assert_eq!(a.scale(&2.0f32), [2.0, 4.0]);
+    assert_eq!(a.mul_add(&2.0f32, &b), [5.0, 9.0]);
+}
+
+#[test]
+fn inner_space_array() {
+    let a = [3.0f64, 4.0];
+    assert_eq!(a.dot(&[1.0, 2.0]), 11.0);
+    assert_eq!(a.length(), 5.0);
+    assert_eq!(a.distance(&[0.0, 8.0]), 5.0);
+    assert_eq!((-2.0f32).length(), 2.0);
+}
+
+#[test]
+fn planar_pair() {
+    let a = [1.0f32, 2.0];
+    assert_eq!(a.perp(), [-2.0, 1.0]);
+    assert_eq!(a.cross(&[0.0, 1.0]), 1.0);
+    assert_eq!([1.0f64, 0.0].cross(&[0.0, -1.0]), -1.0);
+}
@@ -0,0 +1,100 @@
+//! Discrete keyframes that hold their value until the next key
+
+use alloc::vec::Vec;
+
+/// A track of values keyed by time, without interpolation.
+///
+/// Each value is held until the time of the next key,
+/// which suits sprite frame indices, flags and other values
+/// that can not be interpolated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepTrack<V, S = f32> {
+    keys: Vec<(S, V)>,
+}
+
+impl<V, S> Default for StepTrack<V, S> {
+    fn default() -> StepTrack<V, S> {
+        StepTrack { keys: Vec::new() }
+    }
+}
+
+impl<V, S> StepTrack<V, S>
+    where V: Clone, S: Copy + PartialOrd
+{
+    /// Creates an empty track.
+    pub fn new() -> StepTrack<V, S> {
+        StepTrack::default()
+    }
+
+    /// Inserts a key, replacing any key at the same time.
+    pub fn insert(&mut self, time: S, value: V) {
+        let i = self.keys.iter().position(|&(t, _)| t >= time).unwrap_or(self.keys.len());
+        match self.keys.get_mut(i) {
+            Some(key) if key.0 == time => key.1 = value,
+            _ => self.keys.insert(i, (time, value)),
+        }
+    }
+
+    /// Returns the keys sorted by time.
+    pub fn keys(&self) -> &[(S, V)] {
+        &self.keys
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the track has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the value held at `time`,
+    /// or `None` if `time` is before the first key.
+    pub fn sample(&self, time: S) -> Option<&V> {
+        self.keys.iter().take_while(|&&(t, _)| t <= time).last().map(|(_, v)| v)
+    }
+
+    /// Returns the keys passed when moving from `from` to `to`,
+    /// excluding `from` and including `to`.
+    ///
+    /// This is used to fire triggers such as sound cues once per key.
+    pub fn crossed(&self, from: S, to: S) -> impl Iterator<Item = &(S, V)> {
+        self.keys.iter().filter(move |&&(t, _)| t > from && t <= to)
+    }
+}
+
+/// Keys are inserted one by one, so the track stays sorted.
+#[cfg(feature = "arbitrary")]
+impl<'a, V, S> ::arbitrary::Arbitrary<'a> for StepTrack<V, S>
+    where V: Clone + ::arbitrary::Arbitrary<'a>, S: Copy + PartialOrd + ::arbitrary::Arbitrary<'a>
+{
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<StepTrack<V, S>> {
+        let mut track = StepTrack::new();
+        for key in u.arbitrary_iter::<(S, V)>()? {
+            let (time, value) = key?;
+            track.insert(time, value);
+        }
+        Ok(track)
+    }
+}
+
+#[test]
+fn step_track_hold() {
+    let mut track = StepTrack::new();
+    track.insert(1.0f32, 'b');
+    track.insert(0.0, 'a');
+    track.insert(2.0, 'c');
+    track.insert(1.0, 'B');
+
+    assert_eq!(track.len(), 3);
+    assert_eq!(track.sample(-1.0), None);
+    assert_eq!(track.sample(0.0), Some(&'a'));
+    assert_eq!(track.sample(0.99), Some(&'a'));
+    assert_eq!(track.sample(1.0), Some(&'B'));
+    assert_eq!(track.sample(5.0), Some(&'c'));
+
+    let crossed: Vec<char> = track.crossed(0.0, 2.0).map(|&(_, v)| v).collect();
+    assert_eq!(crossed, vec!['B', 'c']);
+}
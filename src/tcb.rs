@@ -0,0 +1,164 @@
+//! Kochanek-Bartels splines, with tension, continuity and bias per key
+
+use alloc::vec::Vec;
+
+use error::{self, check_increasing};
+use hermite::hermite;
+use spatial::Spatial;
+
+/// A key of a `TcbSpline`.
+///
+/// With all three parameters at zero, the spline is a Catmull-Rom spline.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TcbKey<T> {
+    /// The time of the key.
+    pub time: f32,
+    /// The value at the key.
+    pub value: T,
+    /// Shortens the tangents towards `1.0`, and lengthens them towards `-1.0`.
+    pub tension: f32,
+    /// Makes a corner at the key towards `-1.0` or `1.0`,
+    /// with the corner pointing inwards or outwards.
+    pub continuity: f32,
+    /// Turns the tangents towards the previous key at `1.0`,
+    /// or the next key at `-1.0`, so the curve overshoots or anticipates.
+    pub bias: f32,
+}
+
+impl<T> TcbKey<T> {
+    /// Creates a key.
+    pub fn new(time: f32, value: T, tension: f32, continuity: f32, bias: f32) -> TcbKey<T> {
+        TcbKey { time, value, tension, continuity, bias }
+    }
+}
+
+/// A spline through keys with tension, continuity and bias,
+/// as used by the keyframe editors of many 3D packages.
+///
+/// The tangents of each key are derived from its neighbours,
+/// and scaled for uneven times between keys, so the speed is continuous
+/// across keys with zero continuity.
+/// The segments are cubic Hermite curves.
+/// The first and last keys use their only neighbour on both sides.
+/// Times outside the key range hold the first or last value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcbSpline<T> {
+    keys: Vec<TcbKey<T>>,
+}
+
+impl<T> TcbSpline<T>
+    where T: Spatial<Scalar = f32> + Clone
+{
+    /// Creates a spline from keys, sorted by time.
+    pub fn new(mut keys: Vec<TcbKey<T>>) -> TcbSpline<T> {
+        keys.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap_or(::core::cmp::Ordering::Equal));
+        TcbSpline { keys }
+    }
+
+    /// Creates a spline from keys, checking that there is at least one key
+    /// and that the keys are in increasing time order, instead of sorting them.
+    pub fn try_new(keys: Vec<TcbKey<T>>) -> error::Result<TcbSpline<T>> {
+        let times: Vec<f32> = keys.iter().map(|k| k.time).collect();
+        check_increasing(&times)?;
+        Ok(TcbSpline { keys })
+    }
+
+    /// Returns the keys in time order.
+    pub fn keys(&self) -> &[TcbKey<T>] {
+        &self.keys
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the incoming and outgoing tangents of key `k`,
+    /// per unit of the segment parameter on either side.
+    pub fn tangents(&self, k: usize) -> Option<(T, T)> {
+        let key = self.keys.get(k)?;
+        let prev = k.checked_sub(1).and_then(|j| self.keys.get(j))
+            .map(|p| (key.value.sub(&p.value), key.time - p.time));
+        let next = self.keys.get(k + 1).map(|n| (n.value.sub(&key.value), n.time - key.time));
+        let ((d_in, dt_in), (d_out, dt_out)) = match (prev, next) {
+            (Some(prev), Some(next)) => (prev, next),
+            (Some(prev), None) => (prev.clone(), prev),
+            (None, Some(next)) => (next.clone(), next),
+            (None, None) => return None,
+        };
+        let (t, c, b) = (1.0 - key.tension, key.continuity, key.bias);
+        // Each tangent is scaled by the time of its own segment
+        // relative to the mean of both, which keeps the speed continuous.
+        let dt = dt_in + dt_out;
+        let (s_in, s_out) = if dt > 0.0 { (t * dt_in / dt, t * dt_out / dt) } else { (0.0, 0.0) };
+        let m_in = d_in.scale(&((1.0 + b) * (1.0 - c) * s_in))
+            .add(&d_out.scale(&((1.0 - b) * (1.0 + c) * s_in)));
+        let m_out = d_in.scale(&((1.0 + b) * (1.0 + c) * s_out))
+            .add(&d_out.scale(&((1.0 - b) * (1.0 - c) * s_out)));
+        Some((m_in, m_out))
+    }
+
+    /// Returns the value at `time`, or `None` if there are no keys.
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let i = self.keys.iter().take_while(|k| k.time <= time).count();
+        let (a, b) = match (i.checked_sub(1).and_then(|j| self.keys.get(j)), self.keys.get(i)) {
+            (Some(a), Some(b)) => (a, b),
+            (Some(a), None) => return Some(a.value.clone()),
+            (None, b) => return b.map(|b| b.value.clone()),
+        };
+        let (_, m0) = self.tangents(i - 1)?;
+        let (m1, _) = self.tangents(i)?;
+        let t = (time - a.time) / (b.time - a.time);
+        Some(hermite(&a.value, &m0, &b.value, &m1, &t))
+    }
+}
+
+#[test]
+fn tcb_catmull_rom() {
+    let values = [[0.0f32, 0.0], [1.0, 2.0], [3.0, 2.0], [4.0, 0.0]];
+    let spline = TcbSpline::new(values.iter().enumerate()
+        .map(|(i, &v)| TcbKey::new(i as f32, v, 0.0, 0.0, 0.0)).collect());
+    for i in 0..=8 {
+        let t = i as f32 / 8.0;
+        let expected = ::catmull_rom(&values[0], &values[1], &values[2], &values[3], &0.0, &t);
+        let p = spline.sample(1.0 + t).unwrap();
+        assert!((p[0] - expected[0]).abs() < 1e-5 && (p[1] - expected[1]).abs() < 1e-5);
+    }
+    assert_eq!(spline.sample(-1.0), Some(values[0]));
+    assert_eq!(spline.sample(9.0), Some(values[3]));
+    assert_eq!(spline.tangents(0), Some(([1.0, 2.0], [1.0, 2.0])));
+    assert_eq!(TcbSpline::<f32>::new(vec![]).sample(0.0), None);
+}
+
+#[test]
+fn tcb_parameters() {
+    let key = |time, value, tension, continuity, bias| TcbKey::new(time, value, tension, continuity, bias);
+    // Full tension gives flat tangents.
+    let spline = TcbSpline::new(vec![key(0.0, 0.0f32, 1.0, 0.0, 0.0), key(2.0, 1.0, 1.0, 0.0, 0.0)]);
+    assert_eq!(spline.sample(1.0), Some(0.5));
+    assert_eq!(spline.tangents(1), Some((0.0, 0.0)));
+    // Continuity makes a corner, and bias turns both tangents towards one side.
+    let keys = vec![key(0.0, 0.0f32, 0.0, 0.0, 0.0), key(1.0, 1.0, 0.0, -1.0, 0.0), key(2.0, 0.0, 0.0, 0.0, 0.0)];
+    assert_eq!(TcbSpline::new(keys.clone()).tangents(1), Some((1.0, -1.0)));
+    let mut biased = keys;
+    biased[1] = key(1.0, 1.0, 0.0, 0.0, 1.0);
+    assert_eq!(TcbSpline::new(biased).tangents(1), Some((1.0, 1.0)));
+    // Uneven times scale the tangents so the speed stays continuous.
+    let spline = TcbSpline::new(vec![key(0.0, 0.0f32, 0.0, 0.0, 0.0), key(1.0, 1.0, 0.0, 0.0, 0.0), key(4.0, 4.0, 0.0, 0.0, 0.0)]);
+    assert_eq!(spline.tangents(1), Some((1.0, 3.0)));
+}
+
+#[test]
+fn tcb_try_new() {
+    use error::InterpolationError::*;
+
+    let keys = vec![TcbKey::new(1.0, 0.0f32, 0.0, 0.0, 0.0), TcbKey::new(0.0, 1.0, 0.0, 0.0, 0.0)];
+    assert_eq!(TcbSpline::try_new(keys.clone()).err(), Some(NotIncreasing { index: 1 }));
+    assert_eq!(TcbSpline::<f32>::try_new(vec![]).err(), Some(Empty));
+    assert!(TcbSpline::try_new(keys.into_iter().rev().collect()).is_ok());
+}
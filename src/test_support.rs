@@ -0,0 +1,87 @@
+//! Reference evaluators and golden samples for validating integrations
+//!
+//! The reference evaluators are slow but simple enough to check by eye.
+//! The golden samples are the canonical results of this crate,
+//! written in a plain text format that round-trips every float exactly.
+//!
+//! Unlike the rest of the crate, these helpers may panic on invalid input.
+
+#![allow(clippy::expect_used, clippy::indexing_slicing)]
+
+use std::io::{self, Write};
+
+use ease::{Ease, EaseFunction};
+use lerp::Lerp;
+
+/// Evaluates a Bézier curve of any degree at `t`
+/// by repeated linear interpolation of the control points.
+///
+/// Panics if `points` is empty.
+pub fn de_casteljau<T>(points: &[T], t: &T::Scalar) -> T
+    where T: Lerp + Clone
+{
+    let mut points = points.to_vec();
+    while points.len() > 1 {
+        points = points.windows(2).map(|w| w[0].lerp(&w[1], t)).collect();
+    }
+    points.pop().expect("a curve needs at least one point")
+}
+
+/// Approximates the length of a curve by summing the distances
+/// between `segments + 1` evenly spaced points.
+pub fn brute_force_arc_length<F, P>(mut f: F, segments: usize) -> f64
+    where F: FnMut(f64) -> P, P: AsRef<[f64]>
+{
+    let segments = segments.max(1);
+    let mut prev = f(0.0);
+    let mut length = 0.0;
+    for i in 1..=segments {
+        let next = f(i as f64 / segments as f64);
+        let d2: f64 = prev.as_ref().iter().zip(next.as_ref())
+            .map(|(a, b)| (b - a) * (b - a))
+            .sum();
+        length += d2.sqrt();
+        prev = next;
+    }
+    length
+}
+
+/// Samples an ease function at `n` evenly spaced points from zero to one, inclusive.
+pub fn golden_ease(f: EaseFunction, n: usize) -> Vec<(f64, f64)> {
+    ::record::record(n, |t: f64| t.calc(f))
+}
+
+/// Writes samples as lines of `name t value`.
+pub fn write_golden<W: Write>(w: &mut W, name: &str, samples: &[(f64, f64)]) -> io::Result<()> {
+    for &(t, v) in samples {
+        writeln!(w, "{} {:?} {:?}", name, t, v)?;
+    }
+    Ok(())
+}
+
+/// Writes `n` golden samples of every ease function.
+pub fn write_golden_eases<W: Write>(w: &mut W, n: usize) -> io::Result<()> {
+    for &f in EaseFunction::ALL.iter() {
+        write_golden(w, &format!("{:?}", f), &golden_ease(f, n))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn reference_evaluators() {
+    let points = [0.0f64, 1.0, 3.0, 2.0];
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let expected = ::cub_bez(&points[0], &points[1], &points[2], &points[3], &t);
+        assert!((de_casteljau(&points, &t) - expected).abs() < 1e-12);
+    }
+
+    let length = brute_force_arc_length(|t| [3.0 * t, 4.0 * t], 10);
+    assert!((length - 5.0).abs() < 1e-12);
+
+    let mut out = vec![];
+    write_golden_eases(&mut out, 2).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.lines().count(), 60);
+    assert!(out.starts_with("QuadraticIn 0.0 0.0\nQuadraticIn 1.0 1.0\n"));
+}
@@ -0,0 +1,112 @@
+//! Events fired when an animated value crosses thresholds
+
+use alloc::vec::Vec;
+
+use animate::Animate;
+
+/// A threshold crossed by an animated value.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Crossing<V> {
+    /// The index of the threshold.
+    pub index: usize,
+    /// The threshold value.
+    pub threshold: V,
+    /// `true` when the value moved upwards through the threshold.
+    pub rising: bool,
+}
+
+/// Wraps an animation and reports when its value crosses thresholds.
+///
+/// A threshold is crossed when the previous value is on one side of it
+/// and the new value is on the threshold or beyond.
+#[derive(Debug, Clone)]
+pub struct Thresholds<A: Animate> {
+    /// The wrapped animation.
+    pub animation: A,
+    thresholds: Vec<A::Value>,
+    previous: A::Value,
+}
+
+impl<A> Thresholds<A>
+    where A: Animate, A::Value: Copy + PartialOrd
+{
+    /// Creates a new threshold watcher.
+    pub fn new(animation: A, thresholds: Vec<A::Value>) -> Thresholds<A> {
+        let previous = animation.value();
+        Thresholds {
+            animation,
+            thresholds,
+            previous,
+        }
+    }
+
+    /// Returns the thresholds.
+    pub fn thresholds(&self) -> &[A::Value] {
+        &self.thresholds
+    }
+
+    /// Advances the animation by `dt`, returning the thresholds crossed.
+    pub fn update(&mut self, dt: A::Time) -> Crossings<'_, A::Value> {
+        self.animation.update(dt);
+        let from = self.previous;
+        let to = self.animation.value();
+        self.previous = to;
+        Crossings {
+            thresholds: &self.thresholds,
+            index: 0,
+            from,
+            to,
+        }
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> A::Value {
+        self.previous
+    }
+}
+
+/// An iterator over the thresholds crossed during one update.
+pub struct Crossings<'a, V: 'a> {
+    thresholds: &'a [V],
+    index: usize,
+    from: V,
+    to: V,
+}
+
+impl<'a, V> Iterator for Crossings<'a, V>
+    where V: Copy + PartialOrd
+{
+    type Item = Crossing<V>;
+
+    fn next(&mut self) -> Option<Crossing<V>> {
+        while let Some(&threshold) = self.thresholds.get(self.index) {
+            let index = self.index;
+            self.index += 1;
+            if self.from < threshold && threshold <= self.to {
+                return Some(Crossing { index, threshold, rising: true });
+            }
+            if self.from > threshold && threshold >= self.to {
+                return Some(Crossing { index, threshold, rising: false });
+            }
+        }
+        None
+    }
+}
+
+#[test]
+fn thresholds_crossed() {
+    use tween::Tween;
+
+    let mut door = Thresholds::new(Tween::new(0.0f32, 1.0, 1.0), vec![0.5, 1.0]);
+    assert_eq!(door.update(0.25).count(), 0);
+    let crossed: Vec<_> = door.update(0.25).collect();
+    assert_eq!(crossed, vec![Crossing { index: 0, threshold: 0.5, rising: true }]);
+    assert_eq!(door.update(0.25).count(), 0);
+    let crossed: Vec<_> = door.update(1.0).map(|c| c.index).collect();
+    assert_eq!(crossed, vec![1]);
+    assert_eq!(door.update(1.0).count(), 0);
+
+    let mut closing = Thresholds::new(Tween::new(1.0f32, 0.0, 1.0), vec![0.5]);
+    let crossed: Vec<_> = closing.update(1.0).collect();
+    assert_eq!(crossed, vec![Crossing { index: 0, threshold: 0.5, rising: false }]);
+}
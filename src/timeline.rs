@@ -0,0 +1,335 @@
+//! Tweens laid out in time with named markers
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use animate::Animate;
+use ease::Ease;
+use float::Float;
+use lerp::Lerp;
+use tween::Tween;
+
+/// How many times a timeline plays.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub enum Repeat {
+    /// Plays a number of times.
+    Times(u32),
+    /// Loops forever.
+    Forever,
+}
+
+/// Tweens of one value placed at start times, played by a playhead.
+///
+/// Named markers can be placed on the timeline,
+/// to seek, play from or stop at a label.
+///
+/// The state of a timeline depends only on the playhead time,
+/// so seeking to a time reproduces the value and loop count exactly.
+/// Markers are not fired by seeking. To replay the markers passed,
+/// call `markers_between` with the old and the new playhead time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timeline<T: Lerp> {
+    /// How many times the timeline plays.
+    pub repeat: Repeat,
+    clips: Vec<(T::Scalar, Tween<T>)>,
+    markers: Vec<(String, T::Scalar)>,
+    time: T::Scalar,
+    stop: Option<T::Scalar>,
+}
+
+impl<T> Timeline<T>
+    where T: Lerp, T::Scalar: Float + Ease
+{
+    /// Creates an empty timeline.
+    pub fn new() -> Timeline<T> {
+        Timeline {
+            repeat: Repeat::Times(1),
+            clips: Vec::new(),
+            markers: Vec::new(),
+            time: T::Scalar::zero(),
+            stop: None,
+        }
+    }
+
+    /// Places a tween starting at `start`.
+    pub fn insert(&mut self, start: T::Scalar, tween: Tween<T>) {
+        let i = self.clips.iter().position(|&(t, _)| t > start).unwrap_or(self.clips.len());
+        self.clips.insert(i, (start, tween));
+    }
+
+    /// Places a tween starting at the end of the timeline.
+    pub fn push(&mut self, tween: Tween<T>) {
+        let start = self.duration();
+        self.insert(start, tween);
+    }
+
+    /// Returns the tweens with their start times.
+    pub fn clips(&self) -> &[(T::Scalar, Tween<T>)] {
+        &self.clips
+    }
+
+    /// Returns the time when the last tween ends.
+    pub fn duration(&self) -> T::Scalar {
+        self.clips.iter().fold(T::Scalar::zero(), |end, &(start, ref tween)| {
+            let clip_end = start + tween.total_duration();
+            if clip_end > end { clip_end } else { end }
+        })
+    }
+
+    /// Places a named marker at `time`, replacing any marker with the same label.
+    pub fn mark(&mut self, label: &str, time: T::Scalar) {
+        match self.markers.iter_mut().find(|m| m.0 == label) {
+            Some(m) => m.1 = time,
+            None => self.markers.push((label.into(), time)),
+        }
+    }
+
+    /// Returns the time of a marker.
+    pub fn marker(&self, label: &str) -> Option<T::Scalar> {
+        self.markers.iter().find(|m| m.0 == label).map(|m| m.1)
+    }
+
+    /// Returns the markers in the order they were placed.
+    pub fn markers(&self) -> &[(String, T::Scalar)] {
+        &self.markers
+    }
+
+    /// Returns the time of the playhead, including all loops played.
+    pub fn time(&self) -> T::Scalar {
+        self.time
+    }
+
+    /// Returns the total time of all loops,
+    /// or `None` when looping forever.
+    pub fn total_duration(&self) -> Option<T::Scalar> {
+        match self.repeat {
+            Repeat::Times(n) => Some(self.duration() * T::Scalar::from_f64(n as f64)),
+            Repeat::Forever => None,
+        }
+    }
+
+    /// Splits a playhead time into the number of loops completed
+    /// and the time within the current loop.
+    fn split(&self, time: T::Scalar) -> (u32, T::Scalar) {
+        let zero = T::Scalar::zero();
+        let duration = self.duration();
+        if duration <= zero || time <= zero {
+            return (0, if time > zero { duration } else { zero });
+        }
+        if let Repeat::Times(n) = self.repeat {
+            if time >= duration * T::Scalar::from_f64(n as f64) {
+                return (n, duration);
+            }
+        }
+        let loops = (time / duration).floor();
+        (loops.to_f64() as u32, time - loops * duration)
+    }
+
+    /// Returns the number of loops completed.
+    pub fn loop_count(&self) -> u32 {
+        self.split(self.time).0
+    }
+
+    /// Returns the time of the playhead within the current loop.
+    pub fn local_time(&self) -> T::Scalar {
+        self.split(self.time).1
+    }
+
+    /// Moves the playhead to `time`, clamped to the start and the total duration.
+    pub fn seek(&mut self, time: T::Scalar) {
+        let zero = T::Scalar::zero();
+        let time = if time < zero { zero } else { time };
+        self.time = match self.total_duration() {
+            Some(total) if time > total => total,
+            _ => time,
+        };
+    }
+
+    /// Moves the playhead to a marker in the first loop.
+    /// Returns `false` if there is no marker with the label.
+    pub fn seek_label(&mut self, label: &str) -> bool {
+        match self.marker(label) {
+            Some(time) => { self.seek(time); true }
+            None => false,
+        }
+    }
+
+    /// Moves the playhead to a marker in the first loop and plays to the end.
+    /// Returns `false` if there is no marker with the label.
+    pub fn play_from(&mut self, label: &str) -> bool {
+        if self.seek_label(label) {
+            self.stop = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Stops the playhead at the next time it reaches a marker.
+    /// Returns `false` if there is no marker with the label.
+    pub fn stop_at(&mut self, label: &str) -> bool {
+        match self.marker(label) {
+            Some(time) => {
+                let start = self.time - self.local_time();
+                let stop = start + time;
+                self.stop = Some(if stop < self.time {
+                    stop + self.duration()
+                } else {
+                    stop
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the time where the playhead stops,
+    /// or `None` when looping forever.
+    pub fn end(&self) -> Option<T::Scalar> {
+        match (self.stop, self.total_duration()) {
+            (Some(stop), Some(total)) => Some(if stop < total { stop } else { total }),
+            (Some(stop), None) => Some(stop),
+            (None, total) => total,
+        }
+    }
+
+    /// Returns `true` when the playhead has reached the end.
+    pub fn is_finished(&self) -> bool {
+        match self.end() {
+            Some(end) => self.time >= end,
+            None => false,
+        }
+    }
+
+    /// Advances the playhead by `dt`, stopping at the end.
+    pub fn update(&mut self, dt: T::Scalar) {
+        let time = self.time + dt;
+        self.time = match self.end() {
+            Some(end) if self.time >= end => return,
+            Some(end) if time > end => end,
+            _ => time,
+        };
+    }
+
+    /// Returns the labels of the markers passed when moving the playhead
+    /// forwards from `from` to `to`, excluding `from` and including `to`.
+    pub fn markers_between(&self, from: T::Scalar, to: T::Scalar) -> Vec<&str> {
+        let mut labels = vec![];
+        let duration = self.duration();
+        if to <= from || duration <= T::Scalar::zero() {
+            return labels;
+        }
+        let (first, _) = self.split(from);
+        let (last, _) = self.split(to);
+        for i in first..=last {
+            let start = duration * T::Scalar::from_f64(i as f64);
+            let mut markers: Vec<_> = self.markers.iter()
+                .map(|m| (start + m.1, &m.0[..]))
+                .filter(|m| m.0 > from && m.0 <= to)
+                .collect();
+            markers.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(::core::cmp::Ordering::Equal));
+            labels.extend(markers.into_iter().map(|m| m.1));
+        }
+        labels
+    }
+
+    /// Returns the value at playhead time `time`,
+    /// from the last tween started at or before it in the current loop.
+    pub fn value_at(&self, time: T::Scalar) -> Option<T> {
+        let (_, time) = self.split(time);
+        let clip = self.clips.iter().rev().find(|c| c.0 <= time).or_else(|| self.clips.first());
+        clip.map(|&(start, ref tween)| tween.value_at(time - start))
+    }
+
+    /// Returns the value at the playhead.
+    pub fn value(&self) -> Option<T> {
+        self.value_at(self.time)
+    }
+}
+
+impl<T> Default for Timeline<T>
+    where T: Lerp, T::Scalar: Float + Ease
+{
+    fn default() -> Timeline<T> {
+        Timeline::new()
+    }
+}
+
+impl<T> Animate for Timeline<T>
+    where T: Lerp, T::Scalar: Float + Ease
+{
+    type Value = Option<T>;
+    type Time = T::Scalar;
+
+    fn update(&mut self, dt: T::Scalar) {
+        Timeline::update(self, dt)
+    }
+
+    fn value(&self) -> Option<T> {
+        Timeline::value(self)
+    }
+
+    fn is_finished(&self) -> bool {
+        Timeline::is_finished(self)
+    }
+}
+
+#[test]
+fn timeline_markers() {
+    let mut timeline = Timeline::new();
+    timeline.push(Tween::new(0.0f32, 10.0, 1.0));
+    timeline.push(Tween::new(10.0, 0.0, 1.0));
+    timeline.mark("open", 1.0);
+    timeline.mark("half", 1.5);
+    assert_eq!(timeline.duration(), 2.0);
+    assert_eq!(timeline.value(), Some(0.0));
+
+    assert!(timeline.stop_at("open"));
+    timeline.update(0.5);
+    assert_eq!(timeline.value(), Some(5.0));
+    timeline.update(5.0);
+    assert_eq!(timeline.time(), 1.0);
+    assert!(timeline.is_finished());
+
+    assert!(timeline.play_from("half"));
+    assert_eq!(timeline.value(), Some(5.0));
+    timeline.update(5.0);
+    assert_eq!(timeline.value(), Some(0.0));
+
+    assert!(!timeline.seek_label("missing"));
+}
+
+#[test]
+fn timeline_seek_loops() {
+    let mut timeline = Timeline::new();
+    timeline.push(Tween::new(0.0f64, 10.0, 1.0));
+    timeline.repeat = Repeat::Times(3);
+    timeline.mark("half", 0.5);
+
+    timeline.seek(2.25);
+    assert_eq!(timeline.loop_count(), 2);
+    assert_eq!(timeline.local_time(), 0.25);
+    assert_eq!(timeline.value(), Some(2.5));
+    assert_eq!(timeline.markers_between(0.0, 2.25), vec!["half", "half"]);
+
+    timeline.seek(0.25);
+    assert_eq!(timeline.loop_count(), 0);
+    assert_eq!(timeline.value(), Some(2.5));
+
+    timeline.update(10.0);
+    assert!(timeline.is_finished());
+    assert_eq!(timeline.loop_count(), 3);
+    assert_eq!(timeline.value(), Some(10.0));
+
+    timeline.repeat = Repeat::Forever;
+    timeline.seek(1.25);
+    assert!(timeline.stop_at("half"));
+    timeline.update(10.0);
+    assert_eq!(timeline.time(), 1.5);
+    timeline.seek(1.75);
+    assert!(timeline.stop_at("half"));
+    assert_eq!(timeline.end(), Some(2.5));
+}
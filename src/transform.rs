@@ -0,0 +1,104 @@
+//! Rigid body transform interpolation
+
+use ease::{Ease, Easing};
+use float::Float;
+use lerp::Lerp;
+use slerp::Slerp;
+
+/// A translation, rotation and scale.
+///
+/// Translation and scale are interpolated linearly,
+/// while rotation is interpolated along the shortest arc.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct Transform<V, R> {
+    /// The translation.
+    pub translation: V,
+    /// The rotation.
+    pub rotation: R,
+    /// The scale.
+    pub scale: V,
+}
+
+impl<V, R> Lerp for Transform<V, R>
+    where V: Lerp, R: Slerp<Scalar = V::Scalar>
+{
+    type Scalar = V::Scalar;
+
+    #[inline(always)]
+    fn lerp(&self, other: &Self, scalar: &Self::Scalar) -> Self {
+        Transform {
+            translation: self.translation.lerp(&other.translation, scalar),
+            rotation: self.rotation.slerp(&other.rotation, scalar),
+            scale: self.scale.lerp(&other.scale, scalar),
+        }
+    }
+}
+
+/// Interpolates between two transforms with an independent easing per channel.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+pub struct TransformTween<V, R> {
+    /// The transform at the start.
+    pub from: Transform<V, R>,
+    /// The transform at the end.
+    pub to: Transform<V, R>,
+    /// The easing of the translation.
+    pub translation: Easing,
+    /// The easing of the rotation.
+    pub rotation: Easing,
+    /// The easing of the scale.
+    pub scale: Easing,
+}
+
+impl<V, R> TransformTween<V, R>
+    where V: Lerp, R: Slerp<Scalar = V::Scalar>, V::Scalar: Ease + Float
+{
+    /// Creates a new tween with linear easing on all channels.
+    pub fn new(from: Transform<V, R>, to: Transform<V, R>) -> TransformTween<V, R> {
+        TransformTween {
+            from,
+            to,
+            translation: Easing::Linear,
+            rotation: Easing::Linear,
+            scale: Easing::Linear,
+        }
+    }
+
+    /// Calculates the transform at normalized progress `t`.
+    pub fn sample(&self, t: &V::Scalar) -> Transform<V, R> {
+        Transform {
+            translation: self.from.translation.lerp(&self.to.translation,
+                                                    &self.translation.calc(*t)),
+            rotation: self.from.rotation.slerp(&self.to.rotation,
+                                               &self.rotation.calc(*t)),
+            scale: self.from.scale.lerp(&self.to.scale, &self.scale.calc(*t)),
+        }
+    }
+}
+
+#[test]
+fn transform_tween_channels() {
+    use ease::EaseFunction;
+
+    let from = Transform {
+        translation: [0.0, 0.0, 0.0],
+        rotation: [0.0, 0.0, 0.0, 1.0],
+        scale: [1.0, 1.0, 1.0],
+    };
+    let to = Transform {
+        translation: [10.0, 0.0, 0.0],
+        rotation: [0.0, 0.0, 1.0, 0.0],
+        scale: [2.0, 2.0, 2.0],
+    };
+    let mut tween = TransformTween::new(from, to);
+    tween.scale = Easing::Function(EaseFunction::QuadraticIn);
+
+    assert_eq!(tween.sample(&0.0f64), from);
+    assert_eq!(tween.sample(&1.0f64), to);
+
+    let mid = tween.sample(&0.5f64);
+    assert_eq!(mid.translation, [5.0, 0.0, 0.0]);
+    assert_eq!(mid.scale, [1.25, 1.25, 1.25]);
+    assert!((mid.rotation[2] - (0.5f64).sqrt()).abs() < 1e-12);
+}
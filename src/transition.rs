@@ -0,0 +1,148 @@
+//! Replacing running tweens without popping
+
+use animate::Animate;
+use ease::Ease;
+use float::Float;
+use lerp::Lerp;
+use tween::Tween;
+
+/// Drives a value with a tween that can be replaced while running.
+///
+/// A replacement can either cut to the new tween immediately,
+/// or keep the old tween running and blend it out over a short window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition<T: Lerp> {
+    current: Tween<T>,
+    outgoing: Option<Tween<T>>,
+    blend_elapsed: T::Scalar,
+    blend_duration: T::Scalar,
+}
+
+impl<T> Transition<T>
+    where T: Lerp + Clone, T::Scalar: Float + Ease
+{
+    /// Creates a new transition running `tween`.
+    pub fn new(tween: Tween<T>) -> Transition<T> {
+        Transition {
+            current: tween,
+            outgoing: None,
+            blend_elapsed: T::Scalar::zero(),
+            blend_duration: T::Scalar::zero(),
+        }
+    }
+
+    /// Replaces the running tween immediately.
+    pub fn cut(&mut self, tween: Tween<T>) {
+        self.current = tween;
+        self.outgoing = None;
+    }
+
+    /// Replaces the running tween, blending the old one out over `duration`.
+    ///
+    /// When a blend is already in progress, the blended value is frozen
+    /// and used as the outgoing value, so the output stays continuous.
+    pub fn blend(&mut self, tween: Tween<T>, duration: T::Scalar) {
+        if duration <= T::Scalar::zero() {
+            return self.cut(tween);
+        }
+
+        let old = if self.outgoing.is_some() {
+            let value = self.value();
+            Tween::new(value.clone(), value, T::Scalar::zero())
+        } else {
+            self.current.clone()
+        };
+        self.current = tween;
+        self.outgoing = Some(old);
+        self.blend_elapsed = T::Scalar::zero();
+        self.blend_duration = duration;
+    }
+
+    /// Advances the running tweens by `dt`.
+    pub fn update(&mut self, dt: T::Scalar) {
+        self.current.update(dt);
+        if let Some(ref mut old) = self.outgoing {
+            old.update(dt);
+            self.blend_elapsed = self.blend_elapsed + dt;
+        }
+        if self.outgoing.is_some() && self.blend_elapsed >= self.blend_duration {
+            self.outgoing = None;
+        }
+    }
+
+    /// Returns the running tween.
+    pub fn current(&self) -> &Tween<T> {
+        &self.current
+    }
+
+    /// Returns `true` while an old tween is being blended out.
+    pub fn is_blending(&self) -> bool {
+        self.outgoing.is_some()
+    }
+
+    /// Returns `true` when the running tween is finished and nothing is blending.
+    pub fn is_finished(&self) -> bool {
+        self.outgoing.is_none() && self.current.is_finished()
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> T {
+        match self.outgoing {
+            Some(ref old) => {
+                let w = self.blend_elapsed / self.blend_duration;
+                old.value().lerp(&self.current.value(), &w)
+            }
+            None => self.current.value(),
+        }
+    }
+}
+
+impl<T> Animate for Transition<T>
+    where T: Lerp + Clone, T::Scalar: Float + Ease
+{
+    type Value = T;
+    type Time = T::Scalar;
+
+    fn update(&mut self, dt: T::Scalar) {
+        Transition::update(self, dt)
+    }
+
+    fn value(&self) -> T {
+        Transition::value(self)
+    }
+
+    fn is_finished(&self) -> bool {
+        Transition::is_finished(self)
+    }
+}
+
+#[test]
+fn transition_blend() {
+    let mut transition = Transition::new(Tween::new(0.0f32, 10.0, 10.0));
+    transition.update(5.0);
+    assert_eq!(transition.value(), 5.0);
+
+    transition.blend(Tween::new(20.0, 20.0, 1.0), 2.0);
+    assert!(transition.is_blending());
+    assert_eq!(transition.value(), 5.0);
+    transition.update(1.0);
+    // Old tween is at 6.0, new is at 20.0, halfway through the blend.
+    assert_eq!(transition.value(), 13.0);
+    transition.update(1.0);
+    assert!(!transition.is_blending());
+    assert_eq!(transition.value(), 20.0);
+}
+
+#[test]
+fn transition_blend_during_blend() {
+    let mut transition = Transition::new(Tween::new(0.0f64, 0.0, 1.0));
+    transition.blend(Tween::new(10.0, 10.0, 1.0), 2.0);
+    transition.update(1.0);
+    assert_eq!(transition.value(), 5.0);
+    transition.blend(Tween::new(0.0, 0.0, 1.0), 1.0);
+    assert_eq!(transition.value(), 5.0);
+
+    transition.cut(Tween::new(3.0, 3.0, 1.0));
+    assert!(!transition.is_blending());
+    assert_eq!(transition.value(), 3.0);
+}
@@ -0,0 +1,237 @@
+//! Time driven interpolation between two values
+
+use animate::Animate;
+use ease::{Ease, Easing};
+use float::Float;
+use lerp::Lerp;
+use progress::ProgressMode;
+
+/// Interpolates from one value to another over a duration.
+///
+/// An optional delay holds the start value before the tween begins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tween<T: Lerp> {
+    /// The value at the start.
+    pub from: T,
+    /// The value at the end.
+    pub to: T,
+    /// The time it takes to reach the end, not counting the delay.
+    pub duration: T::Scalar,
+    /// The time to wait before starting.
+    pub delay: T::Scalar,
+    /// The easing applied to progress.
+    pub easing: Easing,
+    /// How progress past the end is mapped, when evaluating at a later time.
+    pub progress_mode: ProgressMode,
+    elapsed: T::Scalar,
+}
+
+impl<T> Tween<T>
+    where T: Lerp, T::Scalar: Float + Ease
+{
+    /// Creates a new tween with linear easing and no delay.
+    pub fn new(from: T, to: T, duration: T::Scalar) -> Tween<T> {
+        Tween {
+            from,
+            to,
+            duration,
+            delay: T::Scalar::zero(),
+            easing: Easing::Linear,
+            progress_mode: ProgressMode::Clamp,
+            elapsed: T::Scalar::zero(),
+        }
+    }
+
+    /// Sets the easing.
+    pub fn easing<E: Into<Easing>>(mut self, easing: E) -> Tween<T> {
+        self.easing = easing.into();
+        self
+    }
+
+    /// Sets how progress past the end is mapped.
+    ///
+    /// With `Wrap` or `Mirror`, `value_at` loops after the duration.
+    /// Updating still stops at the end, where progress is one.
+    pub fn progress_mode(mut self, mode: ProgressMode) -> Tween<T> {
+        self.progress_mode = mode;
+        self
+    }
+
+    /// Sets the delay.
+    pub fn delay(mut self, delay: T::Scalar) -> Tween<T> {
+        self.delay = delay;
+        self
+    }
+
+    /// Returns the delay plus the duration.
+    pub fn total_duration(&self) -> T::Scalar {
+        self.delay + self.duration
+    }
+
+    /// Advances the tween by `dt`.
+    pub fn update(&mut self, dt: T::Scalar) {
+        let total = self.total_duration();
+        let elapsed = self.elapsed + dt;
+        self.elapsed = if elapsed > total { total } else { elapsed };
+    }
+
+    /// Moves to `time` since the start, clamped to the total duration.
+    pub fn seek(&mut self, time: T::Scalar) {
+        let zero = T::Scalar::zero();
+        let total = self.total_duration();
+        self.elapsed = if time < zero {
+            zero
+        } else if time > total {
+            total
+        } else {
+            time
+        };
+    }
+
+    /// Returns the time elapsed since the start, including the delay.
+    pub fn elapsed(&self) -> T::Scalar {
+        self.elapsed
+    }
+
+    /// Returns the linear progress between zero and one at `time` since the start.
+    fn progress_at(&self, time: T::Scalar) -> T::Scalar {
+        let zero = T::Scalar::zero();
+        let time = time - self.delay;
+        if self.duration <= zero || time == self.duration {
+            T::Scalar::one()
+        } else if time <= zero {
+            zero
+        } else if time < self.duration {
+            time / self.duration
+        } else {
+            self.progress_mode.apply(time / self.duration)
+        }
+    }
+
+    /// Returns the linear progress between zero and one.
+    pub fn progress(&self) -> T::Scalar {
+        self.progress_at(self.elapsed)
+    }
+
+    /// Returns `true` when the end has been reached.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.total_duration()
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> T {
+        self.value_at(self.elapsed)
+    }
+
+    /// Returns the value at `time` since the start, without changing the tween.
+    pub fn value_at(&self, time: T::Scalar) -> T {
+        self.from.lerp(&self.to, &self.easing.calc(self.progress_at(time)))
+    }
+}
+
+impl<T> Animate for Tween<T>
+    where T: Lerp, T::Scalar: Float + Ease
+{
+    type Value = T;
+    type Time = T::Scalar;
+
+    fn update(&mut self, dt: T::Scalar) {
+        Tween::update(self, dt)
+    }
+
+    fn value(&self) -> T {
+        Tween::value(self)
+    }
+
+    fn is_finished(&self) -> bool {
+        Tween::is_finished(self)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> ::arbitrary::Arbitrary<'a> for Tween<T>
+    where T: Lerp + ::arbitrary::Arbitrary<'a>, T::Scalar: Float + Ease + ::arbitrary::Arbitrary<'a>
+{
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Tween<T>> {
+        let mut tween = Tween::new(u.arbitrary()?, u.arbitrary()?, u.arbitrary()?)
+            .delay(u.arbitrary()?)
+            .easing(u.arbitrary::<Easing>()?)
+            .progress_mode(u.arbitrary()?);
+        tween.update(u.arbitrary()?);
+        Ok(tween)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T> ::defmt::Format for Tween<T>
+    where T: Lerp + ::defmt::Format, T::Scalar: ::defmt::Format
+{
+    fn format(&self, f: ::defmt::Formatter) {
+        ::defmt::write!(f, "Tween {{ from: {}, to: {}, duration: {}, delay: {}, easing: {}, progress_mode: {}, elapsed: {} }}",
+            self.from, self.to, self.duration, self.delay, self.easing, self.progress_mode, self.elapsed)
+    }
+}
+
+#[test]
+fn tween_update() {
+    use ease::EaseFunction;
+
+    let mut tween = Tween::new(0.0f32, 10.0, 2.0);
+    assert_eq!(tween.value(), 0.0);
+    tween.update(1.0);
+    assert_eq!(tween.value(), 5.0);
+    assert!(!tween.is_finished());
+    tween.update(5.0);
+    assert_eq!(tween.value(), 10.0);
+    assert!(tween.is_finished());
+    tween.seek(0.5);
+    assert_eq!(tween.value(), 2.5);
+    assert!(!tween.is_finished());
+
+    let mut tween = Tween::new(0.0f64, 8.0, 1.0).easing(EaseFunction::QuadraticIn);
+    tween.update(0.5);
+    assert_eq!(tween.value(), 2.0);
+}
+
+#[test]
+fn tween_delay() {
+    let mut tween = Tween::new(0.0f32, 10.0, 1.0).delay(0.5);
+    assert_eq!(tween.total_duration(), 1.5);
+    tween.update(0.5);
+    assert_eq!(tween.value(), 0.0);
+    tween.update(0.5);
+    assert_eq!(tween.value(), 5.0);
+    tween.update(0.5);
+    assert!(tween.is_finished());
+    assert_eq!(tween.value_at(0.25), 0.0);
+}
+
+#[test]
+fn tween_progress_mode() {
+    let tween = Tween::new(0.0f32, 10.0, 1.0).progress_mode(ProgressMode::Mirror);
+    assert_eq!(tween.value_at(1.0), 10.0);
+    assert_eq!(tween.value_at(1.25), 7.5);
+    assert_eq!(tween.value_at(2.25), 2.5);
+    let tween = tween.progress_mode(ProgressMode::Wrap);
+    assert_eq!(tween.value_at(1.25), 2.5);
+    let mut tween = tween.progress_mode(ProgressMode::Clamp);
+    assert_eq!(tween.value_at(1.25), 10.0);
+    tween.update(5.0);
+    assert_eq!(tween.value(), 10.0);
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn tween_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use step_track::StepTrack;
+
+    let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..32 {
+        let tween = Tween::<f32>::arbitrary(&mut u).unwrap();
+        let _ = tween.value();
+    }
+    let track = StepTrack::<u8, u16>::arbitrary(&mut u).unwrap();
+    assert!(track.keys().windows(2).all(|w| w[0].0 < w[1].0));
+}
@@ -0,0 +1,132 @@
+//! Import of Unity animation curves
+//!
+//! Unity serializes an `AnimationCurve` as a list of keyframes under `m_Curve`,
+//! in scene and asset YAML as well as in `JsonUtility` output.
+//! With the `serde` feature, `UnityCurve` can be read from either format
+//! with a serde YAML or JSON parser.
+
+use alloc::vec::Vec;
+
+use curve::{AnimationCurve, CurveKey};
+
+/// A keyframe of a serialized Unity `AnimationCurve`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct UnityKeyframe {
+    /// The time of the key.
+    pub time: f32,
+    /// The value at the key.
+    pub value: f32,
+    /// The slope arriving at the key, infinite for a stepped key.
+    #[cfg_attr(feature = "serde", serde(alias = "inTangent", deserialize_with = "de::slope"))]
+    pub in_slope: f32,
+    /// The slope leaving the key, infinite for a stepped key.
+    #[cfg_attr(feature = "serde", serde(alias = "outTangent", deserialize_with = "de::slope"))]
+    pub out_slope: f32,
+    /// Which weights are used: `1` for in, `2` for out and `3` for both.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weighted_mode: u8,
+    /// The weight of the in handle.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub in_weight: f32,
+    /// The weight of the out handle.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub out_weight: f32,
+}
+
+impl From<UnityKeyframe> for CurveKey {
+    fn from(key: UnityKeyframe) -> CurveKey {
+        CurveKey {
+            time: key.time,
+            value: key.value,
+            in_tangent: key.in_slope,
+            out_tangent: key.out_slope,
+            in_weight: if key.weighted_mode & 1 != 0 { Some(key.in_weight) } else { None },
+            out_weight: if key.weighted_mode & 2 != 0 { Some(key.out_weight) } else { None },
+        }
+    }
+}
+
+/// A serialized Unity `AnimationCurve`.
+///
+/// Only the keys are read. Unity's pre and post infinity modes are ignored,
+/// and the converted curve holds its end values.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+pub struct UnityCurve {
+    /// The keyframes.
+    #[cfg_attr(feature = "serde", serde(rename = "m_Curve", alias = "keys"))]
+    pub keys: Vec<UnityKeyframe>,
+}
+
+impl UnityCurve {
+    /// Converts the keys to an `AnimationCurve`.
+    pub fn to_curve(&self) -> AnimationCurve {
+        unity_curve(&self.keys)
+    }
+}
+
+/// Converts Unity keyframes to an `AnimationCurve`.
+pub fn unity_curve(keys: &[UnityKeyframe]) -> AnimationCurve {
+    AnimationCurve::new(keys.iter().map(|&k| k.into()).collect())
+}
+
+#[cfg(feature = "serde")]
+mod de {
+    use alloc::string::String;
+
+    use serde::{Deserialize, Deserializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Slope {
+        Number(f32),
+        Text(String),
+    }
+
+    /// Reads a slope, accepting the `Infinity` text Unity writes for stepped keys.
+    pub fn slope<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        match Slope::deserialize(deserializer)? {
+            Slope::Number(v) => Ok(v),
+            Slope::Text(s) => match s.trim_start_matches('+') {
+                "Infinity" | "inf" | ".inf" => Ok(f32::INFINITY),
+                "-Infinity" | "-inf" | "-.inf" => Ok(f32::NEG_INFINITY),
+                s => s.parse().map_err(::serde::de::Error::custom),
+            },
+        }
+    }
+}
+
+#[test]
+fn unity_weighted_keys() {
+    let keys = [
+        UnityKeyframe {
+            time: 0.0, value: 0.0, in_slope: 0.0, out_slope: 0.0,
+            weighted_mode: 2, in_weight: 0.0, out_weight: 0.5,
+        },
+        UnityKeyframe {
+            time: 1.0, value: 1.0, in_slope: 0.0, out_slope: 0.0,
+            weighted_mode: 0, in_weight: 0.9, out_weight: 0.9,
+        },
+    ];
+    let curve = unity_curve(&keys);
+    assert_eq!(curve.keys()[0].out_weight, Some(0.5));
+    assert_eq!(curve.keys()[1].in_weight, None);
+    assert_eq!(curve.sample(1.0), Some(1.0));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn unity_json() {
+    let json = r#"{"serializedVersion": "2", "m_Curve": [
+        {"serializedVersion": "3", "time": 0.0, "value": 0.0, "inSlope": 0.0, "outSlope": "Infinity",
+         "tangentMode": 0, "weightedMode": 0, "inWeight": 0.0, "outWeight": 0.0},
+        {"serializedVersion": "3", "time": 2.0, "value": 4.0, "inSlope": 0.0, "outSlope": 0.0,
+         "tangentMode": 0, "weightedMode": 0, "inWeight": 0.0, "outWeight": 0.0}
+    ], "m_PreInfinity": 2, "m_PostInfinity": 2, "m_RotationOrder": 4}"#;
+    let curve: UnityCurve = serde_json::from_str(json).unwrap();
+    let curve = curve.to_curve();
+    assert_eq!(curve.sample(1.0), Some(0.0));
+    assert_eq!(curve.sample(2.0), Some(4.0));
+}
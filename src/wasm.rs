@@ -0,0 +1,59 @@
+//! Thin wrappers exposing the curve math to JavaScript
+//!
+//! Web front-ends calling these get the same results as the Rust code,
+//! instead of reimplementing the curves.
+
+use wasm_bindgen::prelude::*;
+
+use bake::BakedTrack;
+use ease::{Ease, EaseFunction};
+
+/// Evaluates an ease function by name at `t`.
+///
+/// See `EaseFunction::from_name` for the accepted names.
+#[wasm_bindgen]
+pub fn ease(name: &str, t: f64) -> Result<f64, JsValue> {
+    match EaseFunction::from_name(name) {
+        Some(f) => Ok(t.calc(f)),
+        None => Err(JsValue::from_str(&format!("unknown ease function `{}`", name))),
+    }
+}
+
+/// Evaluates a cubic Bézier curve through `x0` and `x3`, with control points `x1` and `x2`.
+#[wasm_bindgen(js_name = cubicBezier)]
+pub fn cubic_bezier(x0: f64, x1: f64, x2: f64, x3: f64, t: f64) -> f64 {
+    ::cub_bez(&x0, &x1, &x2, &x3, &t)
+}
+
+/// A baked track, decoded once from the bytes produced by the `bincode` feature.
+#[wasm_bindgen]
+pub struct Track {
+    track: BakedTrack<f32>,
+}
+
+#[wasm_bindgen]
+impl Track {
+    /// Decodes a `BakedTrack<f32>` encoded with bincode's standard configuration.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Track, JsValue> {
+        bincode::decode_from_slice(bytes, bincode::config::standard())
+            .map(|(track, _)| Track { track })
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    /// Returns the value at `time`, or `NaN` if the track is empty.
+    pub fn sample(&self, time: f32) -> f32 {
+        self.track.sample(time).unwrap_or(f32::NAN)
+    }
+}
+
+#[test]
+fn wasm_wrappers() {
+    assert_eq!(ease("cubicIn", 0.5).ok(), Some(0.125));
+    assert_eq!(cubic_bezier(0.0, 1.0, 2.0, 3.0, 0.5), 1.5);
+
+    let track = BakedTrack::bake(0.0, 1.0, 3, |t| t * 2.0);
+    let bytes = bincode::encode_to_vec(&track, bincode::config::standard()).unwrap();
+    let track = Track::from_bytes(&bytes).ok().unwrap();
+    assert_eq!(track.sample(1.5), 3.0);
+}